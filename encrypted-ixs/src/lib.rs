@@ -171,9 +171,15 @@ mod circuits {
     ) -> Enc<Shared, ReputationScore> {
         let m = metrics.to_arcis();
         
-        // Base score from success rate
+        // Base score from success rate, computed in u128 so the counts are
+        // neither truncated nor overflow
+        let successful = if m.successful > m.total_executed {
+            m.total_executed
+        } else {
+            m.successful
+        };
         let success_rate = if m.total_executed > 0 {
-            (m.successful as u32 * 1000) / m.total_executed as u32
+            ((successful as u128 * 1000) / m.total_executed as u128) as u32
         } else {
             500 // Default middle score for new solvers
         };
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
 use arcium_anchor::prelude::*;
+use silence_intent::{CrossChainIntent, DestinationAmount, FulfillmentReceipt};
 
 // Computation definition offsets for encrypted instructions
 const COMP_DEF_OFFSET_PROCESS_INFERENCE: u32 = comp_def_offset("process_inference");
@@ -11,10 +13,251 @@ const COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF: u32 = comp_def_offset("generate_pr
 
 declare_id!("2oFwMgL8qEUN14w6DhJ4jdbccG1FFrosKqH8CVjiN1i2");
 
+// Well-known chain ids (each chain still needs a `ChainConfig` registered by the admin)
+pub const CHAIN_ID_SOLANA: u32 = 1;
+pub const CHAIN_ID_NEAR: u32 = 2;
+pub const CHAIN_ID_ZCASH: u32 = 3;
+
+/// Max chains a single solver can support
+pub const MAX_SOLVER_CHAINS: usize = 16;
+
+/// Fixed-point scale for `FeeVault.reward_per_stake`
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Upper bound of `Solver.reputation_score`
+pub const MAX_REPUTATION: u32 = 1000;
+
+/// Max length of a solver metadata URI
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// Max relayers attesting fulfillment receipts
+pub const MAX_RELAYERS: usize = 16;
+
+/// Maximum fills settled by one `settle_intents_batch` (transaction size bound)
+pub const MAX_SETTLE_BATCH: usize = 8;
+
+/// Max delegates allowed to update a model
+pub const MAX_MODEL_DELEGATES: usize = 8;
+
+/// Max operators allowed to process a model's inferences
+pub const MAX_MODEL_OPERATORS: usize = 8;
+
+/// Models listed per `ModelIndex` page
+pub const MODEL_INDEX_PAGE_SIZE: usize = 32;
+
 // ============================================================================
 // STATE ACCOUNTS
 // ============================================================================
 
+/// Layout version stored as the last field (`layout_version`) of every account.
+///
+/// Accounts created before versioning have no version byte. It decodes as
+/// `LEGACY` when the data ends first and is not written back while the
+/// account is legacy, so those accounts keep working at their old size
+/// until a `migrate_*` instruction reallocates them. The exception is an
+/// account reused through `init_if_needed` (`UserMetrics`, `RelayerSet`,
+/// `ReceiptAttestation`): Anchor checks its size against the current layout
+/// before the handler runs, so it must be migrated first.
+///
+/// Each account type counts its own versions, see [`Versioned`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AccountVersion(pub u8);
+
+impl AccountVersion {
+    /// Layout without a version byte
+    pub const LEGACY: Self = Self(0);
+    /// Highest version of any account type; later ones fail to decode
+    pub const LATEST: Self = Self(2);
+
+    /// Whether an account just created by `init_if_needed` still needs its
+    /// fields set. Its zeroed data decodes as `LEGACY`, so this only holds
+    /// for types created after versioning, which never had a legacy layout.
+    pub fn is_uninitialized(self) -> bool {
+        self == Self::LEGACY
+    }
+}
+
+impl AnchorSerialize for AccountVersion {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if *self == Self::LEGACY {
+            return Ok(());
+        }
+        writer.write_all(&[self.0])
+    }
+}
+
+impl AnchorDeserialize for AccountVersion {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut byte = [0u8; 1];
+        if reader.read(&mut byte)? == 0 {
+            return Ok(Self::LEGACY);
+        }
+        if byte[0] > Self::LATEST.0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unsupported account version",
+            ));
+        }
+        Ok(Self(byte[0]))
+    }
+}
+
+impl anchor_lang::Space for AccountVersion {
+    const INIT_SPACE: usize = 1;
+}
+
+#[cfg(feature = "idl-build")]
+impl anchor_lang::IdlBuild for AccountVersion {
+    fn create_type() -> Option<anchor_lang::idl::types::IdlTypeDef> {
+        use anchor_lang::idl::types::*;
+        Some(IdlTypeDef {
+            name: Self::get_full_path(),
+            docs: vec!["Layout version, absent on legacy accounts".into()],
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Tuple(vec![IdlType::U8])),
+            },
+        })
+    }
+}
+
+/// Account type with a versioned layout
+pub trait Versioned {
+    /// Version of the layout this program creates and migrates to
+    const LAYOUT_VERSION: AccountVersion;
+}
+
+macro_rules! layout_versions {
+    ($($version:literal => $($account:ident),+;)*) => {$($(
+        impl Versioned for $account {
+            const LAYOUT_VERSION: AccountVersion = AccountVersion($version);
+        }
+    )+)*};
+}
+
+// Version 2 appended `model_version` to `InferenceRequest` and `BatchInference`,
+// `recipient_hash` to `ReceiptAttestation`, and `previous_window_volume` to
+// `ChainConfig` and `Solver`
+layout_versions! {
+    1 => BridgeConfig, FeeVault, RelayerSet, ModelMetadata, ModelAccess, ModelOperators, ModelVersion,
+        ModelDirectory, ModelIndex, ModelListing, CreditAccount, SubscriptionPlan, Subscription, UserMetrics,
+        Intent, IntentFill;
+    2 => InferenceRequest, BatchInference, ReceiptAttestation, ChainConfig, Solver;
+}
+
+/// `ModelVersion` a request or batch was created against, if any.
+///
+/// Appended after `layout_version`, so requests created before version 2
+/// end before it and decode as unpinned. Unpinned is not written back,
+/// which keeps those requests at their old size; the pin is only set
+/// when an account is created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VersionPin(pub Option<Pubkey>);
+
+impl AnchorSerialize for VersionPin {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.0 {
+            Some(address) => Some(address).serialize(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl AnchorDeserialize for VersionPin {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut tag = [0u8; 1];
+        if reader.read(&mut tag)? == 0 || tag[0] == 0 {
+            return Ok(Self(None));
+        }
+        if tag[0] != 1 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid version pin",
+            ));
+        }
+        Ok(Self(Some(Pubkey::deserialize_reader(reader)?)))
+    }
+}
+
+impl anchor_lang::Space for VersionPin {
+    const INIT_SPACE: usize = 1 + 32;
+}
+
+#[cfg(feature = "idl-build")]
+impl anchor_lang::IdlBuild for VersionPin {
+    fn create_type() -> Option<anchor_lang::idl::types::IdlTypeDef> {
+        use anchor_lang::idl::types::*;
+        Some(IdlTypeDef {
+            name: Self::get_full_path(),
+            docs: vec!["`ModelVersion` address, absent on requests before layout version 2".into()],
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Tuple(vec![IdlType::Option(Box::new(IdlType::Pubkey))])),
+            },
+        })
+    }
+}
+
+/// `FulfillmentReceipt::recipient_hash` of an attested receipt.
+///
+/// Appended after `layout_version` like `VersionPin`: receipts attested
+/// before version 2 end before it, decode as unknown and can't be consumed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReceiptRecipient(pub Option<[u8; 32]>);
+
+impl AnchorSerialize for ReceiptRecipient {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.0 {
+            Some(recipient_hash) => Some(recipient_hash).serialize(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl AnchorDeserialize for ReceiptRecipient {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut tag = [0u8; 1];
+        if reader.read(&mut tag)? == 0 || tag[0] == 0 {
+            return Ok(Self(None));
+        }
+        if tag[0] != 1 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid receipt recipient",
+            ));
+        }
+        Ok(Self(Some(<[u8; 32]>::deserialize_reader(reader)?)))
+    }
+}
+
+impl anchor_lang::Space for ReceiptRecipient {
+    const INIT_SPACE: usize = 1 + 32;
+}
+
+#[cfg(feature = "idl-build")]
+impl anchor_lang::IdlBuild for ReceiptRecipient {
+    fn create_type() -> Option<anchor_lang::idl::types::IdlTypeDef> {
+        use anchor_lang::idl::types::*;
+        Some(IdlTypeDef {
+            name: Self::get_full_path(),
+            docs: vec!["Recipient hash, absent on receipts before layout version 2".into()],
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Tuple(vec![IdlType::Option(Box::new(IdlType::Array(
+                    Box::new(IdlType::U8),
+                    IdlArrayLen::Value(32),
+                )))])),
+            },
+        })
+    }
+}
+
 /// Model metadata stored on-chain
 #[account]
 #[derive(InitSpace)]
@@ -48,13 +291,245 @@ pub struct ModelMetadata {
     pub is_active: bool,
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl ModelMetadata {
+    /// Address paid for inferences: the payout set in `access`, else the owner
+    pub fn payout(&self, access: Option<&ModelAccess>) -> Pubkey {
+        access.and_then(|access| access.payout).unwrap_or(self.owner)
+    }
+
+    /// Whether `key` may update the model (its owner or a delegate)
+    pub fn is_manager(&self, key: &Pubkey, access: Option<&ModelAccess>) -> bool {
+        *key == self.owner || access.is_some_and(|access| access.delegates.contains(key))
+    }
+}
+
+/// Ownership transfer, delegates and payout of a model.
+///
+/// Created by the owner on first use; models without one are managed and
+/// paid by their owner alone.
+#[account]
+#[derive(InitSpace)]
+pub struct ModelAccess {
+    /// Model these settings belong to
+    pub model_id: u64,
+    /// Proposed owner, who must accept before ownership moves
+    pub pending_owner: Option<Pubkey>,
+    /// Keys allowed to update the model besides the owner
+    #[max_len(MAX_MODEL_DELEGATES)]
+    pub delegates: Vec<Pubkey>,
+    /// Receives inference payments instead of the owner
+    pub payout: Option<Pubkey>,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl ModelAccess {
+    /// Decode the `model_access` PDA of a model, `None` if it was never created
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        load_account(info)
+    }
+}
+
+/// Decode an account that may not have been created, `None` if it was not
+pub fn load_account<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
+
+/// Keys that process a model's inferences and report batch results.
+///
+/// Managed by the owner and delegates; created on first use.
+#[account]
+#[derive(InitSpace)]
+pub struct ModelOperators {
+    /// Model these operators serve
+    pub model_id: u64,
+    /// Keys allowed to record results for batches on the model
+    #[max_len(MAX_MODEL_OPERATORS)]
+    pub operators: Vec<Pubkey>,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// Published release of a model; never changes except for deprecation
+#[account]
+#[derive(InitSpace)]
+pub struct ModelVersion {
+    /// Model this release belongs to
+    pub model_id: u64,
+    /// Version string (max 16 chars), unique per model
+    #[max_len(16)]
+    pub version: String,
+    /// Commitment to the released weights
+    pub weights_commitment: [u8; 32],
+    /// Hash of the release notes
+    pub changelog_hash: [u8; 32],
+    /// Key that published the release
+    pub publisher: Pubkey,
+    /// Release timestamp
+    pub released_at: i64,
+    /// Deprecation timestamp; deprecated versions take no new requests
+    pub deprecated_at: Option<i64>,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// Number of models in the discovery index
+#[account]
+#[derive(InitSpace)]
+pub struct ModelDirectory {
+    /// Models listed so far; the next one goes to page `model_count / MODEL_INDEX_PAGE_SIZE`
+    pub model_count: u64,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl ModelDirectory {
+    /// Page the next listed model goes to
+    pub fn next_page(&self) -> u32 {
+        (self.model_count / MODEL_INDEX_PAGE_SIZE as u64) as u32
+    }
+}
+
+/// Filterable fields of a listed model
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ModelSummary {
+    pub model_id: u64,
+    pub model_type: u8,
+    pub tee_required: bool,
+    pub is_active: bool,
+    pub cost_per_inference: u64,
+}
+
+impl From<&ModelMetadata> for ModelSummary {
+    fn from(model: &ModelMetadata) -> Self {
+        Self {
+            model_id: model.model_id,
+            model_type: model.model_type,
+            tee_required: model.tee_required,
+            is_active: model.is_active,
+            cost_per_inference: model.cost_per_inference,
+        }
+    }
+}
+
+/// One page of the model discovery index, in listing order
+#[account]
+#[derive(InitSpace)]
+pub struct ModelIndex {
+    /// Page number (PDA seed)
+    pub page: u32,
+    /// Listed models, kept in step with `register_model` and `update_model`
+    #[max_len(MODEL_INDEX_PAGE_SIZE)]
+    pub entries: Vec<ModelSummary>,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// Where a model is listed; its existence keeps a model from being listed twice
+#[account]
+#[derive(InitSpace)]
+pub struct ModelListing {
+    /// Listed model
+    pub model_id: u64,
+    /// `ModelIndex` page holding the model's summary
+    pub page: u32,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// Prepaid inference credits of a user with one payee (a model's payout address)
+#[account]
+#[derive(InitSpace)]
+pub struct CreditAccount {
+    /// User the credits belong to
+    pub user: Pubkey,
+    /// Payout address of the models the credits pay for
+    pub provider: Pubkey,
+    /// Lamports left to spend
+    pub balance: u64,
+    /// Lamports spent and not yet claimed by the provider
+    pub earned: u64,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// Fixed-price bundle of inferences per epoch, offered on a model
+#[account]
+#[derive(InitSpace)]
+pub struct SubscriptionPlan {
+    pub model_id: u64,
+    /// Plan number (PDA seed)
+    pub plan_id: u8,
+    /// Inferences a subscriber may request per epoch
+    pub inferences_per_epoch: u32,
+    /// Lamports paid to the model's payout address per epoch
+    pub price: u64,
+    /// Inactive plans take no new subscriptions
+    pub is_active: bool,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// A user's subscription to a model, valid for one epoch at a time
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub user: Pubkey,
+    pub model_id: u64,
+    /// Plan paid for in `epoch`
+    pub plan_id: u8,
+    /// Epoch the subscription covers
+    pub epoch: u64,
+    /// Quota of the plan when it was paid for
+    pub inferences_per_epoch: u32,
+    /// Inferences used in `epoch`
+    pub used: u32,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl Subscription {
+    /// Inferences left in `epoch`
+    pub fn remaining(&self, epoch: u64) -> u32 {
+        if self.epoch == epoch {
+            self.inferences_per_epoch.saturating_sub(self.used)
+        } else {
+            0
+        }
+    }
 }
 
 /// Inference request stored on-chain
 #[account]
 #[derive(InitSpace)]
 pub struct InferenceRequest {
-    /// Request ID (unique)
+    /// Request ID (unique per user)
     pub request_id: u64,
     /// Associated model ID
     pub model_id: u64,
@@ -74,13 +549,17 @@ pub struct InferenceRequest {
     pub attestation_hash: [u8; 32],
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+    /// Model version the request was created against
+    pub model_version: VersionPin,
 }
 
 /// Batch inference request
 #[account]
 #[derive(InitSpace)]
 pub struct BatchInference {
-    /// Batch ID
+    /// Batch ID (unique per user)
     pub batch_id: u64,
     /// Associated model ID
     pub model_id: u64,
@@ -98,6 +577,10 @@ pub struct BatchInference {
     pub failed_count: u32,
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+    /// Model version the batch was created against
+    pub model_version: VersionPin,
 }
 
 /// User inference metrics
@@ -116,16 +599,205 @@ pub struct UserMetrics {
     pub total_cost: u64,
     /// Average latency in milliseconds
     pub average_latency_ms: u64,
+    /// Next inference request id for this user
+    pub next_request_id: u64,
+    /// Next batch id for this user
+    pub next_batch_id: u64,
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
 }
 
-/// Supported blockchain networks for bridging
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum Chain {
-    Solana,
-    Near,
-    Zcash,
+/// Volume window used for rate limiting.
+///
+/// Windows are `window_secs` long and aligned to `start`. The volume of the
+/// previous window counts in proportion to its overlap with the trailing
+/// `window_secs`, so the cap holds across a window boundary instead of
+/// allowing twice the cap around it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct VolumeWindow {
+    /// Window start timestamp
+    pub start: i64,
+    /// Volume recorded in the window in lamports
+    pub volume: u64,
+}
+
+impl VolumeWindow {
+    /// Start, previous and own volume of the window containing `now`
+    fn rolled(&self, previous: &PreviousVolume, now: i64, window_secs: i64) -> (i64, u64, u64) {
+        let elapsed = now.saturating_sub(self.start).max(0);
+        if window_secs <= 0 || elapsed < window_secs {
+            return (self.start, previous.0.unwrap_or(0), self.volume);
+        }
+        let windows = elapsed / window_secs;
+        let start = self.start.saturating_add(windows.saturating_mul(window_secs));
+        let previous = if windows == 1 && previous.0.is_some() { self.volume } else { 0 };
+        (start, previous, 0)
+    }
+
+    /// Volume counted in the trailing `window_secs` before `now`
+    pub fn current(&self, previous: &PreviousVolume, now: i64, window_secs: i64) -> u64 {
+        let (start, previous, volume) = self.rolled(previous, now, window_secs);
+        if window_secs <= 0 {
+            return volume;
+        }
+        let overlap = window_secs.saturating_sub(now.saturating_sub(start)).max(0) as u128;
+        let weighted = previous as u128 * overlap / window_secs as u128;
+        volume.saturating_add(weighted as u64)
+    }
+
+    /// Whether `amount` fits under `cap` (0 = unlimited)
+    pub fn has_capacity(&self, previous: &PreviousVolume, now: i64, window_secs: i64, cap: u64, amount: u64) -> bool {
+        cap == 0 || self.current(previous, now, window_secs).saturating_add(amount) <= cap
+    }
+
+    /// Record `amount`, moving to the window containing `now` first
+    pub fn record(&mut self, previous: &mut PreviousVolume, now: i64, window_secs: i64, amount: u64) {
+        let (start, previous_volume, volume) = self.rolled(previous, now, window_secs);
+        self.start = start;
+        self.volume = volume.saturating_add(amount);
+        if previous.0.is_some() {
+            previous.0 = Some(previous_volume);
+        }
+    }
+}
+
+/// Volume of the window before `VolumeWindow.start`.
+///
+/// Appended after `layout_version` like `VersionPin`. Accounts before
+/// version 2 end before it and decode as absent, which is not written back:
+/// they count no previous volume, as fixed windows, until migrated.
+/// Migrated accounts read the zeroed bytes as present.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PreviousVolume(pub Option<u64>);
+
+impl AnchorSerialize for PreviousVolume {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.0 {
+            Some(volume) => volume.serialize(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl AnchorDeserialize for PreviousVolume {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut bytes = [0u8; 8];
+        let read = reader.read(&mut bytes)?;
+        if read == 0 {
+            return Ok(Self(None));
+        }
+        reader.read_exact(&mut bytes[read..])?;
+        Ok(Self(Some(u64::from_le_bytes(bytes))))
+    }
+}
+
+impl anchor_lang::Space for PreviousVolume {
+    const INIT_SPACE: usize = 8;
+}
+
+#[cfg(feature = "idl-build")]
+impl anchor_lang::IdlBuild for PreviousVolume {
+    fn create_type() -> Option<anchor_lang::idl::types::IdlTypeDef> {
+        use anchor_lang::idl::types::*;
+        Some(IdlTypeDef {
+            name: Self::get_full_path(),
+            docs: vec!["Previous window volume, absent on accounts before layout version 2".into()],
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Tuple(vec![IdlType::U64])),
+            },
+        })
+    }
+}
+
+/// Parameters of the time-weighted reputation model.
+///
+/// Mirrored by `ReputationParams` in the NEAR bridge contract; both sides
+/// use the same integer math so scores agree across chains.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ReputationParams {
+    /// Time for an idle score to halve (0 = no decay)
+    pub half_life_secs: i64,
+    /// Points for every settled fill
+    pub success_points: u32,
+    /// Lamports of fill volume per bonus point
+    pub volume_unit: u64,
+    /// Cap on the volume bonus per fill
+    pub max_volume_bonus: u32,
+    /// Match-to-execution latency tolerated without penalty
+    pub target_latency_secs: i64,
+    /// Extra latency costing one point
+    pub latency_penalty_secs: i64,
+    /// Cap on the latency penalty per fill
+    pub max_latency_penalty: u32,
+    /// Points lost for a failed fill
+    pub failure_penalty: u32,
+}
+
+impl Default for ReputationParams {
+    fn default() -> Self {
+        Self {
+            half_life_secs: 30 * 86_400,
+            success_points: 1,
+            volume_unit: 10_000_000_000, // 10 SOL
+            max_volume_bonus: 10,
+            target_latency_secs: 600,
+            latency_penalty_secs: 600,
+            max_latency_penalty: 5,
+            failure_penalty: 5,
+        }
+    }
+}
+
+impl ReputationParams {
+    /// Decay `score` over `elapsed` seconds: halves every `half_life_secs`,
+    /// linearly interpolated between halvings
+    pub fn decay(&self, score: u32, elapsed: i64) -> u32 {
+        self.decay_applied(score, elapsed).0
+    }
+
+    /// `decay`, plus the seconds of `elapsed` it accounts for: the whole
+    /// half-lives and the shortest remainder giving the same drop. The rest
+    /// has not lowered the score yet and carries over to the next update.
+    pub fn decay_applied(&self, score: u32, elapsed: i64) -> (u32, i64) {
+        if self.half_life_secs <= 0 || elapsed <= 0 {
+            return (score, elapsed.max(0));
+        }
+        let halvings = elapsed / self.half_life_secs;
+        if halvings >= 32 {
+            return (0, elapsed);
+        }
+        let halved = score >> halvings;
+        let rem = elapsed % self.half_life_secs;
+        let period = 2 * self.half_life_secs as i128;
+        let drop = (halved as i128 * rem as i128 / period) as u32;
+        let rem_applied = if halved == 0 {
+            rem
+        } else {
+            ((drop as i128 * period + halved as i128 - 1) / halved as i128) as i64
+        };
+        (halved - drop, halvings * self.half_life_secs + rem_applied)
+    }
+
+    /// Net points for a fill of `amount` executed `latency_secs` after matching
+    pub fn success_delta(&self, amount: u64, latency_secs: i64) -> i64 {
+        let volume_bonus = if self.volume_unit == 0 {
+            0
+        } else {
+            (amount / self.volume_unit).min(self.max_volume_bonus as u64) as i64
+        };
+        let late_secs = latency_secs.saturating_sub(self.target_latency_secs).max(0);
+        let latency_penalty = if self.latency_penalty_secs <= 0 {
+            0
+        } else {
+            (late_secs / self.latency_penalty_secs).min(self.max_latency_penalty as i64)
+        };
+        self.success_points as i64 + volume_bonus - latency_penalty
+    }
 }
 
 /// Intent status lifecycle
@@ -139,20 +811,31 @@ pub enum IntentStatus {
     Settled,    // Fully settled, rewards distributed
     Failed,     // Execution failed
     Disputed,   // Under dispute resolution
+    Cancelled,  // Cancelled by creator, unfilled amount refunded
+}
+
+impl IntentStatus {
+    /// Whether the intent can no longer change state
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            IntentStatus::Settled | IntentStatus::Failed | IntentStatus::Cancelled
+        )
+    }
 }
 
 /// Cross-chain transfer intent
 #[account]
 #[derive(InitSpace)]
 pub struct Intent {
-    /// Unique intent ID
+    /// Unique intent ID (assigned from `BridgeConfig.total_intents`)
     pub intent_id: u64,
     /// Creator of the intent
     pub creator: Pubkey,
-    /// Source chain (always Solana for this program)
-    pub source_chain: Chain,
-    /// Destination chain
-    pub destination_chain: Chain,
+    /// Source chain id (always Solana for this program)
+    pub source_chain_id: u32,
+    /// Destination chain id
+    pub destination_chain_id: u32,
     /// Source amount in lamports
     pub source_amount: u64,
     /// Destination amount (encrypted commitment)
@@ -167,13 +850,23 @@ pub struct Intent {
     pub is_shielded: bool,
     /// Current status
     pub status: IntentStatus,
-    /// Matched solver (if any)
-    pub solver: Option<Pubkey>,
+    /// Whether several solvers may each fill a portion
+    pub allow_partial_fill: bool,
+    /// Amount claimed by live or settled fills
+    pub filled_amount: u64,
+    /// Amount paid out through settled fills
+    pub settled_amount: u64,
+    /// Amount returned to the creator (failed fills, cancelled remainder)
+    pub refunded_amount: u64,
+    /// Number of fills awaiting execution
+    pub matched_fills: u32,
+    /// Number of fills executed but not yet settled
+    pub executed_fills: u32,
     /// Creation timestamp
     pub created_at: i64,
     /// Expiration timestamp
     pub expires_at: i64,
-    /// Execution timestamp (if executed)
+    /// Execution timestamp of the most recent fill
     pub executed_at: Option<i64>,
     /// Destination transaction hash
     pub destination_tx_hash: [u8; 32],
@@ -181,6 +874,87 @@ pub struct Intent {
     pub privacy_proof: [u8; 32],
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl Intent {
+    /// Amount not yet claimed by any fill nor refunded
+    pub fn remaining_amount(&self) -> u64 {
+        self.source_amount
+            .saturating_sub(self.filled_amount)
+            .saturating_sub(self.refunded_amount)
+    }
+
+    /// Recompute the aggregate status from fill counters.
+    ///
+    /// `on_empty` is used when every lamport was refunded without any fill
+    /// settling (e.g. `Failed` or `Cancelled`).
+    pub fn refresh_status(&mut self, on_empty: IntentStatus) {
+        self.status = if self.remaining_amount() > 0 {
+            IntentStatus::Created
+        } else if self.matched_fills > 0 {
+            IntentStatus::Matched
+        } else if self.executed_fills > 0 {
+            IntentStatus::Executed
+        } else if self.settled_amount > 0 {
+            IntentStatus::Settled
+        } else {
+            on_empty
+        };
+    }
+}
+
+impl From<&Intent> for CrossChainIntent {
+    fn from(intent: &Intent) -> Self {
+        CrossChainIntent {
+            version: silence_intent::MESSAGE_VERSION,
+            source_chain_id: intent.source_chain_id,
+            destination_chain_id: intent.destination_chain_id,
+            intent_id: silence_intent::solana_intent_id(intent.intent_id),
+            creator: intent.creator.to_bytes(),
+            source_token: intent.source_token.to_bytes(),
+            source_amount: intent.source_amount as u128,
+            destination_token_hash: intent.destination_token_hash,
+            destination_amount: DestinationAmount::Commitment(intent.destination_amount_commitment),
+            recipient_hash: intent.recipient_hash,
+            is_shielded: intent.is_shielded,
+            expires_at: intent.expires_at.max(0) as u64,
+        }
+    }
+}
+
+/// Fill lifecycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum FillStatus {
+    Matched,   // Portion claimed by solver
+    Executed,  // Transfer completed on destination
+}
+
+/// Portion of an intent claimed by a single solver
+#[account]
+#[derive(InitSpace)]
+pub struct IntentFill {
+    /// Intent this fill belongs to
+    pub intent: Pubkey,
+    /// Solver authority that claimed the fill
+    pub solver: Pubkey,
+    /// Claimed amount in lamports
+    pub amount: u64,
+    /// Current status
+    pub status: FillStatus,
+    /// Match timestamp
+    pub matched_at: i64,
+    /// Latest time the fill may be executed before it can be timed out
+    pub execution_deadline: i64,
+    /// Execution timestamp (if executed)
+    pub executed_at: Option<i64>,
+    /// Destination transaction hash
+    pub destination_tx_hash: [u8; 32],
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
 }
 
 /// Solver entity
@@ -189,8 +963,9 @@ pub struct Intent {
 pub struct Solver {
     /// Solver authority
     pub solver_id: Pubkey,
-    /// Supported chains bitmap (bit 0=Solana, 1=Near, 2=Zcash)
-    pub supported_chains: u8,
+    /// Supported chain ids
+    #[max_len(MAX_SOLVER_CHAINS)]
+    pub supported_chains: Vec<u32>,
     /// Staked amount in lamports
     pub stake: u64,
     /// Reputation score (0-1000)
@@ -207,20 +982,125 @@ pub struct Solver {
     pub is_active: bool,
     /// Registration timestamp
     pub registered_at: i64,
+    /// Settled volume in the current rate limit window
+    pub volume_window: VolumeWindow,
+    /// Staker rewards already accounted for (stake * reward_per_stake)
+    pub reward_debt: u128,
+    /// Staker rewards checkpointed but not yet claimed
+    pub pending_rewards: u64,
+    /// Off-chain profile (endpoints, contact, quotes API)
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String,
+    /// Highest protocol fee, in basis points, the solver's fills accept (0 = any)
+    pub fee_bps: u16,
+    /// Smallest fill the solver is willing to take
+    pub min_fill_amount: u64,
+    /// Last liveness heartbeat
+    pub last_heartbeat_at: i64,
+    /// Last settled or failed fill
+    pub last_active_at: i64,
+    /// Time `reputation_score` was last decayed
+    pub reputation_updated_at: i64,
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+    /// Volume of the rate limit window before `volume_window`
+    pub previous_window_volume: PreviousVolume,
 }
 
-/// Bridge configuration
-#[account]
-#[derive(InitSpace)]
-pub struct BridgeConfig {
-    /// Contract owner/admin
-    pub owner: Pubkey,
-    /// Minimum solver stake in lamports
-    pub min_solver_stake: u64,
-    /// Protocol fee in basis points (100 = 1%)
-    pub protocol_fee_bps: u16,
+impl Solver {
+    /// Reputation score with decay applied up to `now`
+    pub fn current_reputation(&self, params: &ReputationParams, now: i64) -> u32 {
+        params.decay(self.reputation_score, now.saturating_sub(self.reputation_updated_at))
+    }
+
+    /// Store the half-lives that fully elapsed by `now`.
+    ///
+    /// The decay within the current half-life stays interpolated from the
+    /// stored score, so refreshing more often never changes the result.
+    pub fn refresh_reputation(&mut self, params: &ReputationParams, now: i64) {
+        if params.half_life_secs <= 0 {
+            self.reputation_updated_at = now;
+            return;
+        }
+        let elapsed = now.saturating_sub(self.reputation_updated_at);
+        let whole = elapsed - elapsed % params.half_life_secs;
+        let (score, applied) = params.decay_applied(self.reputation_score, whole);
+        self.reputation_score = score;
+        self.reputation_updated_at += applied;
+    }
+
+    /// Store the decay up to `now` before the score changes, keeping the
+    /// time too short to have cost a point
+    fn apply_decay(&mut self, params: &ReputationParams, now: i64) {
+        let elapsed = now.saturating_sub(self.reputation_updated_at);
+        let (score, applied) = params.decay_applied(self.reputation_score, elapsed);
+        self.reputation_score = score;
+        self.reputation_updated_at += applied;
+    }
+
+    /// Decay, then credit a settled fill
+    pub fn record_success(&mut self, params: &ReputationParams, now: i64, amount: u64, latency_secs: i64) {
+        self.apply_decay(params, now);
+        let score = self.reputation_score as i64 + params.success_delta(amount, latency_secs);
+        self.reputation_score = score.clamp(0, MAX_REPUTATION as i64) as u32;
+        self.last_active_at = now;
+    }
+
+    /// Decay, then penalize a failed fill
+    pub fn record_failure(&mut self, params: &ReputationParams, now: i64) {
+        self.apply_decay(params, now);
+        self.reputation_score = self.reputation_score.saturating_sub(params.failure_penalty);
+        self.last_active_at = now;
+    }
+
+    /// Volume settled by the solver in the trailing rate limit window
+    pub fn window_volume(&self, now: i64, window_secs: i64) -> u64 {
+        self.volume_window.current(&self.previous_window_volume, now, window_secs)
+    }
+
+    /// Whether `amount` fits under the solver volume `cap` (0 = unlimited)
+    pub fn has_volume_capacity(&self, now: i64, window_secs: i64, cap: u64, amount: u64) -> bool {
+        self.volume_window
+            .has_capacity(&self.previous_window_volume, now, window_secs, cap, amount)
+    }
+
+    pub fn record_volume(&mut self, now: i64, window_secs: i64, amount: u64) {
+        self.volume_window.record(&mut self.previous_window_volume, now, window_secs, amount);
+    }
+
+    /// Whether the solver sent a heartbeat within `timeout_secs` (0 = no timeout)
+    pub fn is_live(&self, now: i64, timeout_secs: i64) -> bool {
+        timeout_secs == 0 || now < self.last_heartbeat_at.saturating_add(timeout_secs)
+    }
+
+    /// Staker rewards accrued since the last checkpoint
+    pub fn accrued_rewards(&self, reward_per_stake: u128) -> u64 {
+        if !self.is_active {
+            return 0;
+        }
+        let earned = (self.stake as u128) * reward_per_stake / REWARD_PRECISION;
+        earned.saturating_sub(self.reward_debt) as u64
+    }
+
+    /// Move accrued rewards into `pending_rewards` and reset the debt
+    pub fn checkpoint_rewards(&mut self, reward_per_stake: u128) {
+        self.pending_rewards = self.pending_rewards.saturating_add(self.accrued_rewards(reward_per_stake));
+        self.reward_debt = (self.stake as u128) * reward_per_stake / REWARD_PRECISION;
+    }
+}
+
+/// Bridge configuration
+#[account]
+#[derive(InitSpace)]
+pub struct BridgeConfig {
+    /// Contract owner/admin
+    pub owner: Pubkey,
+    /// Minimum solver stake in lamports
+    pub min_solver_stake: u64,
+    /// Protocol fee in basis points (100 = 1%)
+    pub protocol_fee_bps: u16,
     /// Total volume processed
     pub total_volume: u64,
     /// Total intents created
@@ -229,8 +1109,332 @@ pub struct BridgeConfig {
     pub active_solvers: u32,
     /// Protocol fee vault
     pub fee_vault: Pubkey,
+    /// Guardian allowed to pause the bridge without the owner
+    pub guardian: Pubkey,
+    /// Global emergency pause flag
+    pub paused: bool,
+    /// Rate limit window length in seconds
+    pub rate_limit_window_secs: i64,
+    /// Max settled volume per solver per window (0 = unlimited)
+    pub solver_volume_cap: u64,
+    /// Share of protocol fees streamed to solver stakers in basis points
+    pub staker_fee_share_bps: u16,
+    /// Total stake of active solvers
+    pub total_stake: u64,
+    /// Solvers without a heartbeat for this long cannot match (0 = disabled)
+    pub heartbeat_timeout_secs: i64,
+    /// Reputation model parameters
+    pub reputation: ReputationParams,
+    /// Execution window used when the solver gives no estimate
+    pub default_execution_secs: i64,
+    /// Longest execution window a solver may quote
+    pub max_execution_secs: i64,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl BridgeConfig {
+    /// Fails if the bridge is paused or the chain is disabled
+    pub fn require_operational(&self, chain: &ChainConfig) -> Result<()> {
+        require!(!self.paused, ErrorCode::BridgePaused);
+        require!(chain.enabled, ErrorCode::ChainPaused);
+        Ok(())
+    }
+}
+
+/// Protocol fee vault PDA holding collected fees and unclaimed staker rewards
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    /// Total protocol fees collected in lamports
+    pub total_collected: u64,
+    /// Total fees withdrawn by the admin
+    pub total_withdrawn: u64,
+    /// Staker rewards accrued but not yet claimed
+    pub unclaimed_rewards: u64,
+    /// Accumulated rewards per staked lamport, scaled by `REWARD_PRECISION`
+    pub reward_per_stake: u128,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+impl FeeVault {
+    /// Account for a collected fee, streaming the staker share into the accumulator
+    pub fn credit_fee(&mut self, fee: u64, staker_fee_share_bps: u16, total_stake: u64) {
+        self.total_collected = self.total_collected.saturating_add(fee);
+        if total_stake == 0 {
+            return;
+        }
+        let staker_share = (fee as u128) * (staker_fee_share_bps as u128) / 10000;
+        self.reward_per_stake += staker_share * REWARD_PRECISION / (total_stake as u128);
+        self.unclaimed_rewards = self.unclaimed_rewards.saturating_add(staker_share as u64);
+    }
+}
+
+/// Chain registry entry, one PDA per chain id
+#[account]
+#[derive(InitSpace)]
+pub struct ChainConfig {
+    /// Chain identifier referenced by intents and solvers
+    pub chain_id: u32,
+    /// Human readable chain name (max 16 chars)
+    #[max_len(16)]
+    pub name: String,
+    /// Whether the chain accepts new activity (circuit breaker)
+    pub enabled: bool,
+    /// Minimum intent source amount in lamports
+    pub min_amount: u64,
+    /// Seconds to wait after execution before settlement
+    pub finality_seconds: u32,
+    /// Protocol fee override in basis points
+    pub fee_bps_override: Option<u16>,
+    /// Max volume bridged to this chain per window (0 = unlimited)
+    pub volume_cap: u64,
+    /// Settled volume in the current rate limit window
+    pub volume_window: VolumeWindow,
+    /// Whether execution must consume a relayer-attested fulfillment receipt
+    pub requires_receipt: bool,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+    /// Volume of the rate limit window before `volume_window`
+    pub previous_window_volume: PreviousVolume,
+}
+
+impl ChainConfig {
+    /// Protocol fee for intents to this chain
+    pub fn fee_bps(&self, config: &BridgeConfig) -> u16 {
+        self.fee_bps_override.unwrap_or(config.protocol_fee_bps)
+    }
+
+    /// Volume bridged to this chain in the trailing rate limit window
+    pub fn window_volume(&self, now: i64, window_secs: i64) -> u64 {
+        self.volume_window.current(&self.previous_window_volume, now, window_secs)
+    }
+
+    /// Whether `amount` fits under `volume_cap`
+    pub fn has_volume_capacity(&self, now: i64, window_secs: i64, amount: u64) -> bool {
+        self.volume_window
+            .has_capacity(&self.previous_window_volume, now, window_secs, self.volume_cap, amount)
+    }
+
+    pub fn record_volume(&mut self, now: i64, window_secs: i64, amount: u64) {
+        self.volume_window.record(&mut self.previous_window_volume, now, window_secs, amount);
+    }
+}
+
+/// Relayers trusted to attest cross-chain fulfillment receipts
+#[account]
+#[derive(InitSpace)]
+pub struct RelayerSet {
+    /// Relayer keys
+    #[max_len(MAX_RELAYERS)]
+    pub relayers: Vec<Pubkey>,
+    /// Attestations needed before a receipt can be consumed
+    pub threshold: u8,
     /// Bump for PDA derivation
     pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+}
+
+/// Fulfillment receipt from a destination chain, attested by relayers.
+///
+/// Keyed by `receipt_hash`, so relayers reporting conflicting receipts
+/// for one fill tally separately instead of blocking each other.
+#[account]
+#[derive(InitSpace)]
+pub struct ReceiptAttestation {
+    /// `FulfillmentReceipt::hash`
+    pub receipt_hash: [u8; 32],
+    /// `CrossChainIntent::hash` of the fulfilled intent
+    pub intent_hash: [u8; 32],
+    /// Chain the delivery happened on
+    pub destination_chain_id: u32,
+    /// Solver allowed to consume the receipt
+    pub solver: Pubkey,
+    /// Amount delivered in destination base units
+    pub amount: u128,
+    /// Delivery time (unix seconds)
+    pub fulfilled_at: u64,
+    /// Relayers that attested this receipt
+    #[max_len(MAX_RELAYERS)]
+    pub attesters: Vec<Pubkey>,
+    /// Whether the relayer threshold was reached
+    pub attested: bool,
+    /// Whether `execute_intent` consumed the receipt
+    pub consumed: bool,
+    /// Bump for PDA derivation
+    pub bump: u8,
+    /// Layout version; fields added later go after it
+    pub layout_version: AccountVersion,
+    /// Recipient the delivery went to, checked against the intent's
+    pub recipient_hash: ReceiptRecipient,
+}
+
+/// Protocol fee charged on `amount` at `fee_bps`
+pub fn protocol_fee_for(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / 10000;
+    Ok(fee as u64)
+}
+
+/// Append `model` to the last index page and record the page in `listing`.
+///
+/// `directory` and `index` may have just been created by `init_if_needed`.
+fn list_model(
+    directory: &mut ModelDirectory,
+    index: &mut ModelIndex,
+    listing: &mut ModelListing,
+    model: &ModelMetadata,
+    bumps: (u8, u8, u8),
+) {
+    let (directory_bump, index_bump, listing_bump) = bumps;
+    if directory.layout_version.is_uninitialized() {
+        directory.bump = directory_bump;
+        directory.layout_version = ModelDirectory::LAYOUT_VERSION;
+    }
+    let page = directory.next_page();
+    if index.layout_version.is_uninitialized() {
+        index.page = page;
+        index.bump = index_bump;
+        index.layout_version = ModelIndex::LAYOUT_VERSION;
+    }
+    index.entries.push(ModelSummary::from(model));
+    directory.model_count += 1;
+    
+    listing.model_id = model.model_id;
+    listing.page = page;
+    listing.bump = listing_bump;
+    listing.layout_version = ModelListing::LAYOUT_VERSION;
+    
+    emit!(ModelListed {
+        model_id: model.model_id,
+        page,
+    });
+}
+
+/// Pay for `count` inferences costing `cost` lamports in total.
+///
+/// A subscription covers them from its quota for the current epoch, prepaid
+/// credits are debited, and otherwise `user` transfers `cost` to `payee`.
+/// Returns the lamports charged.
+fn pay_for_inferences<'info>(
+    user: &Signer<'info>,
+    payee: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    credit_account: Option<&mut Account<'info, CreditAccount>>,
+    subscription: Option<&mut Account<'info, Subscription>>,
+    count: u32,
+    cost: u64,
+) -> Result<u64> {
+    match (credit_account, subscription) {
+        (Some(_), Some(_)) => err!(ErrorCode::ConflictingPayment),
+        (None, Some(subscription)) => {
+            let epoch = Clock::get()?.epoch;
+            require!(count <= subscription.remaining(epoch), ErrorCode::SubscriptionExhausted);
+            subscription.used += count;
+            Ok(0)
+        }
+        (Some(credits), None) => {
+            credits.balance = credits.balance.checked_sub(cost).ok_or(ErrorCode::InsufficientCredits)?;
+            credits.earned = credits.earned.checked_add(cost).ok_or(ErrorCode::Overflow)?;
+            Ok(cost)
+        }
+        (None, None) => {
+            let transfer_ctx = CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: user.to_account_info(),
+                    to: payee.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(transfer_ctx, cost)?;
+            Ok(cost)
+        }
+    }
+}
+
+/// Fail unless `authority` is the model owner or one of its delegates
+fn require_model_manager(model: &ModelMetadata, authority: &Pubkey, model_access: &AccountInfo) -> Result<()> {
+    let access = ModelAccess::load(model_access)?;
+    require!(model.is_manager(authority, access.as_ref()), ErrorCode::Unauthorized);
+    Ok(())
+}
+
+/// Pin for a new request or batch on `model`.
+///
+/// `release` is a published, undeprecated `ModelVersion` of the model, or
+/// the address of the model's current version if that was never published,
+/// as for models registered before version history.
+fn version_pin(model: &ModelMetadata, release: &AccountInfo) -> Result<VersionPin> {
+    let Some(version) = load_account::<ModelVersion>(release)? else {
+        let (current, _) = Pubkey::find_program_address(
+            &[b"model_version", model.model_id.to_le_bytes().as_ref(), model.version.as_bytes()],
+            &crate::ID,
+        );
+        require_keys_eq!(release.key(), current, ErrorCode::InvalidModelVersion);
+        return Ok(VersionPin(None));
+    };
+    require!(version.model_id == model.model_id, ErrorCode::InvalidModelVersion);
+    require!(version.deprecated_at.is_none(), ErrorCode::DeprecatedModelVersion);
+    Ok(VersionPin(Some(release.key())))
+}
+
+/// Fill in a `ModelAccess` just created by `init_if_needed`
+fn init_model_access(access: &mut ModelAccess, model_id: u64, bump: u8) {
+    if access.layout_version.is_uninitialized() {
+        access.model_id = model_id;
+        access.bump = bump;
+        access.layout_version = ModelAccess::LAYOUT_VERSION;
+    }
+}
+
+/// Stamp a reallocated account of type `T` with its current layout version
+fn migrate<T: Versioned>(version: &mut AccountVersion, address: Pubkey) -> Result<()> {
+    require!(*version < T::LAYOUT_VERSION, ErrorCode::AccountUpToDate);
+    let from_version = version.0;
+    *version = T::LAYOUT_VERSION;
+    emit!(AccountMigrated {
+        account: address,
+        from_version,
+        to_version: version.0,
+    });
+    Ok(())
+}
+
+/// `Accounts` context of a `migrate_*` instruction for each listed account
+/// type: the account reallocated to its current size at `payer`'s expense.
+macro_rules! migrate_accounts {
+    ($($context:ident => $account:ident,)*) => {$(
+        #[derive(Accounts)]
+        pub struct $context<'info> {
+            #[account(mut)]
+            pub payer: Signer<'info>,
+            #[account(
+                mut,
+                realloc = 8 + $account::INIT_SPACE,
+                realloc::payer = payer,
+                realloc::zero = false,
+            )]
+            pub account: Account<'info, $account>,
+            pub system_program: Program<'info, System>,
+        }
+
+        impl<'info> $context<'info> {
+            fn migrate(&mut self) -> Result<()> {
+                let address = self.account.key();
+                migrate::<$account>(&mut self.account.layout_version, address)
+            }
+        }
+    )*};
 }
 
 // ============================================================================
@@ -261,7 +1465,20 @@ pub mod project_silence {
         config.total_intents = 0;
         config.active_solvers = 0;
         config.fee_vault = ctx.accounts.fee_vault.key();
+        ctx.accounts.fee_vault.bump = ctx.bumps.fee_vault;
+        ctx.accounts.fee_vault.layout_version = FeeVault::LAYOUT_VERSION;
+        config.guardian = ctx.accounts.owner.key();
+        config.paused = false;
+        config.rate_limit_window_secs = 86_400; // 1 day
+        config.solver_volume_cap = 0;
+        config.staker_fee_share_bps = 0;
+        config.total_stake = 0;
+        config.heartbeat_timeout_secs = 3_600; // 1 hour
+        config.reputation = ReputationParams::default();
+        config.default_execution_secs = 3_600; // 1 hour
+        config.max_execution_secs = 86_400; // 1 day
         config.bump = ctx.bumps.config;
+        config.layout_version = BridgeConfig::LAYOUT_VERSION;
         
         emit!(BridgeInitialized {
             owner: config.owner,
@@ -309,6 +1526,16 @@ pub mod project_silence {
         model.updated_at = clock.unix_timestamp;
         model.is_active = true;
         model.bump = ctx.bumps.model;
+        model.layout_version = ModelMetadata::LAYOUT_VERSION;
+        
+        let bumps = (ctx.bumps.directory, ctx.bumps.model_index, ctx.bumps.model_listing);
+        list_model(
+            &mut ctx.accounts.directory,
+            &mut ctx.accounts.model_index,
+            &mut ctx.accounts.model_listing,
+            model,
+            bumps,
+        );
         
         emit!(ModelRegistered {
             model_id,
@@ -319,17 +1546,17 @@ pub mod project_silence {
         Ok(())
     }
 
-    /// Update model metadata
+    /// Update model metadata; the version only moves through `publish_model_version`
     pub fn update_model(
         ctx: Context<UpdateModel>,
         name: Option<String>,
         description: Option<String>,
-        version: Option<String>,
         cost_per_inference: Option<u64>,
         is_active: Option<bool>,
     ) -> Result<()> {
         let clock = Clock::get()?;
         let model = &mut ctx.accounts.model;
+        require_model_manager(model, &ctx.accounts.authority.key(), &ctx.accounts.model_access)?;
         
         if let Some(n) = name {
             require!(n.len() <= 32, ErrorCode::NameTooLong);
@@ -339,10 +1566,6 @@ pub mod project_silence {
             require!(d.len() <= 256, ErrorCode::DescriptionTooLong);
             model.description = d;
         }
-        if let Some(v) = version {
-            require!(v.len() <= 16, ErrorCode::VersionTooLong);
-            model.version = v;
-        }
         if let Some(c) = cost_per_inference {
             model.cost_per_inference = c;
         }
@@ -352,6 +1575,17 @@ pub mod project_silence {
         
         model.updated_at = clock.unix_timestamp;
         
+        // Models registered before the discovery index have no listing until `index_model`
+        if let Some(listing) = load_account::<ModelListing>(&ctx.accounts.model_listing)? {
+            let index = ctx.accounts.model_index.as_mut().ok_or(ErrorCode::InvalidModelIndex)?;
+            require!(index.page == listing.page, ErrorCode::InvalidModelIndex);
+            // The listing guarantees the page holds this model
+            let summary = ModelSummary::from(&**model);
+            if let Some(entry) = index.entries.iter_mut().find(|entry| entry.model_id == summary.model_id) {
+                *entry = summary;
+            }
+        }
+        
         emit!(ModelUpdated {
             model_id: model.model_id,
         });
@@ -359,10 +1593,165 @@ pub mod project_silence {
         Ok(())
     }
 
+    /// List a model registered before the discovery index existed; anyone may pay for it
+    pub fn index_model(ctx: Context<IndexModel>) -> Result<()> {
+        let bumps = (ctx.bumps.directory, ctx.bumps.model_index, ctx.bumps.model_listing);
+        list_model(
+            &mut ctx.accounts.directory,
+            &mut ctx.accounts.model_index,
+            &mut ctx.accounts.model_listing,
+            &ctx.accounts.model,
+            bumps,
+        );
+        Ok(())
+    }
+
+    /// Propose a new model owner, or cancel the pending proposal with `None`
+    pub fn transfer_model_ownership(ctx: Context<ManageModel>, new_owner: Option<Pubkey>) -> Result<()> {
+        let model = &ctx.accounts.model;
+        let access = &mut ctx.accounts.model_access;
+        init_model_access(access, model.model_id, ctx.bumps.model_access);
+        access.pending_owner = new_owner;
+        
+        emit!(ModelOwnershipTransferStarted {
+            model_id: model.model_id,
+            owner: model.owner,
+            pending_owner: new_owner,
+        });
+        
+        Ok(())
+    }
+
+    /// Accept a pending ownership transfer; delegates and payout are reset
+    pub fn accept_model_ownership(ctx: Context<AcceptModelOwnership>) -> Result<()> {
+        let model = &mut ctx.accounts.model;
+        let access = &mut ctx.accounts.model_access;
+        let previous_owner = model.owner;
+        
+        model.owner = ctx.accounts.new_owner.key();
+        model.updated_at = Clock::get()?.unix_timestamp;
+        access.pending_owner = None;
+        access.delegates.clear();
+        access.payout = None;
+        
+        emit!(ModelOwnershipTransferred {
+            model_id: model.model_id,
+            previous_owner,
+            new_owner: model.owner,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the keys allowed to update the model
+    pub fn set_model_delegates(ctx: Context<ManageModel>, delegates: Vec<Pubkey>) -> Result<()> {
+        require!(delegates.len() <= MAX_MODEL_DELEGATES, ErrorCode::TooManyDelegates);
+        let model = &ctx.accounts.model;
+        let access = &mut ctx.accounts.model_access;
+        init_model_access(access, model.model_id, ctx.bumps.model_access);
+        access.delegates = delegates.clone();
+        
+        emit!(ModelDelegatesUpdated {
+            model_id: model.model_id,
+            delegates,
+        });
+        
+        Ok(())
+    }
+
+    /// Route inference payments to `payout`, or back to the owner with `None`
+    pub fn set_model_payout(ctx: Context<ManageModel>, payout: Option<Pubkey>) -> Result<()> {
+        let model = &ctx.accounts.model;
+        let access = &mut ctx.accounts.model_access;
+        init_model_access(access, model.model_id, ctx.bumps.model_access);
+        access.payout = payout;
+        
+        emit!(ModelPayoutUpdated {
+            model_id: model.model_id,
+            payout: payout.unwrap_or(model.owner),
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the keys that process the model's inferences (owner or delegate)
+    pub fn set_model_operators(ctx: Context<SetModelOperators>, operators: Vec<Pubkey>) -> Result<()> {
+        require!(operators.len() <= MAX_MODEL_OPERATORS, ErrorCode::TooManyOperators);
+        let model = &ctx.accounts.model;
+        require_model_manager(model, &ctx.accounts.authority.key(), &ctx.accounts.model_access)?;
+        
+        let list = &mut ctx.accounts.model_operators;
+        if list.layout_version.is_uninitialized() {
+            list.model_id = model.model_id;
+            list.bump = ctx.bumps.model_operators;
+            list.layout_version = ModelOperators::LAYOUT_VERSION;
+        }
+        list.operators = operators.clone();
+        
+        emit!(ModelOperatorsUpdated {
+            model_id: model.model_id,
+            operators,
+        });
+        
+        Ok(())
+    }
+
+    /// Publish an immutable model version and make it the model's current one
+    pub fn publish_model_version(
+        ctx: Context<PublishModelVersion>,
+        version: String,
+        weights_commitment: [u8; 32],
+        changelog_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!version.is_empty(), ErrorCode::InvalidModelVersion);
+        require!(version.len() <= 16, ErrorCode::VersionTooLong);
+        let model = &mut ctx.accounts.model;
+        require_model_manager(model, &ctx.accounts.authority.key(), &ctx.accounts.model_access)?;
+        
+        let clock = Clock::get()?;
+        let release = &mut ctx.accounts.model_version;
+        release.model_id = model.model_id;
+        release.version = version.clone();
+        release.weights_commitment = weights_commitment;
+        release.changelog_hash = changelog_hash;
+        release.publisher = ctx.accounts.authority.key();
+        release.released_at = clock.unix_timestamp;
+        release.deprecated_at = None;
+        release.bump = ctx.bumps.model_version;
+        release.layout_version = ModelVersion::LAYOUT_VERSION;
+        
+        model.version = version.clone();
+        model.updated_at = clock.unix_timestamp;
+        
+        emit!(ModelVersionPublished {
+            model_id: model.model_id,
+            version,
+            weights_commitment,
+            changelog_hash,
+        });
+        
+        Ok(())
+    }
+
+    /// Stop new requests from pinning a version; existing pins stay valid
+    pub fn deprecate_model_version(ctx: Context<DeprecateModelVersion>) -> Result<()> {
+        let model = &ctx.accounts.model;
+        require_model_manager(model, &ctx.accounts.authority.key(), &ctx.accounts.model_access)?;
+        let release = &mut ctx.accounts.model_version;
+        require!(release.deprecated_at.is_none(), ErrorCode::DeprecatedModelVersion);
+        release.deprecated_at = Some(Clock::get()?.unix_timestamp);
+        
+        emit!(ModelVersionDeprecated {
+            model_id: model.model_id,
+            version: release.version.clone(),
+        });
+        
+        Ok(())
+    }
+
     /// Create an inference request
     pub fn create_inference_request(
         ctx: Context<CreateInferenceRequest>,
-        request_id: u64,
         prompt_hash: [u8; 32],
         require_attestation: bool,
     ) -> Result<()> {
@@ -373,18 +1762,26 @@ pub mod project_silence {
             require!(require_attestation, ErrorCode::AttestationRequired);
         }
         
-        // Transfer inference cost
-        let transfer_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.user.to_account_info(),
-                to: ctx.accounts.model_owner.to_account_info(),
-            },
+        let access = ModelAccess::load(&ctx.accounts.model_access)?;
+        require_keys_eq!(
+            ctx.accounts.model_owner.key(),
+            model.payout(access.as_ref()),
+            ErrorCode::InvalidOwner
         );
-        anchor_lang::system_program::transfer(transfer_ctx, model.cost_per_inference)?;
+        
+        let charged = pay_for_inferences(
+            &ctx.accounts.user,
+            &ctx.accounts.model_owner,
+            &ctx.accounts.system_program,
+            ctx.accounts.credit_account.as_mut(),
+            ctx.accounts.subscription.as_mut(),
+            1,
+            model.cost_per_inference,
+        )?;
         
         let clock = Clock::get()?;
         let request = &mut ctx.accounts.request;
+        let request_id = ctx.accounts.user_metrics.next_request_id;
         
         request.request_id = request_id;
         request.model_id = model.model_id;
@@ -396,15 +1793,19 @@ pub mod project_silence {
         request.result_hash = [0u8; 32];
         request.attestation_hash = [0u8; 32];
         request.bump = ctx.bumps.request;
+        request.layout_version = InferenceRequest::LAYOUT_VERSION;
+        request.model_version = version_pin(model, &ctx.accounts.model_version)?;
         
         // Update user metrics
         let metrics = &mut ctx.accounts.user_metrics;
         if metrics.user == Pubkey::default() {
             metrics.user = ctx.accounts.user.key();
             metrics.bump = ctx.bumps.user_metrics;
+            metrics.layout_version = UserMetrics::LAYOUT_VERSION;
         }
         metrics.total_inferences += 1;
-        metrics.total_cost += model.cost_per_inference;
+        metrics.total_cost += charged;
+        metrics.next_request_id += 1;
         
         emit!(InferenceRequestCreated {
             request_id,
@@ -490,12 +1891,29 @@ pub mod project_silence {
         
         emit!(InferenceResultStored {
             request_id: request.request_id,
+            user: request.user,
             verified,
         });
         
         Ok(())
     }
 
+    /// Close a completed or failed inference request and reclaim rent
+    pub fn close_inference_request(ctx: Context<CloseInferenceRequest>) -> Result<()> {
+        let request = &ctx.accounts.request;
+        require!(request.status >= 2, ErrorCode::RequestNotFinished); // Completed or Failed
+        
+        let state_hash = hash(&request.to_account_info().try_borrow_data()?).to_bytes();
+        
+        emit!(InferenceRequestClosed {
+            request_id: request.request_id,
+            user: request.user,
+            state_hash,
+        });
+        
+        Ok(())
+    }
+
     // ========================================================================
     // BATCH INFERENCE INSTRUCTIONS
     // ========================================================================
@@ -503,7 +1921,6 @@ pub mod project_silence {
     /// Create a batch inference request
     pub fn create_batch_inference(
         ctx: Context<CreateBatchInference>,
-        batch_id: u64,
         prompt_count: u32,
         require_attestation: bool,
     ) -> Result<()> {
@@ -513,21 +1930,28 @@ pub mod project_silence {
         let model = &ctx.accounts.model;
         require!(model.is_active, ErrorCode::ModelNotActive);
         
-        // Transfer total inference cost
+        let access = ModelAccess::load(&ctx.accounts.model_access)?;
+        require_keys_eq!(
+            ctx.accounts.model_owner.key(),
+            model.payout(access.as_ref()),
+            ErrorCode::InvalidOwner
+        );
+        
         let total_cost = model.cost_per_inference.checked_mul(prompt_count as u64)
             .ok_or(ErrorCode::Overflow)?;
-        
-        let transfer_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.user.to_account_info(),
-                to: ctx.accounts.model_owner.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(transfer_ctx, total_cost)?;
+        let charged = pay_for_inferences(
+            &ctx.accounts.user,
+            &ctx.accounts.model_owner,
+            &ctx.accounts.system_program,
+            ctx.accounts.credit_account.as_mut(),
+            ctx.accounts.subscription.as_mut(),
+            prompt_count,
+            total_cost,
+        )?;
         
         let clock = Clock::get()?;
         let batch = &mut ctx.accounts.batch;
+        let batch_id = ctx.accounts.user_metrics.next_batch_id;
         
         batch.batch_id = batch_id;
         batch.model_id = model.model_id;
@@ -538,18 +1962,23 @@ pub mod project_silence {
         batch.completed_count = 0;
         batch.failed_count = 0;
         batch.bump = ctx.bumps.batch;
+        batch.layout_version = BatchInference::LAYOUT_VERSION;
+        batch.model_version = version_pin(model, &ctx.accounts.model_version)?;
         
         // Update user metrics
         let metrics = &mut ctx.accounts.user_metrics;
         if metrics.user == Pubkey::default() {
             metrics.user = ctx.accounts.user.key();
             metrics.bump = ctx.bumps.user_metrics;
+            metrics.layout_version = UserMetrics::LAYOUT_VERSION;
         }
         metrics.total_inferences += prompt_count as u64;
-        metrics.total_cost += total_cost;
+        metrics.total_cost += charged;
+        metrics.next_batch_id += 1;
         
         emit!(BatchInferenceCreated {
             batch_id,
+            user: ctx.accounts.user.key(),
             model_id: model.model_id,
             prompt_count,
         });
@@ -557,66 +1986,365 @@ pub mod project_silence {
         Ok(())
     }
 
-    // ========================================================================
-    // SILENCE BRIDGE INSTRUCTIONS
-    // ========================================================================
-
-    /// Register as a solver
-    pub fn register_solver(
-        ctx: Context<RegisterSolver>,
-        supported_chains: u8,
+    /// Record completed and failed prompts for a batch (model operators only)
+    pub fn record_batch_results(
+        ctx: Context<RecordBatchResults>,
+        completed: u32,
+        failed: u32,
     ) -> Result<()> {
-        require!(supported_chains > 0, ErrorCode::NoSupportedChains);
+        let batch = &mut ctx.accounts.batch;
         
-        let config = &ctx.accounts.config;
-        let stake = ctx.accounts.user.lamports();
+        let processed = batch.completed_count
+            .checked_add(batch.failed_count)
+            .and_then(|c| c.checked_add(completed))
+            .and_then(|c| c.checked_add(failed))
+            .ok_or(ErrorCode::Overflow)?;
+        require!(processed <= batch.prompt_count, ErrorCode::BatchOverflow);
         
-        // Note: In production, we'd transfer stake to a vault
-        // For now, we just verify they have enough
-        require!(stake >= config.min_solver_stake, ErrorCode::InsufficientStake);
+        batch.completed_count += completed;
+        batch.failed_count += failed;
         
-        let clock = Clock::get()?;
-        let solver = &mut ctx.accounts.solver;
+        // Update user metrics
+        let metrics = &mut ctx.accounts.user_metrics;
+        metrics.successful_inferences += completed as u64;
+        metrics.failed_inferences += failed as u64;
         
-        solver.solver_id = ctx.accounts.user.key();
-        solver.supported_chains = supported_chains;
-        solver.stake = config.min_solver_stake;
-        solver.reputation_score = 100; // Starting score
-        solver.total_intents_executed = 0;
-        solver.successful_intents = 0;
-        solver.failed_intents = 0;
-        solver.total_volume = 0;
-        solver.is_active = true;
-        solver.registered_at = clock.unix_timestamp;
-        solver.bump = ctx.bumps.solver;
+        emit!(BatchResultsRecorded {
+            batch_id: batch.batch_id,
+            user: batch.user,
+            completed_count: batch.completed_count,
+            failed_count: batch.failed_count,
+        });
         
-        // Update config
-        let config = &mut ctx.accounts.config;
-        config.active_solvers += 1;
+        Ok(())
+    }
+
+    /// Close a finished batch and reclaim rent
+    pub fn close_batch(ctx: Context<CloseBatch>) -> Result<()> {
+        let batch = &ctx.accounts.batch;
+        require!(
+            batch.completed_count + batch.failed_count == batch.prompt_count,
+            ErrorCode::BatchNotFinished
+        );
         
-        emit!(SolverRegistered {
-            solver_id: solver.solver_id,
-            supported_chains,
+        let state_hash = hash(&batch.to_account_info().try_borrow_data()?).to_bytes();
+        
+        emit!(BatchClosed {
+            batch_id: batch.batch_id,
+            user: batch.user,
+            state_hash,
         });
         
         Ok(())
     }
 
-    /// Create a cross-chain intent
+    // ========================================================================
+    // CREDITS AND SUBSCRIPTIONS
+    // ========================================================================
+
+    /// Prepay `amount` lamports of inferences on models paying out to `provider`
+    pub fn top_up_credits(ctx: Context<TopUpCredits>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroDeposit);
+        
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.credit_account.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, amount)?;
+        
+        let credits = &mut ctx.accounts.credit_account;
+        if credits.layout_version.is_uninitialized() {
+            credits.user = ctx.accounts.user.key();
+            credits.provider = ctx.accounts.provider.key();
+            credits.bump = ctx.bumps.credit_account;
+            credits.layout_version = CreditAccount::LAYOUT_VERSION;
+        }
+        credits.balance = credits.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        
+        emit!(CreditsToppedUp {
+            user: credits.user,
+            provider: credits.provider,
+            amount,
+            balance: credits.balance,
+        });
+        
+        Ok(())
+    }
+
+    /// Take back unspent credits
+    pub fn withdraw_credits(ctx: Context<WithdrawCredits>, amount: u64) -> Result<()> {
+        let credits = &mut ctx.accounts.credit_account;
+        credits.balance = credits.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientCredits)?;
+        credits.sub_lamports(amount)?;
+        ctx.accounts.user.add_lamports(amount)?;
+        
+        emit!(CreditsWithdrawn {
+            user: credits.user,
+            provider: credits.provider,
+            amount,
+            balance: credits.balance,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay the provider (a payout address) the credits a user has spent on its models
+    pub fn claim_credit_earnings(ctx: Context<ClaimCreditEarnings>) -> Result<()> {
+        let credits = &mut ctx.accounts.credit_account;
+        let amount = credits.earned;
+        require!(amount > 0, ErrorCode::NoRewards);
+        credits.earned = 0;
+        credits.sub_lamports(amount)?;
+        ctx.accounts.provider.add_lamports(amount)?;
+        
+        emit!(CreditEarningsClaimed {
+            user: credits.user,
+            provider: credits.provider,
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Create or change a subscription plan; paid epochs keep their quota
+    pub fn set_subscription_plan(
+        ctx: Context<SetSubscriptionPlan>,
+        plan_id: u8,
+        inferences_per_epoch: u32,
+        price: u64,
+        is_active: bool,
+    ) -> Result<()> {
+        require!(inferences_per_epoch > 0, ErrorCode::InvalidSubscriptionPlan);
+        let model = &ctx.accounts.model;
+        require_model_manager(model, &ctx.accounts.authority.key(), &ctx.accounts.model_access)?;
+        
+        let plan = &mut ctx.accounts.plan;
+        plan.model_id = model.model_id;
+        plan.plan_id = plan_id;
+        plan.inferences_per_epoch = inferences_per_epoch;
+        plan.price = price;
+        plan.is_active = is_active;
+        plan.bump = ctx.bumps.plan;
+        plan.layout_version = SubscriptionPlan::LAYOUT_VERSION;
+        
+        emit!(SubscriptionPlanUpdated {
+            model_id: model.model_id,
+            plan_id,
+            inferences_per_epoch,
+            price,
+            is_active,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay for a plan's quota in the current epoch
+    pub fn subscribe(ctx: Context<Subscribe>) -> Result<()> {
+        let model = &ctx.accounts.model;
+        let access = ModelAccess::load(&ctx.accounts.model_access)?;
+        require_keys_eq!(
+            ctx.accounts.model_owner.key(),
+            model.payout(access.as_ref()),
+            ErrorCode::InvalidOwner
+        );
+        
+        let epoch = Clock::get()?.epoch;
+        let subscription = &mut ctx.accounts.subscription;
+        let fresh = subscription.layout_version.is_uninitialized();
+        require!(fresh || subscription.epoch != epoch, ErrorCode::AlreadySubscribed);
+        
+        let plan = &ctx.accounts.plan;
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.model_owner.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, plan.price)?;
+        
+        subscription.user = ctx.accounts.user.key();
+        subscription.model_id = model.model_id;
+        subscription.plan_id = plan.plan_id;
+        subscription.epoch = epoch;
+        subscription.inferences_per_epoch = plan.inferences_per_epoch;
+        subscription.used = 0;
+        subscription.bump = ctx.bumps.subscription;
+        subscription.layout_version = Subscription::LAYOUT_VERSION;
+        
+        emit!(Subscribed {
+            user: subscription.user,
+            model_id: model.model_id,
+            plan_id: plan.plan_id,
+            epoch,
+            inferences_per_epoch: plan.inferences_per_epoch,
+        });
+        
+        Ok(())
+    }
+
+    // ========================================================================
+    // SILENCE BRIDGE INSTRUCTIONS
+    // ========================================================================
+
+    /// Register as a solver
+    pub fn register_solver(
+        ctx: Context<RegisterSolver>,
+        supported_chains: Vec<u32>,
+    ) -> Result<()> {
+        require!(!supported_chains.is_empty(), ErrorCode::NoSupportedChains);
+        require!(supported_chains.len() <= MAX_SOLVER_CHAINS, ErrorCode::TooManyChains);
+        
+        let config = &ctx.accounts.config;
+        let stake = ctx.accounts.user.lamports();
+        
+        // Note: In production, we'd transfer stake to a vault
+        // For now, we just verify they have enough
+        require!(stake >= config.min_solver_stake, ErrorCode::InsufficientStake);
+        
+        let clock = Clock::get()?;
+        let solver = &mut ctx.accounts.solver;
+        
+        solver.solver_id = ctx.accounts.user.key();
+        solver.supported_chains = supported_chains.clone();
+        solver.stake = config.min_solver_stake;
+        solver.reputation_score = 100; // Starting score
+        solver.total_intents_executed = 0;
+        solver.successful_intents = 0;
+        solver.failed_intents = 0;
+        solver.total_volume = 0;
+        solver.is_active = true;
+        solver.registered_at = clock.unix_timestamp;
+        solver.volume_window = VolumeWindow::default();
+        solver.previous_window_volume = PreviousVolume(Some(0));
+        solver.pending_rewards = 0;
+        solver.metadata_uri = String::new();
+        solver.fee_bps = 0;
+        solver.min_fill_amount = 0;
+        solver.last_heartbeat_at = clock.unix_timestamp;
+        solver.last_active_at = clock.unix_timestamp;
+        solver.reputation_updated_at = clock.unix_timestamp;
+        solver.bump = ctx.bumps.solver;
+        solver.layout_version = Solver::LAYOUT_VERSION;
+        // Start accruing staker rewards from now
+        solver.reward_debt = 0;
+        solver.checkpoint_rewards(ctx.accounts.fee_vault.reward_per_stake);
+        
+        // Update config
+        let config = &mut ctx.accounts.config;
+        config.active_solvers += 1;
+        config.total_stake = config.total_stake.saturating_add(solver.stake);
+        
+        emit!(SolverRegistered {
+            solver_id: solver.solver_id,
+            supported_chains,
+        });
+        
+        Ok(())
+    }
+
+    /// Update a solver's chains, metadata URI and fill limits, which `match_intent` enforces
+    pub fn update_solver(
+        ctx: Context<UpdateSolver>,
+        supported_chains: Option<Vec<u32>>,
+        metadata_uri: Option<String>,
+        fee_bps: Option<u16>,
+        min_fill_amount: Option<u64>,
+    ) -> Result<()> {
+        let solver = &mut ctx.accounts.solver;
+        
+        if let Some(supported_chains) = supported_chains {
+            require!(!supported_chains.is_empty(), ErrorCode::NoSupportedChains);
+            require!(supported_chains.len() <= MAX_SOLVER_CHAINS, ErrorCode::TooManyChains);
+            solver.supported_chains = supported_chains;
+        }
+        if let Some(metadata_uri) = metadata_uri {
+            require!(metadata_uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
+            solver.metadata_uri = metadata_uri;
+        }
+        if let Some(fee_bps) = fee_bps {
+            require!(fee_bps <= 10000, ErrorCode::InvalidSolverFee);
+            solver.fee_bps = fee_bps;
+        }
+        if let Some(min_fill_amount) = min_fill_amount {
+            solver.min_fill_amount = min_fill_amount;
+        }
+        
+        emit!(SolverUpdated {
+            solver_id: solver.solver_id,
+            supported_chains: solver.supported_chains.clone(),
+            fee_bps: solver.fee_bps,
+        });
+        
+        Ok(())
+    }
+
+    /// Record solver liveness; stale solvers are excluded from matching
+    pub fn solver_heartbeat(ctx: Context<SolverHeartbeat>) -> Result<()> {
+        let solver = &mut ctx.accounts.solver;
+        solver.last_heartbeat_at = Clock::get()?.unix_timestamp;
+        
+        emit!(SolverHeartbeatRecorded {
+            solver_id: solver.solver_id,
+            timestamp: solver.last_heartbeat_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Apply reputation decay to a solver (permissionless)
+    pub fn refresh_reputation(ctx: Context<RefreshReputation>) -> Result<()> {
+        let solver = &mut ctx.accounts.solver;
+        let params = &ctx.accounts.config.reputation;
+        let now = Clock::get()?.unix_timestamp;
+        solver.refresh_reputation(params, now);
+        
+        emit!(ReputationRefreshed {
+            solver_id: solver.solver_id,
+            reputation_score: solver.current_reputation(params, now),
+        });
+        
+        Ok(())
+    }
+
+    /// Create a cross-chain intent
     pub fn create_intent(
         ctx: Context<CreateIntent>,
-        intent_id: u64,
-        destination_chain: Chain,
+        destination_chain_id: u32,
         destination_amount_commitment: [u8; 32],
         destination_token_hash: [u8; 32],
         recipient_hash: [u8; 32],
         is_shielded: bool,
         ttl_seconds: i64,
         source_amount: u64,
+        allow_partial_fill: bool,
     ) -> Result<()> {
         require!(source_amount > 0, ErrorCode::ZeroDeposit);
+        let chain_config = &ctx.accounts.chain_config;
+        ctx.accounts.config.require_operational(chain_config)?;
+        require!(source_amount >= chain_config.min_amount, ErrorCode::BelowChainMinimum);
+        // A receipt covers one delivery of the whole intent
+        require!(
+            !(allow_partial_fill && chain_config.requires_receipt),
+            ErrorCode::PartialFillWithReceipt
+        );
+        
+        let clock = Clock::get()?;
+        require!(
+            chain_config.has_volume_capacity(
+                clock.unix_timestamp,
+                ctx.accounts.config.rate_limit_window_secs,
+                source_amount,
+            ),
+            ErrorCode::ChainVolumeCapExceeded
+        );
         
-        // Transfer funds from creator to intent vault (escrow)
+        // Transfer funds from creator to intent vault (escrow), plus a
+        // rent-exempt reserve so partial payouts leave it rent-exempt
+        let vault_reserve = Rent::get()?.minimum_balance(0);
         let transfer_ctx = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
@@ -624,15 +2352,32 @@ pub mod project_silence {
                 to: ctx.accounts.intent_vault.to_account_info(),
             },
         );
-        anchor_lang::system_program::transfer(transfer_ctx, source_amount)?;
-        
-        let clock = Clock::get()?;
+        anchor_lang::system_program::transfer(
+            transfer_ctx,
+            source_amount.checked_add(vault_reserve).ok_or(ErrorCode::Overflow)?,
+        )?;
+
+        // The program must own the vault to pay out of it
+        let intent_id_bytes = ctx.accounts.config.total_intents.to_le_bytes();
+        let vault_seeds: &[&[u8]] = &[b"intent_vault", &intent_id_bytes, &[ctx.bumps.intent_vault]];
+        anchor_lang::system_program::assign(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Assign {
+                    account_to_assign: ctx.accounts.intent_vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            &crate::ID,
+        )?;
+
         let intent = &mut ctx.accounts.intent;
+        let intent_id = ctx.accounts.config.total_intents;
         
         intent.intent_id = intent_id;
         intent.creator = ctx.accounts.creator.key();
-        intent.source_chain = Chain::Solana;
-        intent.destination_chain = destination_chain.clone();
+        intent.source_chain_id = CHAIN_ID_SOLANA;
+        intent.destination_chain_id = destination_chain_id;
         intent.source_amount = source_amount;
         intent.destination_amount_commitment = destination_amount_commitment;
         intent.source_token = Pubkey::default(); // Native SOL
@@ -640,13 +2385,19 @@ pub mod project_silence {
         intent.recipient_hash = recipient_hash;
         intent.is_shielded = is_shielded;
         intent.status = IntentStatus::Created;
-        intent.solver = None;
+        intent.allow_partial_fill = allow_partial_fill;
+        intent.filled_amount = 0;
+        intent.settled_amount = 0;
+        intent.refunded_amount = 0;
+        intent.matched_fills = 0;
+        intent.executed_fills = 0;
         intent.created_at = clock.unix_timestamp;
         intent.expires_at = clock.unix_timestamp + ttl_seconds;
         intent.executed_at = None;
         intent.destination_tx_hash = [0u8; 32];
         intent.privacy_proof = [0u8; 32];
         intent.bump = ctx.bumps.intent;
+        intent.layout_version = Intent::LAYOUT_VERSION;
         
         // Update config stats
         let config = &mut ctx.accounts.config;
@@ -655,7 +2406,7 @@ pub mod project_silence {
         emit!(IntentCreated {
             intent_id,
             creator: intent.creator,
-            destination_chain,
+            destination_chain_id,
             source_amount,
             is_shielded,
         });
@@ -663,85 +2414,194 @@ pub mod project_silence {
         Ok(())
     }
 
-    /// Match an intent with a solver
-    pub fn match_intent(ctx: Context<MatchIntent>) -> Result<()> {
+    /// Claim all or part of an intent as a solver.
+    ///
+    /// Intents created without `allow_partial_fill` must be claimed in full
+    /// by a single solver; otherwise any active solver may claim a portion
+    /// of the remaining amount.
+    pub fn match_intent(
+        ctx: Context<MatchIntent>,
+        fill_amount: u64,
+        estimated_time_secs: Option<i64>,
+    ) -> Result<()> {
+        let intent_key = ctx.accounts.intent.key();
         let intent = &mut ctx.accounts.intent;
         let solver = &ctx.accounts.solver;
         
         require!(intent.status == IntentStatus::Created, ErrorCode::IntentAlreadyMatched);
         require!(solver.is_active, ErrorCode::SolverNotActive);
         
+        ctx.accounts.config.require_operational(&ctx.accounts.chain_config)?;
+        
         let clock = Clock::get()?;
         require!(clock.unix_timestamp < intent.expires_at, ErrorCode::IntentExpired);
+        require!(
+            solver.is_live(clock.unix_timestamp, ctx.accounts.config.heartbeat_timeout_secs),
+            ErrorCode::StaleHeartbeat
+        );
         
         // Verify solver supports required chains
-        let dest_chain_bit = match intent.destination_chain {
-            Chain::Solana => 0b001,
-            Chain::Near => 0b010,
-            Chain::Zcash => 0b100,
-        };
         require!(
-            (solver.supported_chains & dest_chain_bit) != 0,
+            solver.supported_chains.contains(&intent.destination_chain_id),
             ErrorCode::ChainNotSupported
         );
         
-        intent.status = IntentStatus::Matched;
-        intent.solver = Some(ctx.accounts.solver_authority.key());
+        let remaining = intent.remaining_amount();
+        require!(fill_amount > 0 && fill_amount <= remaining, ErrorCode::InvalidFillAmount);
+        require!(
+            intent.allow_partial_fill || fill_amount == intent.source_amount,
+            ErrorCode::PartialFillNotAllowed
+        );
+        require!(fill_amount >= solver.min_fill_amount, ErrorCode::FillBelowSolverMinimum);
+        
+        let config = &ctx.accounts.config;
+        require!(
+            solver.fee_bps == 0 || ctx.accounts.chain_config.fee_bps(config) <= solver.fee_bps,
+            ErrorCode::SolverFeeLimitExceeded
+        );
+        let execution_secs = estimated_time_secs.unwrap_or(config.default_execution_secs);
+        require!(
+            execution_secs > 0 && execution_secs <= config.max_execution_secs,
+            ErrorCode::InvalidExecutionTime
+        );
+        
+        let fill = &mut ctx.accounts.fill;
+        fill.intent = intent_key;
+        fill.solver = ctx.accounts.solver_authority.key();
+        fill.amount = fill_amount;
+        fill.status = FillStatus::Matched;
+        fill.matched_at = clock.unix_timestamp;
+        fill.execution_deadline = clock.unix_timestamp.saturating_add(execution_secs);
+        fill.executed_at = None;
+        fill.destination_tx_hash = [0u8; 32];
+        fill.bump = ctx.bumps.fill;
+        fill.layout_version = IntentFill::LAYOUT_VERSION;
+        
+        intent.filled_amount = intent.filled_amount.checked_add(fill_amount).ok_or(ErrorCode::Overflow)?;
+        intent.matched_fills += 1;
+        let status = intent.status.clone();
+        intent.refresh_status(status);
         
         emit!(IntentMatched {
             intent_id: intent.intent_id,
-            solver: ctx.accounts.solver_authority.key(),
+            solver: fill.solver,
+            fill_amount,
+            remaining_amount: intent.remaining_amount(),
+            execution_deadline: fill.execution_deadline,
         });
         
         Ok(())
     }
 
-    /// Execute intent (called by solver after cross-chain transfer)
+    /// Execute a fill (called by solver after cross-chain transfer)
     pub fn execute_intent(
         ctx: Context<ExecuteIntent>,
         destination_tx_hash: [u8; 32],
         privacy_proof: Option<[u8; 32]>,
     ) -> Result<()> {
         let intent = &mut ctx.accounts.intent;
+        let fill = &mut ctx.accounts.fill;
         
-        require!(
-            intent.solver == Some(ctx.accounts.solver_authority.key()),
-            ErrorCode::NotMatchedSolver
-        );
-        require!(intent.status == IntentStatus::Matched, ErrorCode::InvalidIntentStatus);
+        require!(fill.status == FillStatus::Matched, ErrorCode::InvalidIntentStatus);
+        ctx.accounts.config.require_operational(&ctx.accounts.chain_config)?;
         
         let clock = Clock::get()?;
+        require!(clock.unix_timestamp <= fill.execution_deadline, ErrorCode::ExecutionDeadlinePassed);
+        
+        // A consumed receipt replaces the solver-reported transaction hash
+        let destination_tx_hash = match ctx.accounts.receipt.as_mut() {
+            Some(receipt) => {
+                require!(receipt.attested && !receipt.consumed, ErrorCode::InvalidReceipt);
+                require!(receipt.solver == fill.solver, ErrorCode::NotMatchedSolver);
+                require!(
+                    receipt.destination_chain_id == intent.destination_chain_id
+                        && receipt.intent_hash == CrossChainIntent::from(&**intent).hash()
+                        && receipt.recipient_hash.0 == Some(intent.recipient_hash),
+                    ErrorCode::InvalidReceipt
+                );
+                receipt.consumed = true;
+                receipt.receipt_hash
+            }
+            None => {
+                require!(!ctx.accounts.chain_config.requires_receipt, ErrorCode::ReceiptRequired);
+                destination_tx_hash
+            }
+        };
+        
+        fill.status = FillStatus::Executed;
+        fill.executed_at = Some(clock.unix_timestamp);
+        fill.destination_tx_hash = destination_tx_hash;
         
-        intent.status = IntentStatus::Executed;
+        intent.matched_fills -= 1;
+        intent.executed_fills += 1;
         intent.executed_at = Some(clock.unix_timestamp);
         intent.destination_tx_hash = destination_tx_hash;
         if let Some(proof) = privacy_proof {
             intent.privacy_proof = proof;
         }
+        let status = intent.status.clone();
+        intent.refresh_status(status);
         
         emit!(IntentExecuted {
             intent_id: intent.intent_id,
+            solver: fill.solver,
             destination_tx_hash,
         });
         
         Ok(())
     }
 
-    /// Settle intent and distribute rewards
+    /// Settle an executed fill and distribute rewards pro-rata from the vault
+    ///
+    /// If the settlement would exceed the chain or solver volume cap for the
+    /// current window, the fill stays `Executed`, `VolumeCapReached` is
+    /// emitted and the solver can retry once the window rolls over.
     pub fn settle_intent(ctx: Context<SettleIntent>) -> Result<()> {
         let intent = &mut ctx.accounts.intent;
+        let fill = &ctx.accounts.fill;
         let solver = &mut ctx.accounts.solver;
         let config = &mut ctx.accounts.config;
+        let chain_config = &mut ctx.accounts.chain_config;
+        
+        require!(fill.status == FillStatus::Executed, ErrorCode::IntentNotExecuted);
+        config.require_operational(chain_config)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let executed_at = fill.executed_at.ok_or(ErrorCode::IntentNotExecuted)?;
+        require!(
+            now >= executed_at.saturating_add(chain_config.finality_seconds as i64),
+            ErrorCode::FinalityNotReached
+        );
         
-        require!(intent.status == IntentStatus::Executed, ErrorCode::IntentNotExecuted);
+        // Enforce rate limits
+        let amount = fill.amount;
+        let window_secs = config.rate_limit_window_secs;
+        if !chain_config.has_volume_capacity(now, window_secs, amount) {
+            emit!(VolumeCapReached {
+                intent_id: intent.intent_id,
+                chain_id: intent.destination_chain_id,
+                solver: None,
+                window_volume: chain_config.window_volume(now, window_secs),
+                cap: chain_config.volume_cap,
+            });
+            return Ok(());
+        }
+        if !solver.has_volume_capacity(now, window_secs, config.solver_volume_cap, amount) {
+            emit!(VolumeCapReached {
+                intent_id: intent.intent_id,
+                chain_id: intent.destination_chain_id,
+                solver: Some(solver.solver_id),
+                window_volume: solver.window_volume(now, window_secs),
+                cap: config.solver_volume_cap,
+            });
+            return Ok(());
+        }
+        chain_config.record_volume(now, window_secs, amount);
+        solver.record_volume(now, window_secs, amount);
         
         // Calculate fees
-        let protocol_fee = (intent.source_amount as u128)
-            .checked_mul(config.protocol_fee_bps as u128)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::Overflow)? as u64;
-        let solver_reward = intent.source_amount.checked_sub(protocol_fee)
+        let protocol_fee = protocol_fee_for(amount, chain_config.fee_bps(config))?;
+        let solver_reward = amount.checked_sub(protocol_fee)
             .ok_or(ErrorCode::Overflow)?;
         
         // Transfer solver reward (from intent vault to solver)
@@ -750,52 +2610,307 @@ pub mod project_silence {
         
         // Transfer protocol fee to fee vault
         **ctx.accounts.intent_vault.try_borrow_mut_lamports()? -= protocol_fee;
-        **ctx.accounts.fee_vault.try_borrow_mut_lamports()? += protocol_fee;
+        ctx.accounts.fee_vault.add_lamports(protocol_fee)?;
+        ctx.accounts.fee_vault.credit_fee(protocol_fee, config.staker_fee_share_bps, config.total_stake);
         
         // Update solver stats
         solver.total_intents_executed += 1;
         solver.successful_intents += 1;
-        solver.total_volume += intent.source_amount;
-        solver.reputation_score = solver.reputation_score.saturating_add(1);
+        solver.total_volume += amount;
+        let latency_secs = executed_at.saturating_sub(fill.matched_at);
+        solver.record_success(&config.reputation, now, amount, latency_secs);
         
         // Update intent status
-        intent.status = IntentStatus::Settled;
+        intent.executed_fills -= 1;
+        intent.settled_amount += amount;
+        let status = intent.status.clone();
+        intent.refresh_status(status);
         
         // Update config stats
-        config.total_volume += intent.source_amount;
+        config.total_volume += amount;
         
         emit!(IntentSettled {
             intent_id: intent.intent_id,
+            solver: fill.solver,
+            fill_amount: amount,
             solver_reward,
             protocol_fee,
         });
         
+        // Return fill rent to the solver
+        ctx.accounts.fill.close(ctx.accounts.solver_authority.to_account_info())?;
+        
+        Ok(())
+    }
+
+    /// Settle several executed fills of one solver in a single transaction.
+    ///
+    /// Remaining accounts are passed as `(intent, fill, intent_vault)`
+    /// triples, all writable, for intents to `chain_config`'s chain. Fills
+    /// that would exceed a volume cap are skipped with `VolumeCapReached`.
+    pub fn settle_intents_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleIntentsBatch<'info>>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(!remaining.is_empty(), ErrorCode::EmptyBatch);
+        require!(remaining.len() % 3 == 0, ErrorCode::InvalidRemainingAccounts);
+        require!(remaining.len() / 3 <= MAX_SETTLE_BATCH, ErrorCode::SettleBatchTooLarge);
+        
+        let solver = &mut ctx.accounts.solver;
+        let config = &mut ctx.accounts.config;
+        let chain_config = &mut ctx.accounts.chain_config;
+        let solver_authority = ctx.accounts.solver_authority.to_account_info();
+        config.require_operational(chain_config)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let window_secs = config.rate_limit_window_secs;
+        let fee_bps = chain_config.fee_bps(config);
+        
+        let mut seen_fills: Vec<Pubkey> = Vec::with_capacity(remaining.len() / 3);
+        let mut settled_count: u32 = 0;
+        let mut total_amount: u64 = 0;
+        let mut total_solver_reward: u64 = 0;
+        let mut total_protocol_fee: u64 = 0;
+        
+        for accounts in remaining.chunks(3) {
+            let (intent_info, fill_info, vault_info) = (&accounts[0], &accounts[1], &accounts[2]);
+            require!(
+                intent_info.is_writable && fill_info.is_writable && vault_info.is_writable,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            require!(!seen_fills.contains(fill_info.key), ErrorCode::InvalidRemainingAccounts);
+            seen_fills.push(*fill_info.key);
+            
+            let mut intent = Account::<Intent>::try_from(intent_info)?;
+            let fill = Account::<IntentFill>::try_from(fill_info)?;
+            
+            // Validate the triple
+            let fill_address = Pubkey::create_program_address(
+                &[b"fill", intent_info.key.as_ref(), fill.solver.as_ref(), &[fill.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidRemainingAccounts)?;
+            require!(fill_address == *fill_info.key, ErrorCode::InvalidRemainingAccounts);
+            require!(fill.solver == solver.solver_id, ErrorCode::NotMatchedSolver);
+            require!(fill.status == FillStatus::Executed, ErrorCode::IntentNotExecuted);
+            require!(
+                intent.destination_chain_id == chain_config.chain_id,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            let (vault_address, _) = Pubkey::find_program_address(
+                &[b"intent_vault", intent.intent_id.to_le_bytes().as_ref()],
+                &crate::ID,
+            );
+            require!(vault_address == *vault_info.key, ErrorCode::InvalidRemainingAccounts);
+            
+            let executed_at = fill.executed_at.ok_or(ErrorCode::IntentNotExecuted)?;
+            require!(
+                now >= executed_at.saturating_add(chain_config.finality_seconds as i64),
+                ErrorCode::FinalityNotReached
+            );
+            
+            // Enforce rate limits
+            let amount = fill.amount;
+            if !chain_config.has_volume_capacity(now, window_secs, amount) {
+                emit!(VolumeCapReached {
+                    intent_id: intent.intent_id,
+                    chain_id: intent.destination_chain_id,
+                    solver: None,
+                    window_volume: chain_config.window_volume(now, window_secs),
+                    cap: chain_config.volume_cap,
+                });
+                continue;
+            }
+            if !solver.has_volume_capacity(now, window_secs, config.solver_volume_cap, amount) {
+                emit!(VolumeCapReached {
+                    intent_id: intent.intent_id,
+                    chain_id: intent.destination_chain_id,
+                    solver: Some(solver.solver_id),
+                    window_volume: solver.window_volume(now, window_secs),
+                    cap: config.solver_volume_cap,
+                });
+                continue;
+            }
+            chain_config.record_volume(now, window_secs, amount);
+            solver.record_volume(now, window_secs, amount);
+            
+            let protocol_fee = protocol_fee_for(amount, fee_bps)?;
+            let solver_reward = amount.checked_sub(protocol_fee).ok_or(ErrorCode::Overflow)?;
+            solver.record_success(&config.reputation, now, amount, executed_at.saturating_sub(fill.matched_at));
+            
+            // Release the fill's share of the vault; paid out once below
+            **vault_info.try_borrow_mut_lamports()? -= amount;
+            
+            intent.executed_fills -= 1;
+            intent.settled_amount += amount;
+            let status = intent.status.clone();
+            intent.refresh_status(status);
+            intent.exit(&crate::ID)?;
+            
+            emit!(IntentSettled {
+                intent_id: intent.intent_id,
+                solver: fill.solver,
+                fill_amount: amount,
+                solver_reward,
+                protocol_fee,
+            });
+            
+            // Return fill rent to the solver
+            fill.close(solver_authority.clone())?;
+            
+            settled_count += 1;
+            total_amount = total_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+            total_solver_reward += solver_reward;
+            total_protocol_fee += protocol_fee;
+        }
+        
+        // Pay out solver reward and protocol fee
+        **solver_authority.try_borrow_mut_lamports()? += total_solver_reward;
+        ctx.accounts.fee_vault.add_lamports(total_protocol_fee)?;
+        ctx.accounts.fee_vault.credit_fee(total_protocol_fee, config.staker_fee_share_bps, config.total_stake);
+        
+        // Update solver stats
+        solver.total_intents_executed += settled_count as u64;
+        solver.successful_intents += settled_count as u64;
+        solver.total_volume += total_amount;
+        
+        // Update config stats
+        config.total_volume += total_amount;
+        
+        emit!(IntentsBatchSettled {
+            solver: solver.solver_id,
+            chain_id: chain_config.chain_id,
+            settled_count,
+            skipped_count: (remaining.len() / 3) as u32 - settled_count,
+            total_volume: total_amount,
+            total_solver_reward,
+            total_protocol_fee,
+        });
+        
         Ok(())
     }
 
-    /// Mark intent as failed and refund creator
+    /// Mark a fill as failed and refund its portion to the creator
     pub fn fail_intent(ctx: Context<FailIntent>) -> Result<()> {
         let intent = &mut ctx.accounts.intent;
+        let fill = &ctx.accounts.fill;
         let solver = &mut ctx.accounts.solver;
         
-        require!(
-            intent.solver == Some(ctx.accounts.solver_authority.key()),
-            ErrorCode::NotMatchedSolver
-        );
-        
         // Refund creator
-        **ctx.accounts.intent_vault.try_borrow_mut_lamports()? -= intent.source_amount;
-        **ctx.accounts.creator.try_borrow_mut_lamports()? += intent.source_amount;
+        **ctx.accounts.intent_vault.try_borrow_mut_lamports()? -= fill.amount;
+        **ctx.accounts.creator.try_borrow_mut_lamports()? += fill.amount;
         
         // Update solver stats
         solver.failed_intents += 1;
-        solver.reputation_score = solver.reputation_score.saturating_sub(5);
+        solver.record_failure(&ctx.accounts.config.reputation, Clock::get()?.unix_timestamp);
         
         // Update intent status
-        intent.status = IntentStatus::Failed;
+        match fill.status {
+            FillStatus::Matched => intent.matched_fills -= 1,
+            FillStatus::Executed => intent.executed_fills -= 1,
+        }
+        intent.filled_amount -= fill.amount;
+        intent.refunded_amount += fill.amount;
+        intent.refresh_status(IntentStatus::Failed);
         
         emit!(IntentFailed {
             intent_id: intent.intent_id,
+            solver: fill.solver,
+            refunded: fill.amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Time out a fill that missed its execution deadline (permissionless).
+    ///
+    /// The fill's portion is refunded to the creator, or, when the creator
+    /// calls with `reopen` before the intent expires, returned to the
+    /// unclaimed amount so other solvers can match it. The solver is
+    /// penalized as for a failure and the fill rent goes to the caller.
+    pub fn timeout_intent(ctx: Context<TimeoutIntent>, reopen: bool) -> Result<()> {
+        let intent = &mut ctx.accounts.intent;
+        let fill = &ctx.accounts.fill;
+        let solver = &mut ctx.accounts.solver;
+        
+        let now = Clock::get()?.unix_timestamp;
+        require!(fill.status == FillStatus::Matched, ErrorCode::InvalidIntentStatus);
+        require!(now > fill.execution_deadline, ErrorCode::ExecutionDeadlineNotReached);
+        
+        if reopen {
+            require!(ctx.accounts.caller.key() == intent.creator, ErrorCode::Unauthorized);
+            require!(now < intent.expires_at, ErrorCode::IntentExpired);
+        } else {
+            // Refund creator
+            **ctx.accounts.intent_vault.try_borrow_mut_lamports()? -= fill.amount;
+            **ctx.accounts.creator.try_borrow_mut_lamports()? += fill.amount;
+            intent.refunded_amount += fill.amount;
+        }
+        
+        // Penalize solver
+        solver.failed_intents += 1;
+        solver.record_failure(&ctx.accounts.config.reputation, now);
+        
+        intent.matched_fills -= 1;
+        intent.filled_amount -= fill.amount;
+        intent.refresh_status(IntentStatus::Failed);
+        
+        emit!(IntentTimedOut {
+            intent_id: intent.intent_id,
+            solver: fill.solver,
+            amount: fill.amount,
+            reopened: reopen,
+        });
+        
+        Ok(())
+    }
+
+    /// Refund the unfilled remainder of an intent to its creator.
+    ///
+    /// An intent with no fills can be cancelled at any time (also while
+    /// paused); a partially filled intent only once it has expired.
+    pub fn cancel_intent(ctx: Context<CancelIntent>) -> Result<()> {
+        let intent = &mut ctx.accounts.intent;
+        
+        require!(intent.status == IntentStatus::Created, ErrorCode::InvalidIntentStatus);
+        let remaining = intent.remaining_amount();
+        require!(remaining > 0, ErrorCode::NothingToRefund);
+        if intent.filled_amount > 0 {
+            let now = Clock::get()?.unix_timestamp;
+            require!(now >= intent.expires_at, ErrorCode::InvalidIntentStatus);
+        }
+        
+        // Refund creator
+        **ctx.accounts.intent_vault.try_borrow_mut_lamports()? -= remaining;
+        **ctx.accounts.creator.try_borrow_mut_lamports()? += remaining;
+        
+        intent.refunded_amount += remaining;
+        intent.refresh_status(IntentStatus::Cancelled);
+        
+        emit!(IntentCancelled {
+            intent_id: intent.intent_id,
+            refunded: remaining,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a settled, failed or cancelled intent and reclaim rent
+    pub fn close_intent(ctx: Context<CloseIntent>) -> Result<()> {
+        let intent = &ctx.accounts.intent;
+        require!(intent.status.is_terminal(), ErrorCode::IntentNotTerminal);
+        
+        let state_hash = hash(&intent.to_account_info().try_borrow_data()?).to_bytes();
+        
+        // Sweep whatever is left in the vault back to the creator
+        let vault_remainder = ctx.accounts.intent_vault.lamports();
+        **ctx.accounts.intent_vault.try_borrow_mut_lamports()? -= vault_remainder;
+        **ctx.accounts.creator.try_borrow_mut_lamports()? += vault_remainder;
+        
+        emit!(IntentClosed {
+            intent_id: intent.intent_id,
+            creator: intent.creator,
+            state_hash,
         });
         
         Ok(())
@@ -812,11 +2927,15 @@ pub mod project_silence {
     }
 
     /// Verify encrypted intent amounts
+    ///
+    /// `encrypted_amounts` is `IntentAmounts` encrypted under
+    /// `one_time_pub_key` and `one_time_nonce`.
     pub fn verify_intent_amounts(
         ctx: Context<VerifyIntentAmounts>,
         computation_offset: u64,
         one_time_pub_key: [u8; 32],
         one_time_nonce: u128,
+        encrypted_amounts: [[u8; 32]; 2],
         expected_rate_bps: u64,
         min_source_amount: u128,
         protocol_fee_bps: u64,
@@ -828,6 +2947,8 @@ pub mod project_silence {
         let args = vec![
             Argument::ArcisPubkey(one_time_pub_key),
             Argument::PlaintextU128(one_time_nonce),
+            Argument::EncryptedU128(encrypted_amounts[0]),
+            Argument::EncryptedU128(encrypted_amounts[1]),
             Argument::PlaintextU64(expected_rate_bps),
             Argument::PlaintextU128(min_source_amount),
             Argument::PlaintextU64(protocol_fee_bps),
@@ -859,7 +2980,7 @@ pub mod project_silence {
         };
         
         emit!(IntentAmountsVerified {
-            result: result.ciphertexts[0],
+            result: result.ciphertexts,
             nonce: result.nonce.to_le_bytes(),
         });
         
@@ -956,302 +3077,1362 @@ pub mod project_silence {
 
     /// Deactivate a solver
     pub fn deactivate_solver(ctx: Context<DeactivateSolver>) -> Result<()> {
-        ctx.accounts.solver.is_active = false;
-        ctx.accounts.config.active_solvers = ctx.accounts.config.active_solvers.saturating_sub(1);
+        let solver = &mut ctx.accounts.solver;
+        require!(solver.is_active, ErrorCode::SolverNotActive);
+        
+        // Keep rewards earned so far claimable, then stop accruing
+        solver.checkpoint_rewards(ctx.accounts.fee_vault.reward_per_stake);
+        solver.is_active = false;
+        
+        let config = &mut ctx.accounts.config;
+        config.active_solvers = config.active_solvers.saturating_sub(1);
+        config.total_stake = config.total_stake.saturating_sub(solver.stake);
         
         emit!(SolverDeactivated {
             solver_id: ctx.accounts.solver.solver_id,
         });
         Ok(())
     }
-}
 
-// ============================================================================
-// ACCOUNT CONTEXTS
-// ============================================================================
+    /// Reactivate a deactivated solver (owner approval required)
+    pub fn reactivate_solver(ctx: Context<ReactivateSolver>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let solver = &mut ctx.accounts.solver;
+        require!(!solver.is_active, ErrorCode::SolverAlreadyActive);
+        require!(
+            ctx.accounts.solver_authority.lamports() >= config.min_solver_stake,
+            ErrorCode::InsufficientStake
+        );
+        
+        // Resume accruing staker rewards from now
+        solver.stake = config.min_solver_stake;
+        solver.checkpoint_rewards(ctx.accounts.fee_vault.reward_per_stake);
+        let now = Clock::get()?.unix_timestamp;
+        solver.refresh_reputation(&config.reputation, now);
+        solver.is_active = true;
+        solver.last_heartbeat_at = now;
+        
+        config.active_solvers += 1;
+        config.total_stake = config.total_stake.saturating_add(solver.stake);
+        
+        emit!(SolverReactivated {
+            solver_id: solver.solver_id,
+        });
+        Ok(())
+    }
+
+    /// Set how long a solver may go without a heartbeat before it stops matching
+    pub fn set_heartbeat_timeout(ctx: Context<AdminConfig>, timeout_secs: i64) -> Result<()> {
+        require!(timeout_secs >= 0, ErrorCode::InvalidHeartbeatTimeout);
+        ctx.accounts.config.heartbeat_timeout_secs = timeout_secs;
+        
+        emit!(HeartbeatTimeoutUpdated { timeout_secs });
+        Ok(())
+    }
+
+    /// Set the reputation model parameters
+    pub fn set_reputation_params(ctx: Context<AdminConfig>, params: ReputationParams) -> Result<()> {
+        require!(
+            params.half_life_secs >= 0
+                && params.target_latency_secs >= 0
+                && params.latency_penalty_secs >= 0,
+            ErrorCode::InvalidReputationParams
+        );
+        ctx.accounts.config.reputation = params.clone();
+        
+        emit!(ReputationParamsUpdated { params });
+        Ok(())
+    }
+
+    /// Set the default and maximum execution windows for matched fills
+    pub fn set_execution_window(
+        ctx: Context<AdminConfig>,
+        default_execution_secs: i64,
+        max_execution_secs: i64,
+    ) -> Result<()> {
+        require!(
+            default_execution_secs > 0 && default_execution_secs <= max_execution_secs,
+            ErrorCode::InvalidExecutionTime
+        );
+        
+        let config = &mut ctx.accounts.config;
+        config.default_execution_secs = default_execution_secs;
+        config.max_execution_secs = max_execution_secs;
+        
+        emit!(ExecutionWindowUpdated {
+            default_execution_secs,
+            max_execution_secs,
+        });
+        Ok(())
+    }
+
+    /// Set the share of protocol fees streamed to solver stakers
+    pub fn set_fee_split(ctx: Context<AdminConfig>, staker_fee_share_bps: u16) -> Result<()> {
+        require!(staker_fee_share_bps <= 10000, ErrorCode::InvalidFeeShare);
+        ctx.accounts.config.staker_fee_share_bps = staker_fee_share_bps;
+        
+        emit!(FeeSplitUpdated { staker_fee_share_bps });
+        Ok(())
+    }
+
+    /// Withdraw protocol fees (excluding unclaimed staker rewards)
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        
+        let info = fee_vault.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+        let available = info
+            .lamports()
+            .saturating_sub(rent_exempt)
+            .saturating_sub(fee_vault.unclaimed_rewards);
+        require!(amount <= available, ErrorCode::InsufficientVaultBalance);
+        
+        fee_vault.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+        fee_vault.total_withdrawn = fee_vault.total_withdrawn.saturating_add(amount);
+        
+        emit!(FeesWithdrawn {
+            amount,
+            recipient: ctx.accounts.recipient.key(),
+        });
+        Ok(())
+    }
+
+    /// Claim staker rewards accrued by a solver
+    pub fn claim_solver_rewards(ctx: Context<ClaimSolverRewards>) -> Result<()> {
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        let solver = &mut ctx.accounts.solver;
+        
+        solver.checkpoint_rewards(fee_vault.reward_per_stake);
+        let amount = solver.pending_rewards;
+        require!(amount > 0, ErrorCode::NoRewards);
+        
+        solver.pending_rewards = 0;
+        fee_vault.unclaimed_rewards = fee_vault.unclaimed_rewards.saturating_sub(amount);
+        fee_vault.sub_lamports(amount)?;
+        ctx.accounts.solver_authority.add_lamports(amount)?;
+        
+        emit!(SolverRewardsClaimed {
+            solver_id: solver.solver_id,
+            amount,
+        });
+        Ok(())
+    }
+
+    // ========================================================================
+    // EMERGENCY CONTROLS
+    // ========================================================================
+
+    /// Set the guardian allowed to pause the bridge
+    pub fn set_guardian(ctx: Context<AdminConfig>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.config.guardian = guardian;
+        
+        emit!(GuardianUpdated { guardian });
+        Ok(())
+    }
+
+    /// Pause or unpause the whole bridge (guardian may only pause)
+    pub fn set_paused(ctx: Context<GuardianConfig>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        if !paused {
+            require!(
+                ctx.accounts.authority.key() == config.owner,
+                ErrorCode::Unauthorized
+            );
+        }
+        config.paused = paused;
+        
+        emit!(BridgePauseUpdated {
+            paused,
+            authority: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    /// Trip or reset the circuit breaker for a chain (guardian may only disable)
+    pub fn set_chain_enabled(ctx: Context<SetChainEnabled>, enabled: bool) -> Result<()> {
+        if enabled {
+            require!(
+                ctx.accounts.authority.key() == ctx.accounts.config.owner,
+                ErrorCode::Unauthorized
+            );
+        }
+        let chain_config = &mut ctx.accounts.chain_config;
+        chain_config.enabled = enabled;
+        
+        emit!(ChainStatusUpdated {
+            chain_id: chain_config.chain_id,
+            enabled,
+            authority: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // ========================================================================
+    // RELAYERS
+    // ========================================================================
+
+    /// Set the relayer set attesting fulfillment receipts
+    pub fn set_relayers(ctx: Context<SetRelayers>, relayers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(relayers.len() <= MAX_RELAYERS, ErrorCode::TooManyRelayers);
+        require!(
+            threshold > 0 && threshold as usize <= relayers.len(),
+            ErrorCode::InvalidRelayerThreshold
+        );
+        
+        let relayer_set = &mut ctx.accounts.relayer_set;
+        relayer_set.relayers = relayers;
+        relayer_set.threshold = threshold;
+        relayer_set.bump = ctx.bumps.relayer_set;
+        relayer_set.layout_version = RelayerSet::LAYOUT_VERSION;
+        
+        emit!(RelayersUpdated {
+            relayer_count: relayer_set.relayers.len() as u8,
+            threshold,
+        });
+        Ok(())
+    }
+
+    /// Attest a fulfillment receipt issued on the destination chain.
+    ///
+    /// `receipt_hash` must be the `FulfillmentReceipt::hash` of the other
+    /// arguments; it keys the attestation account.
+    pub fn attest_receipt(
+        ctx: Context<AttestReceipt>,
+        receipt_hash: [u8; 32],
+        intent_hash: [u8; 32],
+        solver: Pubkey,
+        destination_chain_id: u32,
+        recipient_hash: [u8; 32],
+        amount: u128,
+        fulfilled_at: u64,
+    ) -> Result<()> {
+        let relayer = ctx.accounts.relayer.key();
+        let relayer_set = &ctx.accounts.relayer_set;
+        require!(relayer_set.relayers.contains(&relayer), ErrorCode::NotRelayer);
+        
+        let expected_hash = FulfillmentReceipt {
+            version: silence_intent::MESSAGE_VERSION,
+            intent_hash,
+            destination_chain_id,
+            solver: solver.to_bytes(),
+            recipient_hash,
+            amount,
+            fulfilled_at,
+        }
+        .hash();
+        require!(receipt_hash == expected_hash, ErrorCode::InvalidReceipt);
+        
+        let attestation = &mut ctx.accounts.receipt;
+        if attestation.attesters.is_empty() {
+            attestation.receipt_hash = receipt_hash;
+            attestation.intent_hash = intent_hash;
+            attestation.destination_chain_id = destination_chain_id;
+            attestation.solver = solver;
+            attestation.amount = amount;
+            attestation.fulfilled_at = fulfilled_at;
+            attestation.attested = false;
+            attestation.consumed = false;
+            attestation.bump = ctx.bumps.receipt;
+            attestation.layout_version = ReceiptAttestation::LAYOUT_VERSION;
+            attestation.recipient_hash = ReceiptRecipient(Some(recipient_hash));
+        }
+        require!(!attestation.attesters.contains(&relayer), ErrorCode::AlreadyAttested);
+        
+        attestation.attesters.push(relayer);
+        if attestation.attesters.len() >= relayer_set.threshold as usize {
+            attestation.attested = true;
+        }
+        
+        emit!(ReceiptAttested {
+            receipt_hash,
+            intent_hash,
+            solver,
+            attestations: attestation.attesters.len() as u8,
+            attested: attestation.attested,
+        });
+        Ok(())
+    }
+
+    /// Close a consumed or stale receipt, returning its rent to the relayer
+    /// that created it.
+    ///
+    /// Fills execute at most `max_execution_secs` after matching, so an
+    /// unconsumed receipt is stale once that long has passed since delivery.
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        let receipt = &ctx.accounts.receipt;
+        let now = Clock::get()?.unix_timestamp;
+        let usable_until = i64::try_from(receipt.fulfilled_at)
+            .unwrap_or(i64::MAX)
+            .saturating_add(ctx.accounts.config.max_execution_secs);
+        require!(receipt.consumed || now > usable_until, ErrorCode::ReceiptStillUsable);
+        
+        let state_hash = hash(&receipt.to_account_info().try_borrow_data()?).to_bytes();
+        
+        emit!(ReceiptClosed {
+            receipt_hash: receipt.receipt_hash,
+            relayer: ctx.accounts.relayer.key(),
+            state_hash,
+        });
+        
+        Ok(())
+    }
+
+    // ========================================================================
+    // RATE LIMITS
+    // ========================================================================
+
+    /// Set the rate limit window and per-solver volume cap
+    pub fn set_rate_limits(
+        ctx: Context<AdminConfig>,
+        window_secs: i64,
+        solver_volume_cap: u64,
+    ) -> Result<()> {
+        require!(window_secs > 0, ErrorCode::InvalidRateLimitWindow);
+        
+        let config = &mut ctx.accounts.config;
+        config.rate_limit_window_secs = window_secs;
+        config.solver_volume_cap = solver_volume_cap;
+        
+        emit!(RateLimitsUpdated {
+            window_secs,
+            solver_volume_cap,
+        });
+        Ok(())
+    }
+
+    // ========================================================================
+    // CHAIN REGISTRY
+    // ========================================================================
+
+    /// Register a new chain
+    pub fn register_chain(
+        ctx: Context<RegisterChain>,
+        chain_id: u32,
+        name: String,
+        min_amount: u64,
+        finality_seconds: u32,
+        fee_bps_override: Option<u16>,
+        volume_cap: u64,
+    ) -> Result<()> {
+        require!(name.len() <= 16, ErrorCode::NameTooLong);
+        if let Some(fee) = fee_bps_override {
+            require!(fee <= 1000, ErrorCode::FeeTooHigh); // Max 10%
+        }
+        
+        let chain_config = &mut ctx.accounts.chain_config;
+        chain_config.chain_id = chain_id;
+        chain_config.name = name.clone();
+        chain_config.enabled = true;
+        chain_config.min_amount = min_amount;
+        chain_config.finality_seconds = finality_seconds;
+        chain_config.fee_bps_override = fee_bps_override;
+        chain_config.volume_cap = volume_cap;
+        chain_config.volume_window = VolumeWindow::default();
+        chain_config.previous_window_volume = PreviousVolume(Some(0));
+        chain_config.requires_receipt = false;
+        chain_config.bump = ctx.bumps.chain_config;
+        chain_config.layout_version = ChainConfig::LAYOUT_VERSION;
+        
+        emit!(ChainRegistered { chain_id, name });
+        Ok(())
+    }
+
+    /// Update chain parameters
+    pub fn update_chain(
+        ctx: Context<UpdateChain>,
+        name: Option<String>,
+        min_amount: Option<u64>,
+        finality_seconds: Option<u32>,
+        fee_bps_override: Option<Option<u16>>,
+        volume_cap: Option<u64>,
+        requires_receipt: Option<bool>,
+    ) -> Result<()> {
+        let chain_config = &mut ctx.accounts.chain_config;
+        
+        if let Some(n) = name {
+            require!(n.len() <= 16, ErrorCode::NameTooLong);
+            chain_config.name = n;
+        }
+        if let Some(m) = min_amount {
+            chain_config.min_amount = m;
+        }
+        if let Some(f) = finality_seconds {
+            chain_config.finality_seconds = f;
+        }
+        if let Some(o) = fee_bps_override {
+            if let Some(fee) = o {
+                require!(fee <= 1000, ErrorCode::FeeTooHigh); // Max 10%
+            }
+            chain_config.fee_bps_override = o;
+        }
+        if let Some(c) = volume_cap {
+            chain_config.volume_cap = c;
+        }
+        if let Some(r) = requires_receipt {
+            chain_config.requires_receipt = r;
+        }
+        
+        emit!(ChainUpdated {
+            chain_id: chain_config.chain_id,
+        });
+        Ok(())
+    }
+
+    // ========================================================================
+    // ACCOUNT MIGRATIONS
+    // ========================================================================
+    //
+    // Permissionless: each reallocates a legacy account to the current size,
+    // with the payer covering the extra rent, and stamps its version. The
+    // contexts come from `migrate_accounts!`; `#[program]` only dispatches
+    // handlers written out here.
+
+    pub fn migrate_bridge_config(ctx: Context<MigrateBridgeConfig>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_fee_vault(ctx: Context<MigrateFeeVault>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_chain_config(ctx: Context<MigrateChainConfig>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_relayer_set(ctx: Context<MigrateRelayerSet>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_model(ctx: Context<MigrateModel>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_inference_request(ctx: Context<MigrateInferenceRequest>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_batch(ctx: Context<MigrateBatch>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_user_metrics(ctx: Context<MigrateUserMetrics>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_solver(ctx: Context<MigrateSolver>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_intent(ctx: Context<MigrateIntent>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_intent_fill(ctx: Context<MigrateIntentFill>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+
+    pub fn migrate_receipt(ctx: Context<MigrateReceipt>) -> Result<()> {
+        ctx.accounts.migrate()
+    }
+}
+
+// ============================================================================
+// ACCOUNT CONTEXTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeBridge<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + BridgeConfig::INIT_SPACE,
+        seeds = [b"bridge_config"],
+        bump
+    )]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(model_id: u64)]
+pub struct RegisterModel<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ModelMetadata::INIT_SPACE,
+        seeds = [b"model", model_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model: Account<'info, ModelMetadata>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ModelDirectory::INIT_SPACE,
+        seeds = [b"model_directory"],
+        bump
+    )]
+    pub directory: Account<'info, ModelDirectory>,
+    
+    // Must follow `directory`, whose `model_count` picks the page
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ModelIndex::INIT_SPACE,
+        seeds = [b"model_index", directory.next_page().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model_index: Account<'info, ModelIndex>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ModelListing::INIT_SPACE,
+        seeds = [b"model_listing", model_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model_listing: Account<'info, ModelListing>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct IndexModel<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub model: Account<'info, ModelMetadata>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ModelDirectory::INIT_SPACE,
+        seeds = [b"model_directory"],
+        bump
+    )]
+    pub directory: Account<'info, ModelDirectory>,
+    
+    // Must follow `directory`, whose `model_count` picks the page
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ModelIndex::INIT_SPACE,
+        seeds = [b"model_index", directory.next_page().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model_index: Account<'info, ModelIndex>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ModelListing::INIT_SPACE,
+        seeds = [b"model_listing", model.model_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model_listing: Account<'info, ModelListing>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateModel<'info> {
+    /// Model owner or one of its delegates
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist yet
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    /// CHECK: `ModelListing` of the model, absent until the model is indexed
+    #[account(seeds = [b"model_listing", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_listing: UncheckedAccount<'info>,
+    
+    /// Index page named by the listing; required once the model is listed
+    #[account(mut)]
+    pub model_index: Option<Account<'info, ModelIndex>>,
+}
+
+#[derive(Accounts)]
+#[instruction(version: String)]
+pub struct PublishModelVersion<'info> {
+    /// Model owner or one of its delegates
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist yet
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ModelVersion::INIT_SPACE,
+        seeds = [b"model_version", model.model_id.to_le_bytes().as_ref(), version.as_bytes()],
+        bump
+    )]
+    pub model_version: Account<'info, ModelVersion>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeprecateModelVersion<'info> {
+    /// Model owner or one of its delegates
+    pub authority: Signer<'info>,
+    
+    pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist yet
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"model_version", model.model_id.to_le_bytes().as_ref(), model_version.version.as_bytes()],
+        bump = model_version.bump
+    )]
+    pub model_version: Account<'info, ModelVersion>,
+}
+
+#[derive(Accounts)]
+pub struct ManageModel<'info> {
+    #[account(mut, constraint = owner.key() == model.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    pub model: Account<'info, ModelMetadata>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ModelAccess::INIT_SPACE,
+        seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model_access: Account<'info, ModelAccess>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetModelOperators<'info> {
+    /// Model owner or one of its delegates
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist yet
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ModelOperators::INIT_SPACE,
+        seeds = [b"model_operators", model.model_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub model_operators: Account<'info, ModelOperators>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptModelOwnership<'info> {
+    pub new_owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub model: Account<'info, ModelMetadata>,
+    
+    #[account(
+        mut,
+        seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()],
+        bump = model_access.bump,
+        constraint = model_access.pending_owner == Some(new_owner.key()) @ ErrorCode::NotPendingOwner
+    )]
+    pub model_access: Account<'info, ModelAccess>,
+}
+
+#[derive(Accounts)]
+pub struct CreateInferenceRequest<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(constraint = model.is_active @ ErrorCode::ModelNotActive)]
+    pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    /// CHECK: Payout address of the model, checked in the handler; receives payment
+    #[account(mut)]
+    pub model_owner: AccountInfo<'info>,
+    
+    /// CHECK: `ModelVersion` to pin, checked in the handler; the model's
+    /// current version leaves the request unpinned if it was never published
+    pub model_version: UncheckedAccount<'info>,
+    
+    /// Credits to debit instead of paying `model_owner`
+    #[account(
+        mut,
+        seeds = [b"credit", user.key().as_ref(), model_owner.key().as_ref()],
+        bump = credit_account.bump
+    )]
+    pub credit_account: Option<Account<'info, CreditAccount>>,
+    
+    /// Subscription to use instead of paying `model_owner`
+    #[account(
+        mut,
+        seeds = [b"subscription", user.key().as_ref(), model.model_id.to_le_bytes().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    
+    // Must precede `request`, whose seeds use `next_request_id`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserMetrics::INIT_SPACE,
+        seeds = [b"user_metrics", user.key().as_ref()],
+        bump
+    )]
+    pub user_metrics: Account<'info, UserMetrics>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + InferenceRequest::INIT_SPACE,
+        seeds = [
+            b"request",
+            user.key().as_ref(),
+            user_metrics.next_request_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub request: Account<'info, InferenceRequest>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("process_inference", payer)]
+#[derive(Accounts)]
+pub struct InitProcessInferenceCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("process_inference", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct ProcessInference<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_INFERENCE))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("process_inference")]
+#[derive(Accounts)]
+pub struct ProcessInferenceCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_INFERENCE))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StoreInferenceResult<'info> {
+    #[account(constraint = authority.key() == request.user @ ErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub request: Account<'info, InferenceRequest>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_metrics", request.user.as_ref()],
+        bump = user_metrics.bump
+    )]
+    pub user_metrics: Account<'info, UserMetrics>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBatchInference<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(constraint = model.is_active @ ErrorCode::ModelNotActive)]
+    pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    /// CHECK: Payout address of the model, checked in the handler; receives payment
+    #[account(mut)]
+    pub model_owner: AccountInfo<'info>,
+    
+    /// CHECK: `ModelVersion` to pin, checked in the handler; the model's
+    /// current version leaves the request unpinned if it was never published
+    pub model_version: UncheckedAccount<'info>,
+    
+    /// Credits to debit instead of paying `model_owner`
+    #[account(
+        mut,
+        seeds = [b"credit", user.key().as_ref(), model_owner.key().as_ref()],
+        bump = credit_account.bump
+    )]
+    pub credit_account: Option<Account<'info, CreditAccount>>,
+    
+    /// Subscription to use instead of paying `model_owner`
+    #[account(
+        mut,
+        seeds = [b"subscription", user.key().as_ref(), model.model_id.to_le_bytes().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    
+    // Must precede `batch`, whose seeds use `next_batch_id`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserMetrics::INIT_SPACE,
+        seeds = [b"user_metrics", user.key().as_ref()],
+        bump
+    )]
+    pub user_metrics: Account<'info, UserMetrics>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + BatchInference::INIT_SPACE,
+        seeds = [
+            b"batch",
+            user.key().as_ref(),
+            user_metrics.next_batch_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub batch: Account<'info, BatchInference>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseInferenceRequest<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = user,
+        constraint = request.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub request: Account<'info, InferenceRequest>,
+}
+
+#[derive(Accounts)]
+pub struct RecordBatchResults<'info> {
+    /// One of the model's operators
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub batch: Account<'info, BatchInference>,
+    
+    #[account(
+        seeds = [b"model_operators", batch.model_id.to_le_bytes().as_ref()],
+        bump = model_operators.bump,
+        constraint = model_operators.operators.contains(&authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub model_operators: Account<'info, ModelOperators>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_metrics", batch.user.as_ref()],
+        bump = user_metrics.bump
+    )]
+    pub user_metrics: Account<'info, UserMetrics>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBatch<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = user,
+        constraint = batch.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub batch: Account<'info, BatchInference>,
+}
 
 #[derive(Accounts)]
-pub struct InitializeBridge<'info> {
+pub struct TopUpCredits<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub user: Signer<'info>,
+    
+    /// CHECK: Payout address the credits are for; only its key is used
+    pub provider: UncheckedAccount<'info>,
     
     #[account(
-        init,
-        payer = owner,
-        space = 8 + BridgeConfig::INIT_SPACE,
-        seeds = [b"bridge_config"],
+        init_if_needed,
+        payer = user,
+        space = 8 + CreditAccount::INIT_SPACE,
+        seeds = [b"credit", user.key().as_ref(), provider.key().as_ref()],
         bump
     )]
-    pub config: Account<'info, BridgeConfig>,
-    
-    /// CHECK: Fee vault account
-    #[account(mut)]
-    pub fee_vault: AccountInfo<'info>,
+    pub credit_account: Account<'info, CreditAccount>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(model_id: u64)]
-pub struct RegisterModel<'info> {
+pub struct WithdrawCredits<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub user: Signer<'info>,
     
     #[account(
-        init,
-        payer = owner,
-        space = 8 + ModelMetadata::INIT_SPACE,
-        seeds = [b"model", model_id.to_le_bytes().as_ref()],
-        bump
+        mut,
+        seeds = [b"credit", user.key().as_ref(), credit_account.provider.as_ref()],
+        bump = credit_account.bump
     )]
-    pub model: Account<'info, ModelMetadata>,
-    
-    pub system_program: Program<'info, System>,
+    pub credit_account: Account<'info, CreditAccount>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateModel<'info> {
-    #[account(constraint = owner.key() == model.owner @ ErrorCode::Unauthorized)]
-    pub owner: Signer<'info>,
+pub struct ClaimCreditEarnings<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
     
+    #[account(
+        mut,
+        seeds = [b"credit", credit_account.user.as_ref(), provider.key().as_ref()],
+        bump = credit_account.bump
+    )]
+    pub credit_account: Account<'info, CreditAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(plan_id: u8)]
+pub struct SetSubscriptionPlan<'info> {
+    /// Model owner or one of its delegates
     #[account(mut)]
+    pub authority: Signer<'info>,
+    
     pub model: Account<'info, ModelMetadata>,
+    
+    /// CHECK: `ModelAccess` of the model, may not exist yet
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SubscriptionPlan::INIT_SPACE,
+        seeds = [b"subscription_plan", model.model_id.to_le_bytes().as_ref(), &[plan_id]],
+        bump
+    )]
+    pub plan: Account<'info, SubscriptionPlan>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(request_id: u64)]
-pub struct CreateInferenceRequest<'info> {
+pub struct Subscribe<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
     #[account(constraint = model.is_active @ ErrorCode::ModelNotActive)]
     pub model: Account<'info, ModelMetadata>,
     
-    /// CHECK: Model owner receives payment
-    #[account(mut, constraint = model_owner.key() == model.owner @ ErrorCode::InvalidOwner)]
+    /// CHECK: `ModelAccess` of the model, may not exist
+    #[account(seeds = [b"model_access", model.model_id.to_le_bytes().as_ref()], bump)]
+    pub model_access: UncheckedAccount<'info>,
+    
+    /// CHECK: Payout address of the model, checked in the handler; receives payment
+    #[account(mut)]
     pub model_owner: AccountInfo<'info>,
     
     #[account(
-        init,
-        payer = user,
-        space = 8 + InferenceRequest::INIT_SPACE,
-        seeds = [b"request", request_id.to_le_bytes().as_ref()],
-        bump
+        seeds = [b"subscription_plan", model.model_id.to_le_bytes().as_ref(), &[plan.plan_id]],
+        bump = plan.bump,
+        constraint = plan.is_active @ ErrorCode::InvalidSubscriptionPlan
     )]
-    pub request: Account<'info, InferenceRequest>,
+    pub plan: Account<'info, SubscriptionPlan>,
     
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + UserMetrics::INIT_SPACE,
-        seeds = [b"user_metrics", user.key().as_ref()],
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", user.key().as_ref(), model.model_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub user_metrics: Account<'info, UserMetrics>,
+    pub subscription: Account<'info, Subscription>,
     
     pub system_program: Program<'info, System>,
 }
 
-#[init_computation_definition_accounts("process_inference", payer)]
 #[derive(Accounts)]
-pub struct InitProcessInferenceCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
+pub struct RegisterSolver<'info> {
     #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
+    pub user: Signer<'info>,
+    
+    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Solver::INIT_SPACE,
+        seeds = [b"solver", user.key().as_ref()],
+        bump
+    )]
+    pub solver: Account<'info, Solver>,
+    
+    #[account(seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    
     pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("process_inference", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct ProcessInference<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
+pub struct UpdateSolver<'info> {
+    pub solver_authority: Signer<'info>,
+    
     #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
+        mut,
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump
     )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(mut, address = derive_mempool_pda!())]
-    /// CHECK: mempool_account
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_execpool_pda!())]
-    /// CHECK: executing_pool
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset))]
-    /// CHECK: computation_account
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_INFERENCE))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+    pub solver: Account<'info, Solver>,
 }
 
-#[callback_accounts("process_inference")]
 #[derive(Accounts)]
-pub struct ProcessInferenceCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_PROCESS_INFERENCE))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar
-    pub instructions_sysvar: AccountInfo<'info>,
+pub struct SolverHeartbeat<'info> {
+    pub solver_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump
+    )]
+    pub solver: Account<'info, Solver>,
 }
 
 #[derive(Accounts)]
-pub struct StoreInferenceResult<'info> {
-    #[account(constraint = authority.key() == request.user @ ErrorCode::Unauthorized)]
-    pub authority: Signer<'info>,
+pub struct RefreshReputation<'info> {
+    #[account(mut, seeds = [b"solver", solver.solver_id.as_ref()], bump = solver.bump)]
+    pub solver: Account<'info, Solver>,
     
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: u32)]
+pub struct CreateIntent<'info> {
     #[account(mut)]
-    pub request: Account<'info, InferenceRequest>,
+    pub creator: Signer<'info>,
+    
+    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        seeds = [b"chain_config", destination_chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Intent::INIT_SPACE,
+        seeds = [b"intent", config.total_intents.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub intent: Account<'info, Intent>,
     
+    /// CHECK: Intent vault PDA holding escrowed funds
     #[account(
         mut,
-        seeds = [b"user_metrics", request.user.as_ref()],
-        bump = user_metrics.bump
+        seeds = [b"intent_vault", config.total_intents.to_le_bytes().as_ref()],
+        bump
     )]
-    pub user_metrics: Account<'info, UserMetrics>,
+    pub intent_vault: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(batch_id: u64)]
-pub struct CreateBatchInference<'info> {
+pub struct MatchIntent<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(constraint = model.is_active @ ErrorCode::ModelNotActive)]
-    pub model: Account<'info, ModelMetadata>,
+    pub solver_authority: Signer<'info>,
     
-    /// CHECK: Model owner receives payment
-    #[account(mut, constraint = model_owner.key() == model.owner @ ErrorCode::InvalidOwner)]
-    pub model_owner: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = intent.status == IntentStatus::Created @ ErrorCode::IntentAlreadyMatched
+    )]
+    pub intent: Account<'info, Intent>,
     
     #[account(
         init,
-        payer = user,
-        space = 8 + BatchInference::INIT_SPACE,
-        seeds = [b"batch", batch_id.to_le_bytes().as_ref()],
+        payer = solver_authority,
+        space = 8 + IntentFill::INIT_SPACE,
+        seeds = [b"fill", intent.key().as_ref(), solver_authority.key().as_ref()],
         bump
     )]
-    pub batch: Account<'info, BatchInference>,
+    pub fill: Account<'info, IntentFill>,
     
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + UserMetrics::INIT_SPACE,
-        seeds = [b"user_metrics", user.key().as_ref()],
-        bump
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump,
+        constraint = solver.is_active @ ErrorCode::SolverNotActive
     )]
-    pub user_metrics: Account<'info, UserMetrics>,
+    pub solver: Account<'info, Solver>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        seeds = [b"chain_config", intent.destination_chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct RegisterSolver<'info> {
+pub struct ExecuteIntent<'info> {
+    pub solver_authority: Signer<'info>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub intent: Account<'info, Intent>,
+    
+    #[account(
+        mut,
+        seeds = [b"fill", intent.key().as_ref(), solver_authority.key().as_ref()],
+        bump = fill.bump
+    )]
+    pub fill: Account<'info, IntentFill>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        seeds = [b"chain_config", intent.destination_chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    /// Relayer-attested fulfillment receipt (required when the chain demands one)
+    #[account(mut, seeds = [b"receipt", receipt.receipt_hash.as_ref()], bump = receipt.bump)]
+    pub receipt: Option<Account<'info, ReceiptAttestation>>,
+}
+
+#[derive(Accounts)]
+pub struct SettleIntent<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub intent: Account<'info, Intent>,
+    
+    #[account(
+        mut,
+        seeds = [b"fill", intent.key().as_ref(), fill.solver.as_ref()],
+        bump = fill.bump,
+        constraint = fill.status == FillStatus::Executed @ ErrorCode::IntentNotExecuted
+    )]
+    pub fill: Account<'info, IntentFill>,
+    
+    /// CHECK: Intent vault PDA holding escrowed funds - constrained by seeds
+    #[account(
+        mut,
+        seeds = [b"intent_vault", intent.intent_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub intent_vault: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"solver", fill.solver.as_ref()],
+        bump = solver.bump
+    )]
+    pub solver: Account<'info, Solver>,
+    
+    /// CHECK: Solver authority receives reward and fill rent
+    #[account(mut, constraint = solver_authority.key() == fill.solver @ ErrorCode::NotMatchedSolver)]
+    pub solver_authority: AccountInfo<'info>,
     
     #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
     pub config: Account<'info, BridgeConfig>,
     
     #[account(
-        init,
-        payer = user,
-        space = 8 + Solver::INIT_SPACE,
-        seeds = [b"solver", user.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"chain_config", intent.destination_chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump,
+        constraint = fee_vault.key() == config.fee_vault @ ErrorCode::InvalidFeeVault
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct SettleIntentsBatch<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump
     )]
     pub solver: Account<'info, Solver>,
     
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-#[instruction(intent_id: u64)]
-pub struct CreateIntent<'info> {
+    /// CHECK: Solver authority receives rewards and fill rent
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub solver_authority: AccountInfo<'info>,
     
     #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
     pub config: Account<'info, BridgeConfig>,
     
     #[account(
-        init,
-        payer = creator,
-        space = 8 + Intent::INIT_SPACE,
-        seeds = [b"intent", intent_id.to_le_bytes().as_ref()],
-        bump
+        mut,
+        seeds = [b"chain_config", chain_config.chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
     )]
-    pub intent: Account<'info, Intent>,
+    pub chain_config: Account<'info, ChainConfig>,
     
-    /// CHECK: Intent vault PDA holding escrowed funds
     #[account(
         mut,
-        seeds = [b"intent_vault", intent_id.to_le_bytes().as_ref()],
-        bump
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump,
+        constraint = fee_vault.key() == config.fee_vault @ ErrorCode::InvalidFeeVault
     )]
-    pub intent_vault: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
+    pub fee_vault: Account<'info, FeeVault>,
 }
 
 #[derive(Accounts)]
-pub struct MatchIntent<'info> {
+pub struct FailIntent<'info> {
+    #[account(mut)]
     pub solver_authority: Signer<'info>,
     
+    #[account(mut)]
+    pub intent: Account<'info, Intent>,
+    
     #[account(
         mut,
-        constraint = intent.status == IntentStatus::Created @ ErrorCode::IntentAlreadyMatched
+        close = solver_authority,
+        seeds = [b"fill", intent.key().as_ref(), solver_authority.key().as_ref()],
+        bump = fill.bump
     )]
-    pub intent: Account<'info, Intent>,
+    pub fill: Account<'info, IntentFill>,
     
+    /// CHECK: Intent vault PDA holding escrowed funds - constrained by seeds
     #[account(
-        seeds = [b"solver", solver_authority.key().as_ref()],
-        bump = solver.bump,
-        constraint = solver.is_active @ ErrorCode::SolverNotActive
+        mut,
+        seeds = [b"intent_vault", intent.intent_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub solver: Account<'info, Solver>,
-}
-
-#[derive(Accounts)]
-pub struct ExecuteIntent<'info> {
-    pub solver_authority: Signer<'info>,
+    pub intent_vault: AccountInfo<'info>,
+    
+    /// CHECK: Creator receives refund
+    #[account(mut, constraint = creator.key() == intent.creator @ ErrorCode::InvalidCreator)]
+    pub creator: AccountInfo<'info>,
     
     #[account(
         mut,
-        constraint = intent.solver == Some(solver_authority.key()) @ ErrorCode::NotMatchedSolver
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump
     )]
-    pub intent: Account<'info, Intent>,
+    pub solver: Account<'info, Solver>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
 }
 
 #[derive(Accounts)]
-pub struct SettleIntent<'info> {
-    pub authority: Signer<'info>,
+pub struct TimeoutIntent<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    #[account(mut)]
+    pub intent: Account<'info, Intent>,
     
     #[account(
         mut,
-        constraint = intent.status == IntentStatus::Executed @ ErrorCode::IntentNotExecuted
+        close = caller,
+        seeds = [b"fill", intent.key().as_ref(), fill.solver.as_ref()],
+        bump = fill.bump
     )]
-    pub intent: Account<'info, Intent>,
+    pub fill: Account<'info, IntentFill>,
     
     /// CHECK: Intent vault PDA holding escrowed funds - constrained by seeds
     #[account(
@@ -1261,32 +4442,29 @@ pub struct SettleIntent<'info> {
     )]
     pub intent_vault: AccountInfo<'info>,
     
+    /// CHECK: Creator receives refund
+    #[account(mut, constraint = creator.key() == intent.creator @ ErrorCode::InvalidCreator)]
+    pub creator: AccountInfo<'info>,
+    
     #[account(
         mut,
-        seeds = [b"solver", intent.solver.unwrap().as_ref()],
+        seeds = [b"solver", fill.solver.as_ref()],
         bump = solver.bump
     )]
     pub solver: Account<'info, Solver>,
     
-    /// CHECK: Solver authority receives reward
-    #[account(mut, constraint = solver_authority.key() == intent.solver.unwrap() @ ErrorCode::NotMatchedSolver)]
-    pub solver_authority: AccountInfo<'info>,
-    
-    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
     pub config: Account<'info, BridgeConfig>,
-    
-    /// CHECK: Fee vault receives protocol fee
-    #[account(mut, constraint = fee_vault.key() == config.fee_vault @ ErrorCode::InvalidFeeVault)]
-    pub fee_vault: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-pub struct FailIntent<'info> {
-    pub solver_authority: Signer<'info>,
+pub struct CancelIntent<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
     
     #[account(
         mut,
-        constraint = intent.solver == Some(solver_authority.key()) @ ErrorCode::NotMatchedSolver
+        constraint = intent.creator == creator.key() @ ErrorCode::InvalidCreator
     )]
     pub intent: Account<'info, Intent>,
     
@@ -1297,17 +4475,27 @@ pub struct FailIntent<'info> {
         bump
     )]
     pub intent_vault: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseIntent<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
     
-    /// CHECK: Creator receives refund
-    #[account(mut, constraint = creator.key() == intent.creator @ ErrorCode::InvalidCreator)]
-    pub creator: AccountInfo<'info>,
+    #[account(
+        mut,
+        close = creator,
+        constraint = intent.creator == creator.key() @ ErrorCode::InvalidCreator
+    )]
+    pub intent: Account<'info, Intent>,
     
+    /// CHECK: Intent vault PDA holding escrowed funds - constrained by seeds
     #[account(
         mut,
-        seeds = [b"solver", solver_authority.key().as_ref()],
-        bump = solver.bump
+        seeds = [b"intent_vault", intent.intent_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub solver: Account<'info, Solver>,
+    pub intent_vault: AccountInfo<'info>,
 }
 
 // Encrypted computation account contexts
@@ -1416,88 +4604,286 @@ pub struct InitVerifyAttestationCompDef<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[init_computation_definition_accounts("generate_privacy_proof", payer)]
+#[init_computation_definition_accounts("generate_privacy_proof", payer)]
+#[derive(Accounts)]
+pub struct InitGeneratePrivacyProofCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("generate_privacy_proof", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct GeneratePrivacyProof<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("generate_privacy_proof")]
+#[derive(Accounts)]
+pub struct GeneratePrivacyProofCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminConfig<'info> {
+    #[account(constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateSolver<'info> {
+    #[account(constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(mut)]
+    pub solver: Account<'info, Solver>,
+    
+    #[account(seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateSolver<'info> {
+    #[account(constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump
+    )]
+    pub solver: Account<'info, Solver>,
+    
+    /// CHECK: Solver authority, checked for minimum stake
+    pub solver_authority: AccountInfo<'info>,
+    
+    #[account(seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Any account chosen by the owner to receive fees
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSolverRewards<'info> {
+    #[account(mut)]
+    pub solver_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"solver", solver_authority.key().as_ref()],
+        bump = solver.bump
+    )]
+    pub solver: Account<'info, Solver>,
+    
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianConfig<'info> {
+    #[account(
+        constraint = authority.key() == config.owner
+            || authority.key() == config.guardian @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetChainEnabled<'info> {
+    #[account(
+        constraint = authority.key() == config.owner
+            || authority.key() == config.guardian @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"chain_config", chain_config.chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u32)]
+pub struct RegisterChain<'info> {
+    #[account(mut, constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ChainConfig::INIT_SPACE,
+        seeds = [b"chain_config", chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
-pub struct InitGeneratePrivacyProofCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
+pub struct SetRelayers<'info> {
+    #[account(mut, constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
+    pub owner: Signer<'info>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
+    pub config: Account<'info, BridgeConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + RelayerSet::INIT_SPACE,
+        seeds = [b"relayer_set"],
+        bump
+    )]
+    pub relayer_set: Account<'info, RelayerSet>,
+    
     pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("generate_privacy_proof", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct GeneratePrivacyProof<'info> {
+#[instruction(receipt_hash: [u8; 32])]
+pub struct AttestReceipt<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub relayer: Signer<'info>,
+    
+    #[account(seeds = [b"relayer_set"], bump = relayer_set.bump)]
+    pub relayer_set: Account<'info, RelayerSet>,
+    
     #[account(
         init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
+        payer = relayer,
+        space = 8 + ReceiptAttestation::INIT_SPACE,
+        seeds = [b"receipt", receipt_hash.as_ref()],
+        bump
     )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(mut, address = derive_mempool_pda!())]
-    /// CHECK: mempool_account
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_execpool_pda!())]
-    /// CHECK: executing_pool
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset))]
-    /// CHECK: computation_account
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
-    pub clock_account: Account<'info, ClockAccount>,
+    pub receipt: Account<'info, ReceiptAttestation>,
+    
     pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-}
-
-#[callback_accounts("generate_privacy_proof")]
-#[derive(Accounts)]
-pub struct GeneratePrivacyProofCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar
-    pub instructions_sysvar: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-pub struct AdminConfig<'info> {
-    #[account(constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
-    pub owner: Signer<'info>,
+pub struct CloseReceipt<'info> {
+    /// Relayer that created the receipt and paid its rent
+    #[account(mut)]
+    pub relayer: Signer<'info>,
     
-    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    #[account(
+        mut,
+        close = relayer,
+        constraint = receipt.attesters.first() == Some(&relayer.key()) @ ErrorCode::Unauthorized
+    )]
+    pub receipt: Account<'info, ReceiptAttestation>,
+    
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
     pub config: Account<'info, BridgeConfig>,
 }
 
 #[derive(Accounts)]
-pub struct DeactivateSolver<'info> {
+pub struct UpdateChain<'info> {
     #[account(constraint = owner.key() == config.owner @ ErrorCode::Unauthorized)]
     pub owner: Signer<'info>,
     
-    #[account(mut, seeds = [b"bridge_config"], bump = config.bump)]
+    #[account(seeds = [b"bridge_config"], bump = config.bump)]
     pub config: Account<'info, BridgeConfig>,
     
-    #[account(mut)]
-    pub solver: Account<'info, Solver>,
+    #[account(
+        mut,
+        seeds = [b"chain_config", chain_config.chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+}
+
+migrate_accounts! {
+    MigrateBridgeConfig => BridgeConfig,
+    MigrateFeeVault => FeeVault,
+    MigrateChainConfig => ChainConfig,
+    MigrateRelayerSet => RelayerSet,
+    MigrateModel => ModelMetadata,
+    MigrateInferenceRequest => InferenceRequest,
+    MigrateBatch => BatchInference,
+    MigrateUserMetrics => UserMetrics,
+    MigrateSolver => Solver,
+    MigrateIntent => Intent,
+    MigrateIntentFill => IntentFill,
+    MigrateReceipt => ReceiptAttestation,
 }
 
 // ============================================================================
@@ -1523,6 +4909,60 @@ pub struct ModelUpdated {
     pub model_id: u64,
 }
 
+#[event]
+pub struct ModelListed {
+    pub model_id: u64,
+    pub page: u32,
+}
+
+#[event]
+pub struct ModelVersionPublished {
+    pub model_id: u64,
+    pub version: String,
+    pub weights_commitment: [u8; 32],
+    pub changelog_hash: [u8; 32],
+}
+
+#[event]
+pub struct ModelVersionDeprecated {
+    pub model_id: u64,
+    pub version: String,
+}
+
+#[event]
+pub struct ModelOwnershipTransferStarted {
+    pub model_id: u64,
+    pub owner: Pubkey,
+    /// `None` when a pending transfer was cancelled
+    pub pending_owner: Option<Pubkey>,
+}
+
+#[event]
+pub struct ModelOwnershipTransferred {
+    pub model_id: u64,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct ModelDelegatesUpdated {
+    pub model_id: u64,
+    pub delegates: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ModelOperatorsUpdated {
+    pub model_id: u64,
+    pub operators: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ModelPayoutUpdated {
+    pub model_id: u64,
+    /// Address now paid for inferences
+    pub payout: Pubkey,
+}
+
 #[event]
 pub struct InferenceRequestCreated {
     pub request_id: u64,
@@ -1539,12 +4979,79 @@ pub struct InferenceProcessed {
 #[event]
 pub struct InferenceResultStored {
     pub request_id: u64,
+    pub user: Pubkey,
     pub verified: bool,
 }
 
+#[event]
+pub struct InferenceRequestClosed {
+    pub request_id: u64,
+    pub user: Pubkey,
+    /// SHA-256 of the final account data, for indexers
+    pub state_hash: [u8; 32],
+}
+
+#[event]
+pub struct BatchResultsRecorded {
+    pub batch_id: u64,
+    pub user: Pubkey,
+    pub completed_count: u32,
+    pub failed_count: u32,
+}
+
+#[event]
+pub struct BatchClosed {
+    pub batch_id: u64,
+    pub user: Pubkey,
+    /// SHA-256 of the final account data, for indexers
+    pub state_hash: [u8; 32],
+}
+
+#[event]
+pub struct CreditsToppedUp {
+    pub user: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct CreditsWithdrawn {
+    pub user: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct CreditEarningsClaimed {
+    pub user: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SubscriptionPlanUpdated {
+    pub model_id: u64,
+    pub plan_id: u8,
+    pub inferences_per_epoch: u32,
+    pub price: u64,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct Subscribed {
+    pub user: Pubkey,
+    pub model_id: u64,
+    pub plan_id: u8,
+    pub epoch: u64,
+    pub inferences_per_epoch: u32,
+}
+
 #[event]
 pub struct BatchInferenceCreated {
     pub batch_id: u64,
+    pub user: Pubkey,
     pub model_id: u64,
     pub prompt_count: u32,
 }
@@ -1552,7 +5059,7 @@ pub struct BatchInferenceCreated {
 #[event]
 pub struct SolverRegistered {
     pub solver_id: Pubkey,
-    pub supported_chains: u8,
+    pub supported_chains: Vec<u32>,
 }
 
 #[event]
@@ -1560,11 +5067,45 @@ pub struct SolverDeactivated {
     pub solver_id: Pubkey,
 }
 
+#[event]
+pub struct SolverReactivated {
+    pub solver_id: Pubkey,
+}
+
+#[event]
+pub struct SolverUpdated {
+    pub solver_id: Pubkey,
+    pub supported_chains: Vec<u32>,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct SolverHeartbeatRecorded {
+    pub solver_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HeartbeatTimeoutUpdated {
+    pub timeout_secs: i64,
+}
+
+#[event]
+pub struct ReputationParamsUpdated {
+    pub params: ReputationParams,
+}
+
+#[event]
+pub struct ReputationRefreshed {
+    pub solver_id: Pubkey,
+    pub reputation_score: u32,
+}
+
 #[event]
 pub struct IntentCreated {
     pub intent_id: u64,
     pub creator: Pubkey,
-    pub destination_chain: Chain,
+    pub destination_chain_id: u32,
     pub source_amount: u64,
     pub is_shielded: bool,
 }
@@ -1573,29 +5114,77 @@ pub struct IntentCreated {
 pub struct IntentMatched {
     pub intent_id: u64,
     pub solver: Pubkey,
+    pub fill_amount: u64,
+    pub remaining_amount: u64,
+    pub execution_deadline: i64,
 }
 
 #[event]
 pub struct IntentExecuted {
     pub intent_id: u64,
+    pub solver: Pubkey,
     pub destination_tx_hash: [u8; 32],
 }
 
 #[event]
 pub struct IntentSettled {
     pub intent_id: u64,
+    pub solver: Pubkey,
+    pub fill_amount: u64,
     pub solver_reward: u64,
     pub protocol_fee: u64,
 }
 
+#[event]
+pub struct IntentsBatchSettled {
+    pub solver: Pubkey,
+    pub chain_id: u32,
+    pub settled_count: u32,
+    pub skipped_count: u32,
+    pub total_volume: u64,
+    pub total_solver_reward: u64,
+    pub total_protocol_fee: u64,
+}
+
 #[event]
 pub struct IntentFailed {
     pub intent_id: u64,
+    pub solver: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct IntentTimedOut {
+    pub intent_id: u64,
+    pub solver: Pubkey,
+    pub amount: u64,
+    pub reopened: bool,
+}
+
+#[event]
+pub struct ExecutionWindowUpdated {
+    pub default_execution_secs: i64,
+    pub max_execution_secs: i64,
+}
+
+#[event]
+pub struct IntentCancelled {
+    pub intent_id: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct IntentClosed {
+    pub intent_id: u64,
+    pub creator: Pubkey,
+    /// SHA-256 of the final account data, for indexers
+    pub state_hash: [u8; 32],
 }
 
 #[event]
 pub struct IntentAmountsVerified {
-    pub result: [u8; 32],
+    /// `IntentVerification` encrypted to the observer
+    pub result: [[u8; 32]; 3],
     pub nonce: [u8; 16],
 }
 
@@ -1610,6 +5199,98 @@ pub struct ProtocolFeeUpdated {
     pub fee_bps: u16,
 }
 
+#[event]
+pub struct FeeSplitUpdated {
+    pub staker_fee_share_bps: u16,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub amount: u64,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct SolverRewardsClaimed {
+    pub solver_id: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct BridgePauseUpdated {
+    pub paused: bool,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct ChainStatusUpdated {
+    pub chain_id: u32,
+    pub enabled: bool,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RelayersUpdated {
+    pub relayer_count: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ReceiptAttested {
+    pub receipt_hash: [u8; 32],
+    pub intent_hash: [u8; 32],
+    pub solver: Pubkey,
+    pub attestations: u8,
+    pub attested: bool,
+}
+
+#[event]
+pub struct ReceiptClosed {
+    pub receipt_hash: [u8; 32],
+    pub relayer: Pubkey,
+    /// SHA-256 of the final account data, for indexers
+    pub state_hash: [u8; 32],
+}
+
+#[event]
+pub struct RateLimitsUpdated {
+    pub window_secs: i64,
+    pub solver_volume_cap: u64,
+}
+
+#[event]
+pub struct ChainRegistered {
+    pub chain_id: u32,
+    pub name: String,
+}
+
+#[event]
+pub struct ChainUpdated {
+    pub chain_id: u32,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct VolumeCapReached {
+    pub intent_id: u64,
+    pub chain_id: u32,
+    /// Set when the solver cap was hit, `None` for the chain cap
+    pub solver: Option<Pubkey>,
+    pub window_volume: u64,
+    pub cap: u64,
+}
+
 // ============================================================================
 // ERROR CODES
 // ============================================================================
@@ -1664,8 +5345,110 @@ pub enum ErrorCode {
     InvalidIntentStatus,
     #[msg("Intent not executed")]
     IntentNotExecuted,
+    /// Unreachable: the `fee_vault` seeds constraint fails first
     #[msg("Invalid fee vault")]
     InvalidFeeVault,
     #[msg("Invalid creator")]
     InvalidCreator,
+    #[msg("Bridge is paused")]
+    BridgePaused,
+    #[msg("Chain is disabled")]
+    ChainPaused,
+    #[msg("Chain volume cap exceeded for current window")]
+    ChainVolumeCapExceeded,
+    #[msg("Invalid rate limit window")]
+    InvalidRateLimitWindow,
+    #[msg("Too many supported chains")]
+    TooManyChains,
+    #[msg("Amount below chain minimum")]
+    BelowChainMinimum,
+    #[msg("Destination chain finality not reached")]
+    FinalityNotReached,
+    #[msg("Fee share must be at most 10000 bps")]
+    InvalidFeeShare,
+    #[msg("Insufficient fee vault balance")]
+    InsufficientVaultBalance,
+    #[msg("No rewards to claim")]
+    NoRewards,
+    #[msg("Intent is not in a terminal state")]
+    IntentNotTerminal,
+    #[msg("Inference request not finished")]
+    RequestNotFinished,
+    #[msg("Batch not finished")]
+    BatchNotFinished,
+    #[msg("Batch results exceed prompt count")]
+    BatchOverflow,
+    #[msg("Invalid fill amount")]
+    InvalidFillAmount,
+    #[msg("Intent does not allow partial fills")]
+    PartialFillNotAllowed,
+    /// Unreachable: a `Created` intent always has an unfilled remainder
+    #[msg("Nothing left to refund")]
+    NothingToRefund,
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Settlement batch too large (max 8)")]
+    SettleBatchTooLarge,
+    #[msg("Metadata URI too long")]
+    MetadataUriTooLong,
+    #[msg("Solver fee must be at most 10000 bps")]
+    InvalidSolverFee,
+    #[msg("Solver already active")]
+    SolverAlreadyActive,
+    #[msg("Solver heartbeat is stale")]
+    StaleHeartbeat,
+    #[msg("Invalid heartbeat timeout")]
+    InvalidHeartbeatTimeout,
+    #[msg("Invalid reputation parameters")]
+    InvalidReputationParams,
+    #[msg("Invalid execution time")]
+    InvalidExecutionTime,
+    #[msg("Execution deadline passed")]
+    ExecutionDeadlinePassed,
+    #[msg("Execution deadline not reached")]
+    ExecutionDeadlineNotReached,
+    #[msg("Too many relayers")]
+    TooManyRelayers,
+    #[msg("Invalid relayer threshold")]
+    InvalidRelayerThreshold,
+    #[msg("Not a relayer")]
+    NotRelayer,
+    #[msg("Receipt already attested by this relayer")]
+    AlreadyAttested,
+    #[msg("Invalid or unattested fulfillment receipt")]
+    InvalidReceipt,
+    #[msg("Fulfillment receipt required for this chain")]
+    ReceiptRequired,
+    #[msg("Account already uses the current layout")]
+    AccountUpToDate,
+    #[msg("Too many model delegates (max 8)")]
+    TooManyDelegates,
+    #[msg("Signer is not the pending model owner")]
+    NotPendingOwner,
+    #[msg("Invalid model version")]
+    InvalidModelVersion,
+    #[msg("Model version is deprecated")]
+    DeprecatedModelVersion,
+    #[msg("Insufficient inference credits")]
+    InsufficientCredits,
+    #[msg("Pay with either credits or a subscription, not both")]
+    ConflictingPayment,
+    #[msg("Invalid or inactive subscription plan")]
+    InvalidSubscriptionPlan,
+    #[msg("Already subscribed for this epoch")]
+    AlreadySubscribed,
+    #[msg("Subscription quota used up for this epoch")]
+    SubscriptionExhausted,
+    #[msg("Too many model operators (max 8)")]
+    TooManyOperators,
+    #[msg("Missing or wrong model index page")]
+    InvalidModelIndex,
+    #[msg("Fill below the solver's minimum fill amount")]
+    FillBelowSolverMinimum,
+    #[msg("Protocol fee exceeds the solver's fee limit")]
+    SolverFeeLimitExceeded,
+    #[msg("Partial fills are not allowed on chains requiring receipts")]
+    PartialFillWithReceipt,
+    #[msg("Receipt can still be consumed")]
+    ReceiptStillUsable,
 }
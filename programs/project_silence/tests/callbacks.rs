@@ -0,0 +1,85 @@
+//! Arcium callbacks invoked outside the Arcium program.
+//!
+//! The callbacks trust their `output` argument, so they must only run when
+//! the Arcium program delivers a computation result. These tests invoke them
+//! directly with forged outputs; the success path needs an Arcium localnet
+//! and runs in `tests/project_silence.ts`.
+
+mod common;
+
+use anchor_lang::Discriminator;
+use common::*;
+use project_silence::instruction as args;
+use project_silence_client::arcium;
+use project_silence_client::events::parse_logs;
+use project_silence_client::ID;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::sysvar;
+
+/// `(name, instruction discriminator, computation definition offset)` of every callback
+const CALLBACKS: &[(&str, &[u8], u32)] = &[
+    (
+        "process_inference_callback",
+        args::ProcessInferenceCallback::DISCRIMINATOR,
+        arcium::COMP_DEF_OFFSET_PROCESS_INFERENCE,
+    ),
+    (
+        "verify_intent_amounts_callback",
+        args::VerifyIntentAmountsCallback::DISCRIMINATOR,
+        arcium::COMP_DEF_OFFSET_VERIFY_INTENT_AMOUNTS,
+    ),
+    (
+        "generate_privacy_proof_callback",
+        args::GeneratePrivacyProofCallback::DISCRIMINATOR,
+        arcium::COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF,
+    ),
+];
+
+/// Forged computation outputs: empty, an all-zero "success" and random-looking bytes
+fn forged_outputs() -> Vec<Vec<u8>> {
+    vec![vec![], vec![0; 96], (0..96u8).map(|i| i.wrapping_mul(37)).collect()]
+}
+
+fn callback_ix(discriminator: &[u8], comp_def_offset: u32, arcium_program: Pubkey, output: &[u8]) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new_readonly(arcium_program, false),
+            AccountMeta::new_readonly(arcium::comp_def_account(comp_def_offset), false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+        ],
+        data: [discriminator, output].concat(),
+    }
+}
+
+#[test]
+fn direct_callback_invocation_is_rejected() {
+    let mut env = Env::new();
+    for &(name, discriminator, offset) in CALLBACKS {
+        for output in forged_outputs() {
+            let ix = callback_ix(discriminator, offset, arcium::arcium_program(), &output);
+            let failure = match env.try_send(&[ix], &[]) {
+                Ok(_) => panic!("{name} accepted a forged output"),
+                Err(failure) => failure,
+            };
+            assert!(parse_logs(&failure.logs).is_empty(), "{name} emitted an event");
+        }
+    }
+}
+
+#[test]
+fn callback_from_spoofed_arcium_program_is_rejected() {
+    let mut env = Env::new();
+    let spoofer = env.funded(SOL);
+    for &(name, discriminator, offset) in CALLBACKS {
+        let mut ix = callback_ix(discriminator, offset, spoofer.pubkey(), &[0; 96]);
+        ix.accounts[0].is_signer = true;
+        let failure = match env.try_send(&[ix], &[&spoofer]) {
+            Ok(_) => panic!("{name} accepted a spoofed Arcium program"),
+            Err(failure) => failure,
+        };
+        assert!(parse_logs(&failure.logs).is_empty(), "{name} emitted an event");
+    }
+}
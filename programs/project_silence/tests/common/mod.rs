@@ -0,0 +1,402 @@
+//! LiteSVM harness shared by the program's integration tests.
+//!
+//! Requires `anchor build` so that `target/deploy/project_silence.so` exists.
+
+#![allow(dead_code)]
+
+use litesvm::LiteSVM;
+use project_silence::instruction as args;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{
+    decode, BridgeConfig, Intent, ModelAccess, ModelDirectory, ModelMetadata, UserMetrics,
+};
+use project_silence_client::events::{parse_logs, ProgramEvent};
+use project_silence_client::instructions::Payment;
+use project_silence_client::{instructions, pda, ID};
+use silence_intent::{CrossChainIntent, FulfillmentReceipt, CHAIN_ID_NEAR};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+const PROGRAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/project_silence.so");
+pub const SOL: u64 = 1_000_000_000;
+pub const MIN_STAKE: u64 = SOL;
+pub const PROTOCOL_FEE_BPS: u16 = 50;
+pub const MIN_AMOUNT: u64 = 1_000;
+pub const FINALITY_SECS: u32 = 60;
+pub const TTL_SECS: i64 = 3_600;
+pub const MODEL_ID: u64 = 7;
+pub const MODEL_COST: u64 = 10_000;
+/// Version `model_args` registers, never published as a `ModelVersion`
+pub const MODEL_VERSION: &str = "1.0.0";
+pub const PLAN_ID: u8 = 1;
+pub const PLAN_PRICE: u64 = 5 * MODEL_COST;
+
+/// A failed transaction
+#[derive(Debug)]
+pub struct Failure {
+    pub err: TransactionError,
+    pub logs: Vec<String>,
+}
+
+impl Failure {
+    /// Program error code of the failing instruction, if any
+    pub fn custom_code(&self) -> Option<u32> {
+        match self.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+pub type TxResult = Result<Vec<ProgramEvent>, Failure>;
+
+/// Bridge with the NEAR chain registered, one model and two registered solvers
+pub struct Env {
+    pub svm: LiteSVM,
+    pub owner: Keypair,
+    pub solver: Keypair,
+    pub solver2: Keypair,
+    pub creator: Keypair,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(ID, PROGRAM_PATH)
+            .expect("run `anchor build` first");
+        let (owner, solver, solver2, creator) = (Keypair::new(), Keypair::new(), Keypair::new(), Keypair::new());
+        for key in [&owner, &solver, &solver2, &creator] {
+            svm.airdrop(&key.pubkey(), 100 * SOL).unwrap();
+        }
+        let mut env = Self {
+            svm,
+            owner,
+            solver,
+            solver2,
+            creator,
+        };
+        let owner = env.owner.pubkey();
+        env.send(
+            &[instructions::initialize_bridge(&owner, MIN_STAKE, PROTOCOL_FEE_BPS)],
+            &[],
+        );
+        env.register_chain(CHAIN_ID_NEAR);
+        env.send(&[instructions::register_model(&owner, 0, model_args(MODEL_ID))], &[]);
+        for solver in [env.solver.insecure_clone(), env.solver2.insecure_clone()] {
+            env.send(
+                &[instructions::register_solver(&solver.pubkey(), vec![CHAIN_ID_NEAR])],
+                &[&solver],
+            );
+        }
+        env
+    }
+
+    /// Send as the owner plus `signers`
+    pub fn try_send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        let mut all: Vec<&Keypair> = vec![&self.owner];
+        all.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.owner.pubkey()), &all, self.svm.latest_blockhash());
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        match result {
+            Ok(meta) => Ok(parse_logs(&meta.logs)),
+            Err(failed) => Err(Failure {
+                err: failed.err,
+                logs: failed.meta.logs,
+            }),
+        }
+    }
+
+    /// Send, panicking with the logs on failure
+    pub fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Vec<ProgramEvent> {
+        self.try_send(ixs, signers)
+            .unwrap_or_else(|failure| panic!("{:?}\n{:#?}", failure.err, failure.logs))
+    }
+
+    pub fn account<T: anchor_lang::AccountDeserialize>(&self, address: &Pubkey) -> T {
+        decode(&self.svm.get_account(address).expect("account exists").data).unwrap()
+    }
+
+    /// Lamports held at `address` (0 when the account does not exist)
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        self.svm.get_account(address).map_or(0, |account| account.lamports)
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.lamports(address) > 0
+    }
+
+    /// Index page the next registered model is listed on
+    pub fn next_index_page(&self) -> u32 {
+        match self.svm.get_account(&pda::model_directory()) {
+            Some(account) => decode::<ModelDirectory>(&account.data).unwrap().next_page(),
+            None => 0,
+        }
+    }
+
+    pub fn rent_exempt(&self, data_len: usize) -> u64 {
+        self.svm.minimum_balance_for_rent_exemption(data_len)
+    }
+
+    pub fn warp(&mut self, secs: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += secs;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// A funded keypair
+    pub fn funded(&mut self, lamports: u64) -> Keypair {
+        let key = Keypair::new();
+        self.svm.airdrop(&key.pubkey(), lamports).unwrap();
+        key
+    }
+
+    pub fn register_chain(&mut self, chain_id: u32) {
+        let owner = self.owner.pubkey();
+        self.send(
+            &[instructions::register_chain(
+                &owner,
+                args::RegisterChain {
+                    chain_id,
+                    name: format!("chain-{chain_id}"),
+                    min_amount: MIN_AMOUNT,
+                    finality_seconds: FINALITY_SECS,
+                    fee_bps_override: None,
+                    volume_cap: 0,
+                },
+            )],
+            &[],
+        );
+    }
+
+    pub fn next_intent_id(&self) -> u64 {
+        self.account::<BridgeConfig>(&pda::bridge_config()).total_intents
+    }
+
+    pub fn create_intent_ix(&self, args: args::CreateIntent) -> Instruction {
+        instructions::create_intent(&self.creator.pubkey(), self.next_intent_id(), args)
+    }
+
+    /// Create an intent to `CHAIN_ID_NEAR`, returning its id
+    pub fn create_intent(&mut self, source_amount: u64, allow_partial_fill: bool) -> u64 {
+        let intent_id = self.next_intent_id();
+        let ix = self.create_intent_ix(intent_args(CHAIN_ID_NEAR, source_amount, allow_partial_fill));
+        let creator = self.creator.insecure_clone();
+        self.send(&[ix], &[&creator]);
+        intent_id
+    }
+
+    pub fn match_intent(&mut self, solver: &Keypair, intent_id: u64, amount: u64, estimated_time_secs: Option<i64>) {
+        let ix = instructions::match_intent(&solver.pubkey(), intent_id, CHAIN_ID_NEAR, amount, estimated_time_secs);
+        self.send(&[ix], &[solver]);
+    }
+
+    pub fn execute_intent(&mut self, solver: &Keypair, intent_id: u64) {
+        let ix = instructions::execute_intent(&solver.pubkey(), intent_id, CHAIN_ID_NEAR, None, [9; 32], None);
+        self.send(&[ix], &[solver]);
+    }
+
+    /// Match the whole intent with `solver` and execute it
+    pub fn match_and_execute(&mut self, solver: &Keypair, intent_id: u64, amount: u64) {
+        self.match_intent(solver, intent_id, amount, None);
+        self.execute_intent(solver, intent_id);
+    }
+
+    pub fn settle_ix(&self, solver: &Pubkey, intent_id: u64) -> Instruction {
+        instructions::settle_intent(&self.owner.pubkey(), intent_id, CHAIN_ID_NEAR, solver)
+    }
+
+    /// Next inference request of `creator`, paid with `payment`
+    pub fn request_ix(&self, payment: Payment) -> Instruction {
+        let request_id = self.next_user_id(|metrics| metrics.next_request_id);
+        let (creator, payee) = (self.creator.pubkey(), self.payee());
+        let (version, args) = (self.current_version(), request_args());
+        instructions::create_inference_request(&creator, MODEL_ID, &payee, &version, payment, request_id, args)
+    }
+
+    /// Next batch of `creator`, paid with `payment`
+    pub fn batch_ix(&self, prompt_count: u32, payment: Payment) -> Instruction {
+        let batch_id = self.next_user_id(|metrics| metrics.next_batch_id);
+        let (creator, payee) = (self.creator.pubkey(), self.payee());
+        let args = batch_args(prompt_count);
+        let version = self.current_version();
+        instructions::create_batch_inference(&creator, MODEL_ID, &payee, &version, payment, batch_id, args)
+    }
+
+    /// Create an inference request of `creator`, returning its address
+    pub fn create_request(&mut self) -> Pubkey {
+        let creator = self.creator.insecure_clone();
+        let request_id = self.next_user_id(|metrics| metrics.next_request_id);
+        let ix = self.request_ix(Payment::Lamports);
+        self.send(&[ix], &[&creator]);
+        pda::request(&creator.pubkey(), request_id)
+    }
+
+    /// Create a batch of `creator`, returning its address
+    pub fn create_batch(&mut self, prompt_count: u32) -> Pubkey {
+        let creator = self.creator.insecure_clone();
+        let batch_id = self.next_user_id(|metrics| metrics.next_batch_id);
+        let ix = self.batch_ix(prompt_count, Payment::Lamports);
+        self.send(&[ix], &[&creator]);
+        pda::batch(&creator.pubkey(), batch_id)
+    }
+
+    /// Make the owner the model's only operator, which records batch results
+    pub fn set_owner_operator(&mut self) {
+        let owner = self.owner.pubkey();
+        self.send(&[instructions::set_model_operators(&owner, MODEL_ID, vec![owner])], &[]);
+    }
+
+    /// Prepay `amount` of `creator`'s credits with the model's payout address
+    pub fn top_up(&mut self, amount: u64) {
+        let creator = self.creator.insecure_clone();
+        let ix = instructions::top_up_credits(&creator.pubkey(), &self.payee(), amount);
+        self.send(&[ix], &[&creator]);
+    }
+
+    /// Offer plan `PLAN_ID` for `PLAN_PRICE` on the model
+    pub fn offer_plan(&mut self, inferences_per_epoch: u32, is_active: bool) {
+        let owner = self.owner.pubkey();
+        let args = args::SetSubscriptionPlan {
+            plan_id: PLAN_ID,
+            inferences_per_epoch,
+            price: PLAN_PRICE,
+            is_active,
+        };
+        self.send(&[instructions::set_subscription_plan(&owner, MODEL_ID, args)], &[]);
+    }
+
+    pub fn subscribe_ix(&self) -> Instruction {
+        instructions::subscribe(&self.creator.pubkey(), MODEL_ID, &self.owner.pubkey(), PLAN_ID)
+    }
+
+    /// Advance the clock to the next epoch
+    pub fn next_epoch(&mut self) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.epoch += 1;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Payout address of the test model, which requests pay and credits are held with
+    pub fn payee(&self) -> Pubkey {
+        let model: ModelMetadata = self.account(&pda::model(MODEL_ID));
+        let address = pda::model_access(MODEL_ID);
+        let access = self.exists(&address).then(|| self.account::<ModelAccess>(&address));
+        model.payout(access.as_ref())
+    }
+
+    /// Current version of the test model, which new requests pin
+    pub fn current_version(&self) -> String {
+        self.account::<ModelMetadata>(&pda::model(MODEL_ID)).version
+    }
+
+    fn next_user_id(&self, field: fn(&UserMetrics) -> u64) -> u64 {
+        let address = pda::user_metrics(&self.creator.pubkey());
+        if self.exists(&address) {
+            field(&self.account(&address))
+        } else {
+            0
+        }
+    }
+}
+
+/// Arguments of a model owned by whoever registers it
+pub fn model_args(model_id: u64) -> args::RegisterModel {
+    args::RegisterModel {
+        model_id,
+        name: "silence-llm".to_string(),
+        description: "Private inference model".to_string(),
+        model_type: 0,
+        version: MODEL_VERSION.to_string(),
+        tee_required: true,
+        attestation_required: false,
+        cost_per_inference: MODEL_COST,
+    }
+}
+
+pub fn request_args() -> args::CreateInferenceRequest {
+    args::CreateInferenceRequest {
+        prompt_hash: [1; 32],
+        require_attestation: false,
+    }
+}
+
+pub fn batch_args(prompt_count: u32) -> args::CreateBatchInference {
+    args::CreateBatchInference {
+        prompt_count,
+        require_attestation: false,
+    }
+}
+
+pub fn intent_args(destination_chain_id: u32, source_amount: u64, allow_partial_fill: bool) -> args::CreateIntent {
+    args::CreateIntent {
+        destination_chain_id,
+        destination_amount_commitment: silence_intent::amount_commitment(5, &[7; 32]),
+        destination_token_hash: silence_intent::token_hash("near"),
+        recipient_hash: silence_intent::recipient_hash("alice.near"),
+        is_shielded: false,
+        ttl_seconds: TTL_SECS,
+        source_amount,
+        allow_partial_fill,
+    }
+}
+
+/// Receipt of `solver` delivering `intent` to its recipient when it was created
+pub fn receipt_for(intent: &Intent, solver: &Pubkey) -> FulfillmentReceipt {
+    FulfillmentReceipt {
+        version: silence_intent::MESSAGE_VERSION,
+        intent_hash: CrossChainIntent::from(intent).hash(),
+        destination_chain_id: intent.destination_chain_id,
+        solver: solver.to_bytes(),
+        recipient_hash: intent.recipient_hash,
+        amount: 5,
+        fulfilled_at: intent.created_at as u64,
+    }
+}
+
+/// `attest_receipt` arguments for `receipt`
+pub fn attest_args(receipt: &FulfillmentReceipt) -> args::AttestReceipt {
+    args::AttestReceipt {
+        receipt_hash: receipt.hash(),
+        intent_hash: receipt.intent_hash,
+        solver: Pubkey::new_from_array(receipt.solver),
+        destination_chain_id: receipt.destination_chain_id,
+        recipient_hash: receipt.recipient_hash,
+        amount: receipt.amount,
+        fulfilled_at: receipt.fulfilled_at,
+    }
+}
+
+/// Program error code of `code` as reported by the runtime
+pub fn error_code(code: ErrorCode) -> u32 {
+    code.into()
+}
+
+/// Assert that `result` failed with `code`
+#[track_caller]
+pub fn assert_error(result: TxResult, code: ErrorCode) {
+    match result {
+        Ok(_) => panic!("expected {code:?}, transaction succeeded"),
+        Err(failure) => assert_eq!(
+            failure.custom_code(),
+            Some(error_code(code)),
+            "expected {code:?}, got {:?}\n{:#?}",
+            failure.err,
+            failure.logs
+        ),
+    }
+}
+
+/// Replace the account meta at `from` with `to`, keeping its flags
+pub fn replace_account(mut ix: Instruction, from: &Pubkey, to: Pubkey) -> Instruction {
+    let meta = ix
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == *from)
+        .expect("account is in the instruction");
+    meta.pubkey = to;
+    ix
+}
@@ -0,0 +1,160 @@
+//! Lamport accounting between the intent vault, fee vault and solver.
+
+mod common;
+
+use common::*;
+use project_silence_client::accounts::FeeVault;
+use project_silence_client::{instructions, pda};
+use silence_intent::CHAIN_ID_NEAR;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// Balances touched by settlement
+#[derive(Debug)]
+struct Balances {
+    vaults: u64,
+    fee_vault: u64,
+    solver: u64,
+    fills: u64,
+}
+
+impl Balances {
+    fn read(env: &Env, solver: &Pubkey, ids: &[u64]) -> Self {
+        Self {
+            vaults: ids.iter().map(|&id| env.lamports(&pda::intent_vault(id))).sum(),
+            fee_vault: env.lamports(&pda::fee_vault()),
+            solver: env.lamports(solver),
+            fills: ids
+                .iter()
+                .map(|&id| env.lamports(&pda::fill(&pda::intent(id), solver)))
+                .sum(),
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.vaults + self.fee_vault + self.solver + self.fills
+    }
+}
+
+#[test]
+fn settle_intent_conserves_lamports() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let amount = 3 * SOL + 7;
+    let intent_id = env.create_intent(amount, false);
+    env.match_and_execute(&solver, intent_id, amount);
+    env.warp(FINALITY_SECS as i64);
+
+    let before = Balances::read(&env, &solver.pubkey(), &[intent_id]);
+    assert_eq!(before.vaults, amount + env.rent_exempt(0));
+    let events = env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    assert_eq!(events.len(), 1);
+    let after = Balances::read(&env, &solver.pubkey(), &[intent_id]);
+
+    let protocol_fee = amount * PROTOCOL_FEE_BPS as u64 / 10_000;
+    assert_eq!(after.total(), before.total());
+    assert_eq!(after.vaults, before.vaults - amount);
+    assert_eq!(after.fee_vault, before.fee_vault + protocol_fee);
+    assert_eq!(after.solver, before.solver + (amount - protocol_fee) + before.fills);
+    assert_eq!(after.fills, 0);
+
+    let fee_vault: FeeVault = env.account(&pda::fee_vault());
+    assert_eq!(fee_vault.total_collected, protocol_fee);
+}
+
+#[test]
+fn partial_settlement_leaves_the_rest_in_the_vault() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let intent_id = env.create_intent(4 * SOL, true);
+    env.match_and_execute(&solver, intent_id, SOL);
+    env.warp(FINALITY_SECS as i64);
+
+    let before = Balances::read(&env, &solver.pubkey(), &[intent_id]);
+    env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    let after = Balances::read(&env, &solver.pubkey(), &[intent_id]);
+
+    assert_eq!(after.total(), before.total());
+    assert_eq!(after.vaults, 3 * SOL + env.rent_exempt(0));
+}
+
+#[test]
+fn batch_settlement_conserves_lamports() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let amounts = [SOL, 2 * SOL + 1, 5_000_000];
+    let ids: Vec<u64> = amounts.iter().map(|&amount| env.create_intent(amount, false)).collect();
+    for (&id, &amount) in ids.iter().zip(&amounts) {
+        env.match_and_execute(&solver, id, amount);
+    }
+    env.warp(FINALITY_SECS as i64);
+
+    let before = Balances::read(&env, &solver.pubkey(), &ids);
+    let owner = env.owner.pubkey();
+    env.send(
+        &[instructions::settle_intents_batch(
+            &owner,
+            &solver.pubkey(),
+            CHAIN_ID_NEAR,
+            &ids,
+        )],
+        &[],
+    );
+    let after = Balances::read(&env, &solver.pubkey(), &ids);
+
+    // Fees are rounded down per fill
+    let total: u64 = amounts.iter().sum();
+    let protocol_fee: u64 = amounts
+        .iter()
+        .map(|amount| amount * PROTOCOL_FEE_BPS as u64 / 10_000)
+        .sum();
+    assert_eq!(after.total(), before.total());
+    assert_eq!(after.vaults, before.vaults - total);
+    assert_eq!(after.vaults, ids.len() as u64 * env.rent_exempt(0));
+    assert_eq!(after.fee_vault, before.fee_vault + protocol_fee);
+    assert_eq!(after.solver, before.solver + (total - protocol_fee) + before.fills);
+}
+
+#[test]
+fn close_returns_vault_reserve_to_creator() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let creator = env.creator.insecure_clone();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_and_execute(&solver, intent_id, SOL);
+    env.warp(FINALITY_SECS as i64);
+    env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+
+    let reserve = env.lamports(&pda::intent_vault(intent_id));
+    assert_eq!(reserve, env.rent_exempt(0));
+    let intent_rent = env.lamports(&pda::intent(intent_id));
+    let before = env.lamports(&creator.pubkey());
+    env.send(&[instructions::close_intent(&creator.pubkey(), intent_id)], &[&creator]);
+
+    assert_eq!(env.lamports(&creator.pubkey()), before + reserve + intent_rent);
+    assert_eq!(env.lamports(&pda::intent_vault(intent_id)), 0);
+}
+
+#[test]
+fn refunds_and_settlement_drain_the_vault_exactly() {
+    let mut env = Env::new();
+    let (solver, solver2) = (env.solver.insecure_clone(), env.solver2.insecure_clone());
+    let creator = env.creator.pubkey();
+    let intent_id = env.create_intent(3 * SOL, true);
+    env.match_and_execute(&solver, intent_id, SOL);
+    env.match_intent(&solver2, intent_id, SOL, None);
+
+    // solver2 gives up its fill, the creator cancels the unmatched remainder after expiry
+    let creator_before = env.lamports(&creator);
+    env.send(
+        &[instructions::fail_intent(&solver2.pubkey(), intent_id, &creator)],
+        &[&solver2],
+    );
+    env.warp(TTL_SECS);
+    let creator_key = env.creator.insecure_clone();
+    env.send(&[instructions::cancel_intent(&creator, intent_id)], &[&creator_key]);
+    assert_eq!(env.lamports(&creator), creator_before + 2 * SOL);
+
+    env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    assert_eq!(env.lamports(&pda::intent_vault(intent_id)), env.rent_exempt(0));
+}
@@ -0,0 +1,202 @@
+//! Prepaid inference credits and subscription plans.
+
+mod common;
+
+use anchor_lang::Space;
+use common::*;
+use project_silence::instruction as args;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{CreditAccount, Subscription, SubscriptionPlan, UserMetrics};
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::instructions::Payment;
+use project_silence_client::{instructions, pda};
+use solana_sdk::signature::Signer;
+
+fn credits(env: &Env) -> CreditAccount {
+    env.account(&pda::credit_account(&env.creator.pubkey(), &env.payee()))
+}
+
+fn subscription(env: &Env) -> Subscription {
+    env.account(&pda::subscription(&env.creator.pubkey(), MODEL_ID))
+}
+
+#[test]
+fn credits_pay_for_requests_and_batches() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let creator = env.creator.insecure_clone();
+    // Credits are held with the payout address, which claims what is spent
+    let payout = env.funded(SOL);
+    env.send(
+        &[instructions::set_model_payout(&owner, MODEL_ID, Some(payout.pubkey()))],
+        &[],
+    );
+    env.top_up(10 * MODEL_COST);
+
+    let ix = env.request_ix(Payment::Credits);
+    env.send(&[ix], &[&creator]);
+    let ix = env.batch_ix(3, Payment::Credits);
+    env.send(&[ix], &[&creator]);
+
+    assert_eq!(env.lamports(&payout.pubkey()), SOL);
+    let state = credits(&env);
+    assert_eq!((state.user, state.provider), (creator.pubkey(), payout.pubkey()));
+    assert_eq!((state.balance, state.earned), (6 * MODEL_COST, 4 * MODEL_COST));
+    let metrics: UserMetrics = env.account(&pda::user_metrics(&creator.pubkey()));
+    assert_eq!((metrics.total_inferences, metrics.total_cost), (4, 4 * MODEL_COST));
+
+    let address = pda::credit_account(&creator.pubkey(), &payout.pubkey());
+    let held = env.lamports(&address);
+    let claim = instructions::claim_credit_earnings(&payout.pubkey(), &creator.pubkey());
+    let events = env.send(&[claim], &[&payout]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::CreditEarningsClaimed(event)] if event.amount == 4 * MODEL_COST
+    ));
+    assert_eq!(env.lamports(&address), held - 4 * MODEL_COST);
+    assert_eq!(env.lamports(&payout.pubkey()), SOL + 4 * MODEL_COST);
+    assert_eq!(credits(&env).earned, 0);
+    let again = instructions::claim_credit_earnings(&payout.pubkey(), &creator.pubkey());
+    assert_error(env.try_send(&[again], &[&payout]), ErrorCode::NoRewards);
+
+    // After a payout change the old credits no longer pay, but stay claimable and withdrawable
+    env.send(&[instructions::set_model_payout(&owner, MODEL_ID, None)], &[]);
+    let ix = env.request_ix(Payment::Credits);
+    assert!(env.try_send(&[ix], &[&creator]).is_err());
+    let ix = instructions::withdraw_credits(&creator.pubkey(), &payout.pubkey(), 6 * MODEL_COST);
+    env.send(&[ix], &[&creator]);
+}
+
+#[test]
+fn credits_only_pay_their_provider() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+    let other = env.funded(SOL).pubkey();
+    let ix = instructions::top_up_credits(&creator.pubkey(), &other, 10 * MODEL_COST);
+    env.send(&[ix], &[&creator]);
+
+    let address = pda::credit_account(&creator.pubkey(), &other);
+    let ix = env.request_ix(Payment::Credits);
+    let ix = replace_account(ix, &pda::credit_account(&creator.pubkey(), &env.payee()), address);
+    assert!(env.try_send(&[ix], &[&creator]).is_err());
+    let state: CreditAccount = env.account(&address);
+    assert_eq!((state.balance, state.earned), (10 * MODEL_COST, 0));
+}
+
+#[test]
+fn unspent_credits_can_be_withdrawn() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let creator = env.creator.insecure_clone();
+    env.top_up(5 * MODEL_COST);
+    let ix = env.request_ix(Payment::Credits);
+    env.send(&[ix], &[&creator]);
+
+    let before = env.lamports(&creator.pubkey());
+    let events = env.send(
+        &[instructions::withdraw_credits(
+            &creator.pubkey(),
+            &owner,
+            3 * MODEL_COST,
+        )],
+        &[&creator],
+    );
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::CreditsWithdrawn(event)] if event.balance == MODEL_COST
+    ));
+    assert_eq!(env.lamports(&creator.pubkey()), before + 3 * MODEL_COST);
+
+    // Spent credits belong to the provider
+    let ix = instructions::withdraw_credits(&creator.pubkey(), &owner, 2 * MODEL_COST);
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::InsufficientCredits);
+    assert_eq!(credits(&env).earned, MODEL_COST);
+}
+
+#[test]
+fn subscriptions_cover_a_quota_per_epoch() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+    env.offer_plan(3, true);
+    let before = env.lamports(&creator.pubkey());
+    let events = env.send(&[env.subscribe_ix()], &[&creator]);
+    match events.as_slice() {
+        [ProgramEvent::Subscribed(event)] => {
+            assert_eq!((event.model_id, event.plan_id), (MODEL_ID, PLAN_ID));
+            assert_eq!(event.inferences_per_epoch, 3);
+        }
+        _ => panic!("expected one Subscribed event"),
+    }
+    let rent = env.rent_exempt(8 + Subscription::INIT_SPACE);
+    assert_eq!(env.lamports(&creator.pubkey()), before - PLAN_PRICE - rent);
+
+    let ix = env.request_ix(Payment::Subscription);
+    env.send(&[ix], &[&creator]);
+    let ix = env.batch_ix(2, Payment::Subscription);
+    env.send(&[ix], &[&creator]);
+    let metrics: UserMetrics = env.account(&pda::user_metrics(&creator.pubkey()));
+    assert_eq!((metrics.total_inferences, metrics.total_cost), (3, 0));
+    assert_eq!(subscription(&env).used, 3);
+    let ix = env.request_ix(Payment::Subscription);
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::SubscriptionExhausted);
+
+    // Unused quota does not carry over, and a new epoch needs a new payment
+    env.next_epoch();
+    let ix = env.request_ix(Payment::Subscription);
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::SubscriptionExhausted);
+    env.send(&[env.subscribe_ix()], &[&creator]);
+    assert_eq!(subscription(&env).used, 0);
+    let ix = env.request_ix(Payment::Subscription);
+    env.send(&[ix], &[&creator]);
+}
+
+#[test]
+fn plan_changes_leave_paid_epochs_alone() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+    env.offer_plan(3, true);
+    env.send(&[env.subscribe_ix()], &[&creator]);
+
+    env.offer_plan(1, false);
+    let plan: SubscriptionPlan = env.account(&pda::subscription_plan(MODEL_ID, PLAN_ID));
+    assert_eq!((plan.inferences_per_epoch, plan.is_active), (1, false));
+    let ix = env.batch_ix(3, Payment::Subscription);
+    env.send(&[ix], &[&creator]);
+
+    env.next_epoch();
+    assert_error(
+        env.try_send(&[env.subscribe_ix()], &[&creator]),
+        ErrorCode::InvalidSubscriptionPlan,
+    );
+}
+
+#[test]
+fn only_managers_set_plans() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let delegate = env.funded(SOL);
+    let stranger = env.funded(SOL);
+    env.send(
+        &[instructions::set_model_delegates(
+            &owner,
+            MODEL_ID,
+            vec![delegate.pubkey()],
+        )],
+        &[],
+    );
+    let plan = || args::SetSubscriptionPlan {
+        plan_id: PLAN_ID,
+        inferences_per_epoch: 10,
+        price: PLAN_PRICE,
+        is_active: true,
+    };
+
+    let ix = instructions::set_subscription_plan(&stranger.pubkey(), MODEL_ID, plan());
+    assert_error(env.try_send(&[ix], &[&stranger]), ErrorCode::Unauthorized);
+    let ix = instructions::set_subscription_plan(&delegate.pubkey(), MODEL_ID, plan());
+    let events = env.send(&[ix], &[&delegate]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::SubscriptionPlanUpdated(event)] if event.inferences_per_epoch == 10
+    ));
+}
@@ -0,0 +1,66 @@
+//! Canonical cross-chain form of the program's intents.
+
+mod common;
+
+use common::*;
+use project_silence_client::accounts::Intent;
+use project_silence_client::pda;
+use silence_intent::{CrossChainIntent, DestinationAmount, CHAIN_ID_NEAR, CHAIN_ID_SOLANA, MESSAGE_VERSION};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+fn intent(env: &Env, intent_id: u64) -> Intent {
+    env.account(&pda::intent(intent_id))
+}
+
+#[test]
+fn intents_convert_field_by_field() {
+    let mut env = Env::new();
+    let intent_id = env.create_intent(SOL, false);
+    let stored = intent(&env, intent_id);
+    let args = intent_args(CHAIN_ID_NEAR, SOL, false);
+
+    let canonical = CrossChainIntent::from(&stored);
+    assert_eq!(
+        canonical,
+        CrossChainIntent {
+            version: MESSAGE_VERSION,
+            source_chain_id: CHAIN_ID_SOLANA,
+            destination_chain_id: CHAIN_ID_NEAR,
+            intent_id: silence_intent::solana_intent_id(intent_id),
+            creator: env.creator.pubkey().to_bytes(),
+            source_token: Pubkey::default().to_bytes(),
+            source_amount: SOL as u128,
+            destination_token_hash: args.destination_token_hash,
+            destination_amount: DestinationAmount::Commitment(args.destination_amount_commitment),
+            recipient_hash: args.recipient_hash,
+            is_shielded: args.is_shielded,
+            expires_at: stored.expires_at as u64,
+        }
+    );
+    assert_eq!(CrossChainIntent::decode(&canonical.encode()).unwrap(), canonical);
+}
+
+#[test]
+fn converted_intents_hash_like_the_golden_vector() {
+    let mut env = Env::new();
+    let intent_id = env.create_intent(SOL, false);
+    // The Solana vector of silence-intent/tests/golden.rs
+    let mut stored = intent(&env, intent_id);
+    stored.intent_id = 7;
+    stored.creator = Pubkey::new_from_array([1; 32]);
+    stored.source_amount = 1_000_000_000;
+    stored.destination_token_hash = silence_intent::token_hash("wrap.near");
+    stored.destination_amount_commitment = [0xab; 32];
+    stored.recipient_hash = silence_intent::recipient_hash("alice.near");
+    stored.is_shielded = true;
+    stored.expires_at = 1_700_003_600;
+
+    let hash = CrossChainIntent::from(&stored).hash();
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    assert_eq!(hex, "b855a4701286b4a2d3983151873a5db7ca4bf76b9f642ebc19f4a7b1a9836d6f");
+
+    // Timestamps before the epoch clamp to zero instead of wrapping
+    stored.expires_at = -1;
+    assert_eq!(CrossChainIntent::from(&stored).expires_at, 0);
+}
@@ -0,0 +1,106 @@
+//! Execution deadlines of matched fills and timing out the ones that miss them.
+
+mod common;
+
+use common::*;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{IntentFill, Solver};
+use project_silence_client::{instructions, pda};
+use silence_intent::CHAIN_ID_NEAR;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn now(env: &Env) -> i64 {
+    env.svm.get_sysvar::<Clock>().unix_timestamp
+}
+
+fn fill(env: &Env, intent_id: u64, solver: &Keypair) -> IntentFill {
+    env.account(&pda::fill(&pda::intent(intent_id), &solver.pubkey()))
+}
+
+fn timeout(env: &Env, caller: &Keypair, intent_id: u64, reopen: bool) -> Instruction {
+    let (creator, solver) = (env.creator.pubkey(), env.solver.pubkey());
+    instructions::timeout_intent(&caller.pubkey(), intent_id, &creator, &solver, reopen)
+}
+
+#[test]
+fn fills_default_to_the_execution_window() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, None);
+    assert_eq!(fill(&env, intent_id, &solver).execution_deadline, now(&env) + 3_600);
+
+    let owner = env.owner.pubkey();
+    env.send(&[instructions::set_execution_window(&owner, 60, 120)], &[]);
+    let intent_id = env.create_intent(SOL, false);
+    let too_long = instructions::match_intent(&solver.pubkey(), intent_id, CHAIN_ID_NEAR, SOL, Some(121));
+    assert_error(env.try_send(&[too_long], &[&solver]), ErrorCode::InvalidExecutionTime);
+    env.match_intent(&solver, intent_id, SOL, None);
+    assert_eq!(fill(&env, intent_id, &solver).execution_deadline, now(&env) + 60);
+}
+
+#[test]
+fn execution_is_allowed_up_to_the_deadline() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, Some(10));
+    env.warp(10);
+    env.execute_intent(&solver, intent_id);
+
+    // An executed fill can no longer time out
+    let owner = env.owner.insecure_clone();
+    env.warp(1);
+    assert_error(
+        env.try_send(&[timeout(&env, &owner, intent_id, false)], &[]),
+        ErrorCode::InvalidIntentStatus,
+    );
+}
+
+#[test]
+fn timeouts_wait_for_the_deadline_and_penalize_the_solver() {
+    let mut env = Env::new();
+    let (owner, solver) = (env.owner.insecure_clone(), env.solver.insecure_clone());
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, Some(10));
+
+    env.warp(10);
+    assert_error(
+        env.try_send(&[timeout(&env, &owner, intent_id, false)], &[]),
+        ErrorCode::ExecutionDeadlineNotReached,
+    );
+    env.warp(1);
+    env.send(&[timeout(&env, &owner, intent_id, false)], &[]);
+
+    let record: Solver = env.account(&pda::solver(&solver.pubkey()));
+    assert_eq!(record.failed_intents, 1);
+    assert!(record.reputation_score < 100);
+    assert!(!env.exists(&pda::fill(&pda::intent(intent_id), &solver.pubkey())));
+}
+
+#[test]
+fn only_the_creator_reopens_before_expiry() {
+    let mut env = Env::new();
+    let (owner, creator, solver) = (
+        env.owner.insecure_clone(),
+        env.creator.insecure_clone(),
+        env.solver.insecure_clone(),
+    );
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, Some(10));
+
+    env.warp(11);
+    assert_error(
+        env.try_send(&[timeout(&env, &owner, intent_id, true)], &[]),
+        ErrorCode::Unauthorized,
+    );
+    env.warp(TTL_SECS);
+    assert_error(
+        env.try_send(&[timeout(&env, &creator, intent_id, true)], &[&creator]),
+        ErrorCode::IntentExpired,
+    );
+    // Past expiry the fill can still be refunded
+    env.send(&[timeout(&env, &creator, intent_id, false)], &[&creator]);
+}
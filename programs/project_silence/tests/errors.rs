@@ -0,0 +1,612 @@
+//! Every reachable `ErrorCode`, one test per code.
+
+mod common;
+
+use std::collections::BTreeSet;
+
+use common::*;
+use project_silence::instruction as args;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::Intent;
+use project_silence_client::instructions::Payment;
+use project_silence_client::{instructions, pda, ID};
+use silence_intent::{CrossChainIntent, FulfillmentReceipt, CHAIN_ID_NEAR, CHAIN_ID_ZCASH};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Codes no transaction in this harness can produce
+const UNREACHABLE: &[(ErrorCode, &str)] = &[
+    (
+        ErrorCode::AbortedComputation,
+        "returned by Arcium callbacks, which need the Arcium program",
+    ),
+    (
+        ErrorCode::ClusterNotSet,
+        "checked on the MXE account, which needs the Arcium program",
+    ),
+    (ErrorCode::InvalidFeeVault, "the fee vault seeds constraint fails first"),
+    (
+        ErrorCode::NothingToRefund,
+        "a `Created` intent always has an unfilled remainder",
+    ),
+];
+
+/// One test per case: a fresh `Env`, then the closure sends the failing transaction
+macro_rules! error_cases {
+    ($($name:ident: $code:ident => $case:expr;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let mut env = Env::new();
+                let case: fn(&mut Env) -> TxResult = $case;
+                assert_error(case(&mut env), ErrorCode::$code);
+            }
+        )*
+
+        const COVERED: &[ErrorCode] = &[$(ErrorCode::$code),*];
+    };
+}
+
+fn update_chain(env: &mut Env, update: impl FnOnce(&mut args::UpdateChain)) {
+    let mut args = args::UpdateChain {
+        name: None,
+        min_amount: None,
+        finality_seconds: None,
+        fee_bps_override: None,
+        volume_cap: None,
+        requires_receipt: None,
+    };
+    update(&mut args);
+    let owner = env.owner.pubkey();
+    env.send(&[instructions::update_chain(&owner, CHAIN_ID_NEAR, args)], &[]);
+}
+
+fn update_model(env: &mut Env, is_active: bool) {
+    let owner = env.owner.pubkey();
+    env.send(
+        &[instructions::update_model(
+            &owner,
+            MODEL_ID,
+            Some(0),
+            args::UpdateModel {
+                name: None,
+                description: None,
+                cost_per_inference: None,
+                is_active: Some(is_active),
+            },
+        )],
+        &[],
+    );
+}
+
+fn register_model(env: &mut Env, update: impl FnOnce(&mut args::RegisterModel)) -> TxResult {
+    let mut args = model_args(MODEL_ID + 1);
+    update(&mut args);
+    let owner = env.owner.pubkey();
+    let page = env.next_index_page();
+    env.try_send(&[instructions::register_model(&owner, page, args)], &[])
+}
+
+/// Request of `creator` paying the model owner
+fn request(env: &Env, model_id: u64, version: &str) -> Instruction {
+    let (creator, owner) = (env.creator.pubkey(), env.owner.pubkey());
+    let args = request_args();
+    instructions::create_inference_request(&creator, model_id, &owner, version, Payment::Lamports, 0, args)
+}
+
+/// Batch of `creator` paying the model owner
+fn batch(env: &Env, model_id: u64, prompt_count: u32) -> Instruction {
+    let (creator, owner) = (env.creator.pubkey(), env.owner.pubkey());
+    let args = batch_args(prompt_count);
+    instructions::create_batch_inference(&creator, model_id, &owner, MODEL_VERSION, Payment::Lamports, 0, args)
+}
+
+fn update_solver(env: &mut Env, update: impl FnOnce(&mut args::UpdateSolver)) -> TxResult {
+    let mut args = args::UpdateSolver {
+        supported_chains: None,
+        metadata_uri: None,
+        fee_bps: None,
+        min_fill_amount: None,
+    };
+    update(&mut args);
+    let solver = env.solver.insecure_clone();
+    env.try_send(&[instructions::update_solver(&solver.pubkey(), args)], &[&solver])
+}
+
+fn create_intent(env: &mut Env, amount: u64) -> TxResult {
+    let ix = env.create_intent_ix(intent_args(CHAIN_ID_NEAR, amount, false));
+    let creator = env.creator.insecure_clone();
+    env.try_send(&[ix], &[&creator])
+}
+
+fn try_match(env: &mut Env, intent_id: u64, amount: u64, estimated_time_secs: Option<i64>) -> TxResult {
+    let solver = env.solver.insecure_clone();
+    let ix = instructions::match_intent(&solver.pubkey(), intent_id, CHAIN_ID_NEAR, amount, estimated_time_secs);
+    env.try_send(&[ix], &[&solver])
+}
+
+fn try_execute(env: &mut Env, intent_id: u64) -> TxResult {
+    let solver = env.solver.insecure_clone();
+    let ix = instructions::execute_intent(&solver.pubkey(), intent_id, CHAIN_ID_NEAR, None, [9; 32], None);
+    env.try_send(&[ix], &[&solver])
+}
+
+fn try_register_solver(env: &mut Env, solver: &Keypair, chains: Vec<u32>) -> TxResult {
+    env.try_send(&[instructions::register_solver(&solver.pubkey(), chains)], &[solver])
+}
+
+fn set_relayers(env: &mut Env, count: usize, threshold: u8) -> (Vec<Keypair>, TxResult) {
+    let relayers: Vec<Keypair> = (0..count).map(|_| env.funded(SOL)).collect();
+    let owner = env.owner.pubkey();
+    let ix = instructions::set_relayers(&owner, relayers.iter().map(|r| r.pubkey()).collect(), threshold);
+    let result = env.try_send(&[ix], &[]);
+    (relayers, result)
+}
+
+fn receipt(env: &Env, amount: u128) -> FulfillmentReceipt {
+    FulfillmentReceipt {
+        version: silence_intent::MESSAGE_VERSION,
+        intent_hash: [4; 32],
+        destination_chain_id: CHAIN_ID_NEAR,
+        solver: env.solver.pubkey().to_bytes(),
+        recipient_hash: [5; 32],
+        amount,
+        fulfilled_at: 100,
+    }
+}
+
+fn attest(env: &mut Env, relayer: &Keypair, amount: u128) -> TxResult {
+    let ix = instructions::attest_receipt(&relayer.pubkey(), attest_args(&receipt(env, amount)));
+    env.try_send(&[ix], &[relayer])
+}
+
+fn admin(env: &mut Env, ix: fn(&Pubkey) -> solana_sdk::instruction::Instruction) -> TxResult {
+    let owner = env.owner.pubkey();
+    env.try_send(&[ix(&owner)], &[])
+}
+
+error_cases! {
+    unauthorized: Unauthorized => |env| {
+        let stranger = env.creator.insecure_clone();
+        let ix = instructions::update_model(
+            &stranger.pubkey(),
+            MODEL_ID,
+            Some(0),
+            args::UpdateModel {
+                name: None,
+                description: None,
+                cost_per_inference: Some(0),
+                is_active: None,
+            },
+        );
+        env.try_send(&[ix], &[&stranger])
+    };
+    name_too_long: NameTooLong => |env| register_model(env, |m| m.name = "n".repeat(33));
+    description_too_long: DescriptionTooLong => |env| register_model(env, |m| m.description = "d".repeat(257));
+    version_too_long: VersionTooLong => |env| register_model(env, |m| m.version = "v".repeat(17));
+    invalid_model_type: InvalidModelType => |env| register_model(env, |m| m.model_type = 4);
+    model_not_active: ModelNotActive => |env| {
+        update_model(env, false);
+        let creator = env.creator.insecure_clone();
+        let ix = request(env, MODEL_ID, MODEL_VERSION);
+        env.try_send(&[ix], &[&creator])
+    };
+    attestation_required: AttestationRequired => |env| {
+        register_model(env, |m| m.attestation_required = true).expect("register");
+        let creator = env.creator.insecure_clone();
+        let ix = request(env, MODEL_ID + 1, MODEL_VERSION);
+        env.try_send(&[ix], &[&creator])
+    };
+    invalid_owner: InvalidOwner => |env| {
+        let creator = env.creator.insecure_clone();
+        let owner = env.owner.pubkey();
+        let ix = request(env, MODEL_ID, MODEL_VERSION);
+        env.try_send(&[replace_account(ix, &owner, Pubkey::new_unique())], &[&creator])
+    };
+    empty_batch: EmptyBatch => |env| {
+        let creator = env.creator.insecure_clone();
+        let ix = batch(env, MODEL_ID, 0);
+        env.try_send(&[ix], &[&creator])
+    };
+    batch_too_large: BatchTooLarge => |env| {
+        let creator = env.creator.insecure_clone();
+        let ix = batch(env, MODEL_ID, 101);
+        env.try_send(&[ix], &[&creator])
+    };
+    overflow: Overflow => |env| {
+        register_model(env, |m| m.cost_per_inference = u64::MAX).expect("register");
+        let creator = env.creator.insecure_clone();
+        let ix = batch(env, MODEL_ID + 1, 2);
+        env.try_send(&[ix], &[&creator])
+    };
+    fee_too_high: FeeTooHigh => |env| admin(env, |owner| instructions::set_protocol_fee(owner, 1001));
+    insufficient_stake: InsufficientStake => |env| {
+        let poor = env.funded(MIN_STAKE / 2);
+        try_register_solver(env, &poor, vec![CHAIN_ID_NEAR])
+    };
+    no_supported_chains: NoSupportedChains => |env| {
+        let solver = env.funded(10 * SOL);
+        try_register_solver(env, &solver, vec![])
+    };
+    zero_deposit: ZeroDeposit => |env| create_intent(env, 0);
+    intent_already_matched: IntentAlreadyMatched => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let solver2 = env.solver2.insecure_clone();
+        env.match_intent(&solver2, intent_id, SOL, None);
+        try_match(env, intent_id, SOL, None)
+    };
+    solver_not_active: SolverNotActive => |env| {
+        let owner = env.owner.pubkey();
+        let solver = env.solver.pubkey();
+        env.send(&[instructions::deactivate_solver(&owner, &solver)], &[]);
+        let intent_id = env.create_intent(SOL, false);
+        try_match(env, intent_id, SOL, None)
+    };
+    intent_expired: IntentExpired => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        env.warp(TTL_SECS);
+        try_match(env, intent_id, SOL, None)
+    };
+    chain_not_supported: ChainNotSupported => |env| {
+        env.register_chain(CHAIN_ID_ZCASH);
+        let intent_id = env.next_intent_id();
+        let ix = env.create_intent_ix(intent_args(CHAIN_ID_ZCASH, SOL, false));
+        let creator = env.creator.insecure_clone();
+        env.send(&[ix], &[&creator]);
+        let solver = env.solver.insecure_clone();
+        let ix = instructions::match_intent(&solver.pubkey(), intent_id, CHAIN_ID_ZCASH, SOL, None);
+        env.try_send(&[ix], &[&solver])
+    };
+    not_matched_solver: NotMatchedSolver => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let solver = env.solver.insecure_clone();
+        env.match_and_execute(&solver, intent_id, SOL);
+        env.warp(FINALITY_SECS as i64);
+        let ix = replace_account(env.settle_ix(&solver.pubkey(), intent_id), &solver.pubkey(), Pubkey::new_unique());
+        env.try_send(&[ix], &[])
+    };
+    invalid_intent_status: InvalidIntentStatus => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let solver = env.solver.insecure_clone();
+        env.match_and_execute(&solver, intent_id, SOL);
+        try_execute(env, intent_id)
+    };
+    intent_not_executed: IntentNotExecuted => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let solver = env.solver.insecure_clone();
+        env.match_intent(&solver, intent_id, SOL, None);
+        let ix = env.settle_ix(&solver.pubkey(), intent_id);
+        env.try_send(&[ix], &[])
+    };
+    invalid_creator: InvalidCreator => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let solver = env.solver.insecure_clone();
+        env.match_intent(&solver, intent_id, SOL, None);
+        let ix = instructions::fail_intent(&solver.pubkey(), intent_id, &Pubkey::new_unique());
+        env.try_send(&[ix], &[&solver])
+    };
+    bridge_paused: BridgePaused => |env| {
+        admin(env, |owner| instructions::set_paused(owner, true)).expect("pause");
+        create_intent(env, SOL)
+    };
+    chain_paused: ChainPaused => |env| {
+        admin(env, |owner| instructions::set_chain_enabled(owner, CHAIN_ID_NEAR, false)).expect("disable chain");
+        create_intent(env, SOL)
+    };
+    chain_volume_cap_exceeded: ChainVolumeCapExceeded => |env| {
+        update_chain(env, |c| c.volume_cap = Some(SOL - 1));
+        create_intent(env, SOL)
+    };
+    invalid_rate_limit_window: InvalidRateLimitWindow => |env| {
+        admin(env, |owner| instructions::set_rate_limits(owner, 0, 0))
+    };
+    too_many_chains: TooManyChains => |env| {
+        let solver = env.funded(10 * SOL);
+        try_register_solver(env, &solver, (1..=17).collect())
+    };
+    below_chain_minimum: BelowChainMinimum => |env| create_intent(env, MIN_AMOUNT - 1);
+    finality_not_reached: FinalityNotReached => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let solver = env.solver.insecure_clone();
+        env.match_and_execute(&solver, intent_id, SOL);
+        let ix = env.settle_ix(&solver.pubkey(), intent_id);
+        env.try_send(&[ix], &[])
+    };
+    invalid_fee_share: InvalidFeeShare => |env| admin(env, |owner| instructions::set_fee_split(owner, 10_001));
+    insufficient_vault_balance: InsufficientVaultBalance => |env| {
+        admin(env, |owner| instructions::withdraw_fees(owner, owner, 1))
+    };
+    no_rewards: NoRewards => |env| {
+        let solver = env.solver.insecure_clone();
+        env.try_send(&[instructions::claim_solver_rewards(&solver.pubkey())], &[&solver])
+    };
+    intent_not_terminal: IntentNotTerminal => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        let creator = env.creator.insecure_clone();
+        env.try_send(&[instructions::close_intent(&creator.pubkey(), intent_id)], &[&creator])
+    };
+    request_not_finished: RequestNotFinished => |env| {
+        let request = env.create_request();
+        let creator = env.creator.insecure_clone();
+        env.try_send(&[instructions::close_inference_request(&creator.pubkey(), &request)], &[&creator])
+    };
+    batch_not_finished: BatchNotFinished => |env| {
+        let batch = env.create_batch(3);
+        let (owner, creator) = (env.owner.pubkey(), env.creator.insecure_clone());
+        env.set_owner_operator();
+        env.send(
+            &[instructions::record_batch_results(&owner, &batch, &creator.pubkey(), MODEL_ID, 2, 0)],
+            &[],
+        );
+        env.try_send(&[instructions::close_batch(&creator.pubkey(), &batch)], &[&creator])
+    };
+    batch_overflow: BatchOverflow => |env| {
+        let batch = env.create_batch(3);
+        let (owner, creator) = (env.owner.pubkey(), env.creator.pubkey());
+        env.set_owner_operator();
+        let ix = instructions::record_batch_results(&owner, &batch, &creator, MODEL_ID, 3, 1);
+        env.try_send(&[ix], &[])
+    };
+    invalid_fill_amount: InvalidFillAmount => |env| {
+        let intent_id = env.create_intent(SOL, true);
+        try_match(env, intent_id, 0, None)
+    };
+    partial_fill_not_allowed: PartialFillNotAllowed => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        try_match(env, intent_id, SOL / 2, None)
+    };
+    invalid_remaining_accounts: InvalidRemainingAccounts => |env| {
+        let owner = env.owner.pubkey();
+        let solver = env.solver.pubkey();
+        let mut ix = instructions::settle_intents_batch(&owner, &solver, CHAIN_ID_NEAR, &[0]);
+        ix.accounts.pop();
+        env.try_send(&[ix], &[])
+    };
+    settle_batch_too_large: SettleBatchTooLarge => |env| {
+        let owner = env.owner.pubkey();
+        let solver = env.solver.pubkey();
+        let ix = instructions::settle_intents_batch(&owner, &solver, CHAIN_ID_NEAR, &[0; 9]);
+        env.try_send(&[ix], &[])
+    };
+    metadata_uri_too_long: MetadataUriTooLong => |env| {
+        update_solver(env, |s| s.metadata_uri = Some("u".repeat(201)))
+    };
+    invalid_solver_fee: InvalidSolverFee => |env| update_solver(env, |s| s.fee_bps = Some(10_001));
+    fill_below_solver_minimum: FillBelowSolverMinimum => |env| {
+        update_solver(env, |s| s.min_fill_amount = Some(SOL / 2)).expect("update solver");
+        let intent_id = env.create_intent(SOL, true);
+        try_match(env, intent_id, SOL / 4, None)
+    };
+    solver_fee_limit_exceeded: SolverFeeLimitExceeded => |env| {
+        update_solver(env, |s| s.fee_bps = Some(PROTOCOL_FEE_BPS - 1)).expect("update solver");
+        let intent_id = env.create_intent(SOL, false);
+        try_match(env, intent_id, SOL, None)
+    };
+    solver_already_active: SolverAlreadyActive => |env| {
+        let owner = env.owner.pubkey();
+        let solver = env.solver.pubkey();
+        env.try_send(&[instructions::reactivate_solver(&owner, &solver)], &[])
+    };
+    stale_heartbeat: StaleHeartbeat => |env| {
+        admin(env, |owner| instructions::set_heartbeat_timeout(owner, 10)).expect("timeout");
+        let intent_id = env.create_intent(SOL, false);
+        env.warp(20);
+        try_match(env, intent_id, SOL, None)
+    };
+    invalid_heartbeat_timeout: InvalidHeartbeatTimeout => |env| {
+        admin(env, |owner| instructions::set_heartbeat_timeout(owner, -1))
+    };
+    invalid_reputation_params: InvalidReputationParams => |env| {
+        admin(env, |owner| {
+            instructions::set_reputation_params(
+                owner,
+                project_silence::ReputationParams {
+                    half_life_secs: -1,
+                    ..Default::default()
+                },
+            )
+        })
+    };
+    invalid_execution_time: InvalidExecutionTime => |env| {
+        admin(env, |owner| instructions::set_execution_window(owner, 0, 10))
+    };
+    execution_deadline_passed: ExecutionDeadlinePassed => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        try_match(env, intent_id, SOL, Some(10)).expect("match");
+        env.warp(11);
+        try_execute(env, intent_id)
+    };
+    execution_deadline_not_reached: ExecutionDeadlineNotReached => |env| {
+        let intent_id = env.create_intent(SOL, false);
+        try_match(env, intent_id, SOL, None).expect("match");
+        let owner = env.owner.pubkey();
+        let (creator, solver) = (env.creator.pubkey(), env.solver.pubkey());
+        env.try_send(&[instructions::timeout_intent(&owner, intent_id, &creator, &solver, false)], &[])
+    };
+    too_many_relayers: TooManyRelayers => |env| set_relayers(env, 17, 1).1;
+    invalid_relayer_threshold: InvalidRelayerThreshold => |env| set_relayers(env, 2, 0).1;
+    not_relayer: NotRelayer => |env| {
+        set_relayers(env, 1, 1).1.expect("set relayers");
+        let stranger = env.funded(SOL);
+        attest(env, &stranger, 5)
+    };
+    already_attested: AlreadyAttested => |env| {
+        let (relayers, result) = set_relayers(env, 2, 2);
+        result.expect("set relayers");
+        attest(env, &relayers[0], 5).expect("first attestation");
+        attest(env, &relayers[0], 5)
+    };
+    invalid_receipt: InvalidReceipt => |env| {
+        let (relayers, result) = set_relayers(env, 1, 1);
+        result.expect("set relayers");
+        let mut args = attest_args(&receipt(env, 5));
+        args.amount = 6;
+        env.try_send(&[instructions::attest_receipt(&relayers[0].pubkey(), args)], &[&relayers[0]])
+    };
+    receipt_still_usable: ReceiptStillUsable => |env| {
+        let (relayers, result) = set_relayers(env, 2, 2);
+        result.expect("set relayers");
+        attest(env, &relayers[0], 5).expect("attestation");
+        let receipt_hash = receipt(env, 5).hash();
+        env.try_send(&[instructions::close_receipt(&relayers[0].pubkey(), &receipt_hash)], &[&relayers[0]])
+    };
+    partial_fill_with_receipt: PartialFillWithReceipt => |env| {
+        update_chain(env, |c| c.requires_receipt = Some(true));
+        let ix = env.create_intent_ix(intent_args(CHAIN_ID_NEAR, SOL, true));
+        let creator = env.creator.insecure_clone();
+        env.try_send(&[ix], &[&creator])
+    };
+    receipt_required: ReceiptRequired => |env| {
+        update_chain(env, |c| c.requires_receipt = Some(true));
+        let intent_id = env.create_intent(SOL, false);
+        try_match(env, intent_id, SOL, None).expect("match");
+        try_execute(env, intent_id)
+    };
+    account_up_to_date: AccountUpToDate => |env| {
+        let owner = env.owner.pubkey();
+        env.try_send(&[instructions::migrate_bridge_config(&owner, &pda::bridge_config())], &[])
+    };
+    too_many_delegates: TooManyDelegates => |env| {
+        let owner = env.owner.pubkey();
+        let delegates = (0..9).map(|_| Pubkey::new_unique()).collect();
+        env.try_send(&[instructions::set_model_delegates(&owner, MODEL_ID, delegates)], &[])
+    };
+    too_many_operators: TooManyOperators => |env| {
+        let owner = env.owner.pubkey();
+        let operators = (0..9).map(|_| Pubkey::new_unique()).collect();
+        env.try_send(&[instructions::set_model_operators(&owner, MODEL_ID, operators)], &[])
+    };
+    invalid_model_index: InvalidModelIndex => |env| {
+        let owner = env.owner.pubkey();
+        let args = args::UpdateModel {
+            name: None,
+            description: None,
+            cost_per_inference: Some(0),
+            is_active: None,
+        };
+        env.try_send(&[instructions::update_model(&owner, MODEL_ID, None, args)], &[])
+    };
+    not_pending_owner: NotPendingOwner => |env| {
+        let owner = env.owner.pubkey();
+        let proposed = env.funded(SOL);
+        env.send(&[instructions::transfer_model_ownership(&owner, MODEL_ID, Some(proposed.pubkey()))], &[]);
+        let stranger = env.funded(SOL);
+        env.try_send(&[instructions::accept_model_ownership(&stranger.pubkey(), MODEL_ID)], &[&stranger])
+    };
+    invalid_model_version: InvalidModelVersion => |env| {
+        let owner = env.owner.pubkey();
+        env.try_send(&[instructions::publish_model_version(&owner, MODEL_ID, "", [1; 32], [2; 32])], &[])
+    };
+    deprecated_model_version: DeprecatedModelVersion => |env| {
+        let owner = env.owner.pubkey();
+        env.send(&[instructions::publish_model_version(&owner, MODEL_ID, "2.0.0", [1; 32], [2; 32])], &[]);
+        env.send(&[instructions::deprecate_model_version(&owner, MODEL_ID, "2.0.0")], &[]);
+        let creator = env.creator.insecure_clone();
+        let ix = request(env, MODEL_ID, "2.0.0");
+        env.try_send(&[ix], &[&creator])
+    };
+    insufficient_credits: InsufficientCredits => |env| {
+        env.top_up(MODEL_COST - 1);
+        let creator = env.creator.insecure_clone();
+        let ix = env.request_ix(Payment::Credits);
+        env.try_send(&[ix], &[&creator])
+    };
+    conflicting_payment: ConflictingPayment => |env| {
+        env.top_up(MODEL_COST);
+        env.offer_plan(1, true);
+        let creator = env.creator.insecure_clone();
+        env.send(&[env.subscribe_ix()], &[&creator]);
+        // Fill the unused optional account slots of one payment with the other's
+        let mut ix = env.request_ix(Payment::Credits);
+        let subscribed = env.request_ix(Payment::Subscription);
+        for (meta, other) in ix.accounts.iter_mut().zip(subscribed.accounts) {
+            if meta.pubkey == ID {
+                *meta = other;
+            }
+        }
+        env.try_send(&[ix], &[&creator])
+    };
+    invalid_subscription_plan: InvalidSubscriptionPlan => |env| {
+        let owner = env.owner.pubkey();
+        let args = args::SetSubscriptionPlan {
+            plan_id: PLAN_ID,
+            inferences_per_epoch: 0,
+            price: PLAN_PRICE,
+            is_active: true,
+        };
+        env.try_send(&[instructions::set_subscription_plan(&owner, MODEL_ID, args)], &[])
+    };
+    already_subscribed: AlreadySubscribed => |env| {
+        env.offer_plan(1, true);
+        let creator = env.creator.insecure_clone();
+        env.send(&[env.subscribe_ix()], &[&creator]);
+        env.try_send(&[env.subscribe_ix()], &[&creator])
+    };
+    subscription_exhausted: SubscriptionExhausted => |env| {
+        env.offer_plan(1, true);
+        let creator = env.creator.insecure_clone();
+        env.send(&[env.subscribe_ix()], &[&creator]);
+        let ix = env.batch_ix(2, Payment::Subscription);
+        env.try_send(&[ix], &[&creator])
+    };
+}
+
+#[test]
+fn every_error_code_is_covered() {
+    let covered: BTreeSet<u32> = COVERED.iter().map(|&code| error_code(code)).collect();
+    let unreachable: BTreeSet<u32> = UNREACHABLE.iter().map(|&(code, _)| error_code(code)).collect();
+    assert!(covered.is_disjoint(&unreachable));
+
+    let first = error_code(ErrorCode::AbortedComputation);
+    let last = error_code(ErrorCode::ReceiptStillUsable);
+    let missing: Vec<u32> = (first..=last)
+        .filter(|code| !covered.contains(code) && !unreachable.contains(code))
+        .collect();
+    assert!(missing.is_empty(), "error codes without a test: {missing:?}");
+}
+
+/// Attest `tamper`ed receipt of the solver on a matched intent, then execute with it
+fn execute_with_receipt(tamper: fn(&mut Intent, &mut FulfillmentReceipt)) -> TxResult {
+    let mut env = Env::new();
+    let (relayers, result) = set_relayers(&mut env, 1, 1);
+    result.expect("set relayers");
+    update_chain(&mut env, |c| c.requires_receipt = Some(true));
+    let intent_id = env.create_intent(SOL, false);
+    try_match(&mut env, intent_id, SOL, None).expect("match");
+
+    let solver = env.solver.insecure_clone();
+    let mut intent: Intent = env.account(&pda::intent(intent_id));
+    let mut receipt = receipt_for(&intent, &solver.pubkey());
+    tamper(&mut intent, &mut receipt);
+    let ix = instructions::attest_receipt(&relayers[0].pubkey(), attest_args(&receipt));
+    env.send(&[ix], &[&relayers[0]]);
+
+    let receipt_hash = receipt.hash();
+    let ix = instructions::execute_intent(
+        &solver.pubkey(),
+        intent_id,
+        CHAIN_ID_NEAR,
+        Some(&receipt_hash),
+        [9; 32],
+        None,
+    );
+    env.try_send(&[ix], &[&solver])
+}
+
+#[test]
+fn receipt_for_another_intent_is_rejected() {
+    let result = execute_with_receipt(|intent, receipt| {
+        intent.source_amount += 1;
+        receipt.intent_hash = CrossChainIntent::from(&*intent).hash();
+    });
+    assert_error(result, ErrorCode::InvalidReceipt);
+}
+
+#[test]
+fn receipt_for_another_recipient_is_rejected() {
+    let result = execute_with_receipt(|_, receipt| {
+        receipt.recipient_hash = silence_intent::recipient_hash("mallory.near");
+    });
+    assert_error(result, ErrorCode::InvalidReceipt);
+}
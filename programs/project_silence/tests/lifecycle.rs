@@ -0,0 +1,449 @@
+//! Intent and inference lifecycles, end to end.
+
+mod common;
+
+use common::*;
+use project_silence::instruction as args;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{BatchInference, InferenceRequest, Intent, IntentStatus, Solver, UserMetrics};
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::{instructions, pda, ID};
+use silence_intent::{FulfillmentReceipt, CHAIN_ID_NEAR};
+use solana_sdk::clock::Clock;
+use solana_sdk::signature::Signer;
+
+fn intent(env: &Env, intent_id: u64) -> Intent {
+    env.account(&pda::intent(intent_id))
+}
+
+#[test]
+fn create_escrows_amount_in_program_owned_vault() {
+    let mut env = Env::new();
+    let intent_id = env.create_intent(2 * SOL, false);
+
+    let vault = env.svm.get_account(&pda::intent_vault(intent_id)).unwrap();
+    assert_eq!(vault.owner, ID);
+    assert_eq!(vault.lamports, 2 * SOL + env.rent_exempt(0));
+
+    let intent = intent(&env, intent_id);
+    assert!(intent.status == IntentStatus::Created);
+    assert_eq!(intent.creator, env.creator.pubkey());
+    assert_eq!(intent.remaining_amount(), 2 * SOL);
+    assert_eq!(intent.expires_at, intent.created_at + TTL_SECS);
+}
+
+#[test]
+fn full_fill_settles_and_closes() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let intent_id = env.create_intent(2 * SOL, false);
+
+    env.match_intent(&solver, intent_id, 2 * SOL, None);
+    assert!(intent(&env, intent_id).status == IntentStatus::Matched);
+    env.execute_intent(&solver, intent_id);
+    let executed = intent(&env, intent_id);
+    assert!(executed.status == IntentStatus::Executed);
+    assert_eq!(executed.destination_tx_hash, [9; 32]);
+
+    env.warp(FINALITY_SECS as i64);
+    let events = env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    let [ProgramEvent::IntentSettled(settled)] = events.as_slice() else {
+        panic!("expected IntentSettled");
+    };
+    assert_eq!(settled.fill_amount, 2 * SOL);
+    assert_eq!(settled.protocol_fee, 2 * SOL * PROTOCOL_FEE_BPS as u64 / 10_000);
+    assert_eq!(settled.solver_reward + settled.protocol_fee, 2 * SOL);
+
+    let settled = intent(&env, intent_id);
+    assert!(settled.status == IntentStatus::Settled);
+    assert_eq!(settled.settled_amount, 2 * SOL);
+    let record: Solver = env.account(&pda::solver(&solver.pubkey()));
+    assert_eq!(record.successful_intents, 1);
+    assert_eq!(record.total_volume, 2 * SOL);
+
+    let creator = env.creator.insecure_clone();
+    let events = env.send(&[instructions::close_intent(&creator.pubkey(), intent_id)], &[&creator]);
+    assert!(matches!(events.as_slice(), [ProgramEvent::IntentClosed(e)] if e.intent_id == intent_id));
+    assert!(!env.exists(&pda::intent(intent_id)));
+    assert!(!env.exists(&pda::intent_vault(intent_id)));
+}
+
+#[test]
+fn partial_fills_from_two_solvers() {
+    let mut env = Env::new();
+    let (solver, solver2) = (env.solver.insecure_clone(), env.solver2.insecure_clone());
+    let intent_id = env.create_intent(3 * SOL, true);
+
+    env.match_intent(&solver, intent_id, SOL, None);
+    let partly = intent(&env, intent_id);
+    assert!(partly.status == IntentStatus::Created);
+    assert_eq!(partly.remaining_amount(), 2 * SOL);
+
+    env.match_intent(&solver2, intent_id, 2 * SOL, None);
+    let matched = intent(&env, intent_id);
+    assert!(matched.status == IntentStatus::Matched);
+    assert_eq!(matched.matched_fills, 2);
+
+    env.execute_intent(&solver, intent_id);
+    assert!(intent(&env, intent_id).status == IntentStatus::Matched);
+    env.execute_intent(&solver2, intent_id);
+    assert!(intent(&env, intent_id).status == IntentStatus::Executed);
+
+    env.warp(FINALITY_SECS as i64);
+    env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    let half = intent(&env, intent_id);
+    assert!(half.status == IntentStatus::Executed);
+    assert_eq!(half.settled_amount, SOL);
+
+    env.send(&[env.settle_ix(&solver2.pubkey(), intent_id)], &[]);
+    let done = intent(&env, intent_id);
+    assert!(done.status == IntentStatus::Settled);
+    assert_eq!(done.settled_amount, 3 * SOL);
+    assert_eq!(done.executed_fills, 0);
+}
+
+#[test]
+fn failed_fill_refunds_creator() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let creator = env.creator.pubkey();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, None);
+
+    let before = env.lamports(&creator);
+    let events = env.send(
+        &[instructions::fail_intent(&solver.pubkey(), intent_id, &creator)],
+        &[&solver],
+    );
+    assert!(matches!(events.as_slice(), [ProgramEvent::IntentFailed(e)] if e.refunded == SOL));
+    assert_eq!(env.lamports(&creator), before + SOL);
+
+    let failed = intent(&env, intent_id);
+    assert!(failed.status == IntentStatus::Failed);
+    assert_eq!(failed.refunded_amount, SOL);
+    let record: Solver = env.account(&pda::solver(&solver.pubkey()));
+    assert_eq!(record.failed_intents, 1);
+    assert!(!env.exists(&pda::fill(&pda::intent(intent_id), &solver.pubkey())));
+}
+
+#[test]
+fn timeout_refunds_creator() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let creator = env.creator.pubkey();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, Some(10));
+    env.warp(11);
+
+    // Permissionless: the owner times the fill out
+    let before = env.lamports(&creator);
+    let owner = env.owner.pubkey();
+    let events = env.send(
+        &[instructions::timeout_intent(
+            &owner,
+            intent_id,
+            &creator,
+            &solver.pubkey(),
+            false,
+        )],
+        &[],
+    );
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::IntentTimedOut(e)] if e.amount == SOL && !e.reopened
+    ));
+    assert_eq!(env.lamports(&creator), before + SOL);
+    assert!(intent(&env, intent_id).status == IntentStatus::Failed);
+}
+
+#[test]
+fn timeout_with_reopen_lets_another_solver_match() {
+    let mut env = Env::new();
+    let (solver, solver2) = (env.solver.insecure_clone(), env.solver2.insecure_clone());
+    let creator = env.creator.insecure_clone();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, Some(10));
+    env.warp(11);
+
+    env.send(
+        &[instructions::timeout_intent(
+            &creator.pubkey(),
+            intent_id,
+            &creator.pubkey(),
+            &solver.pubkey(),
+            true,
+        )],
+        &[&creator],
+    );
+    let reopened = intent(&env, intent_id);
+    assert!(reopened.status == IntentStatus::Created);
+    assert_eq!(reopened.remaining_amount(), SOL);
+    assert_eq!(reopened.refunded_amount, 0);
+
+    env.match_and_execute(&solver2, intent_id, SOL);
+    assert!(intent(&env, intent_id).status == IntentStatus::Executed);
+}
+
+#[test]
+fn cancel_refunds_unmatched_intent() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+    let intent_id = env.create_intent(SOL, false);
+
+    let before = env.lamports(&creator.pubkey());
+    let events = env.send(
+        &[instructions::cancel_intent(&creator.pubkey(), intent_id)],
+        &[&creator],
+    );
+    assert!(matches!(events.as_slice(), [ProgramEvent::IntentCancelled(e)] if e.refunded == SOL));
+    assert_eq!(env.lamports(&creator.pubkey()), before + SOL);
+    assert!(intent(&env, intent_id).status == IntentStatus::Cancelled);
+
+    env.send(&[instructions::close_intent(&creator.pubkey(), intent_id)], &[&creator]);
+    assert!(!env.exists(&pda::intent(intent_id)));
+}
+
+#[test]
+fn batch_settlement_covers_every_intent() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let ids: Vec<u64> = (0..3).map(|_| env.create_intent(SOL, false)).collect();
+    for &id in &ids {
+        env.match_and_execute(&solver, id, SOL);
+    }
+    env.warp(FINALITY_SECS as i64);
+
+    let owner = env.owner.pubkey();
+    let events = env.send(
+        &[instructions::settle_intents_batch(
+            &owner,
+            &solver.pubkey(),
+            CHAIN_ID_NEAR,
+            &ids,
+        )],
+        &[],
+    );
+    let batch = events
+        .iter()
+        .find_map(|event| match event {
+            ProgramEvent::IntentsBatchSettled(batch) => Some(batch),
+            _ => None,
+        })
+        .expect("IntentsBatchSettled emitted");
+    assert_eq!(batch.settled_count, 3);
+    assert_eq!(batch.skipped_count, 0);
+    assert_eq!(batch.total_volume, 3 * SOL);
+    for id in ids {
+        assert!(intent(&env, id).status == IntentStatus::Settled);
+        assert!(!env.exists(&pda::fill(&pda::intent(id), &solver.pubkey())));
+    }
+}
+
+#[test]
+fn volume_cap_defers_settlement() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let intent_id = env.create_intent(2 * SOL, false);
+    env.match_and_execute(&solver, intent_id, 2 * SOL);
+    env.warp(FINALITY_SECS as i64);
+
+    let owner = env.owner.pubkey();
+    env.send(&[instructions::set_rate_limits(&owner, 86_400, SOL)], &[]);
+    let events = env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::VolumeCapReached(e)] if e.solver == Some(solver.pubkey()) && e.cap == SOL
+    ));
+    assert!(intent(&env, intent_id).status == IntentStatus::Executed);
+
+    env.send(&[instructions::set_rate_limits(&owner, 86_400, 0)], &[]);
+    env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+    assert!(intent(&env, intent_id).status == IntentStatus::Settled);
+}
+
+#[test]
+fn volume_caps_slide_across_window_boundaries() {
+    const WINDOW_SECS: i64 = 1_000;
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let owner = env.owner.pubkey();
+    env.send(&[instructions::set_rate_limits(&owner, WINDOW_SECS, SOL)], &[]);
+    let (first, second) = (env.create_intent(SOL, false), env.create_intent(SOL, false));
+    env.match_and_execute(&solver, first, SOL);
+    env.match_and_execute(&solver, second, SOL);
+
+    // Settle the first fill 10s before a window boundary
+    let now = env.svm.get_sysvar::<Clock>().unix_timestamp;
+    env.warp(2 * WINDOW_SECS - now.rem_euclid(WINDOW_SECS) - 10);
+    env.send(&[env.settle_ix(&solver.pubkey(), first)], &[]);
+
+    // 10s into the next window, 990s of the previous one still count
+    env.warp(20);
+    let events = env.send(&[env.settle_ix(&solver.pubkey(), second)], &[]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::VolumeCapReached(e)] if e.solver == Some(solver.pubkey()) && e.window_volume == SOL / 1_000 * 990
+    ));
+    assert!(intent(&env, second).status == IntentStatus::Executed);
+
+    env.warp(WINDOW_SECS);
+    env.send(&[env.settle_ix(&solver.pubkey(), second)], &[]);
+    assert!(intent(&env, second).status == IntentStatus::Settled);
+}
+
+#[test]
+fn attested_receipt_replaces_destination_tx_hash() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let relayers = [env.funded(SOL), env.funded(SOL)];
+    let owner = env.owner.pubkey();
+    env.send(
+        &[
+            instructions::set_relayers(&owner, relayers.iter().map(|r| r.pubkey()).collect(), 2),
+            instructions::update_chain(
+                &owner,
+                CHAIN_ID_NEAR,
+                args::UpdateChain {
+                    name: None,
+                    min_amount: None,
+                    finality_seconds: None,
+                    fee_bps_override: None,
+                    volume_cap: None,
+                    requires_receipt: Some(true),
+                },
+            ),
+        ],
+        &[],
+    );
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, None);
+
+    let receipt = receipt_for(&intent(&env, intent_id), &solver.pubkey());
+    let receipt_hash = receipt.hash();
+    // A conflicting report tallies on its own receipt and blocks nothing
+    let conflicting = FulfillmentReceipt {
+        amount: 6,
+        ..receipt.clone()
+    };
+    env.send(
+        &[instructions::attest_receipt(
+            &relayers[0].pubkey(),
+            attest_args(&conflicting),
+        )],
+        &[&relayers[0]],
+    );
+    for (i, relayer) in relayers.iter().enumerate() {
+        let ix = instructions::attest_receipt(&relayer.pubkey(), attest_args(&receipt));
+        let events = env.send(&[ix], &[relayer]);
+        let [ProgramEvent::ReceiptAttested(attested)] = events.as_slice() else {
+            panic!("expected ReceiptAttested");
+        };
+        assert_eq!(attested.receipt_hash, receipt_hash);
+        assert_eq!(attested.attestations as usize, i + 1);
+        assert_eq!(attested.attested, i == 1);
+    }
+
+    env.send(
+        &[instructions::execute_intent(
+            &solver.pubkey(),
+            intent_id,
+            CHAIN_ID_NEAR,
+            Some(&receipt_hash),
+            [9; 32],
+            None,
+        )],
+        &[&solver],
+    );
+    let executed = intent(&env, intent_id);
+    assert!(executed.status == IntentStatus::Executed);
+    assert_eq!(executed.destination_tx_hash, receipt_hash);
+
+    // Consumed receipts close right away, stale ones after the execution window
+    let relayer = relayers[0].pubkey();
+    let before = env.lamports(&relayer);
+    env.send(&[instructions::close_receipt(&relayer, &receipt_hash)], &[&relayers[0]]);
+    assert!(!env.exists(&pda::receipt(&receipt_hash)));
+    assert!(env.lamports(&relayer) > before);
+    env.warp(86_401);
+    env.send(
+        &[instructions::close_receipt(&relayer, &conflicting.hash())],
+        &[&relayers[0]],
+    );
+    assert!(!env.exists(&pda::receipt(&conflicting.hash())));
+}
+
+#[test]
+fn inference_request_lifecycle() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+
+    let request = env.create_request();
+    let pending: InferenceRequest = env.account(&request);
+    assert_eq!(pending.status, 0);
+    assert_eq!(pending.model_id, MODEL_ID);
+
+    env.send(
+        &[instructions::store_inference_result(
+            &creator.pubkey(),
+            &request,
+            &creator.pubkey(),
+            [2; 32],
+            [3; 32],
+            true,
+        )],
+        &[&creator],
+    );
+    let completed: InferenceRequest = env.account(&request);
+    assert_eq!(completed.status, 2);
+    assert_eq!(completed.result_hash, [2; 32]);
+
+    let events = env.send(
+        &[instructions::close_inference_request(&creator.pubkey(), &request)],
+        &[&creator],
+    );
+    assert!(matches!(events.as_slice(), [ProgramEvent::InferenceRequestClosed(e)] if e.request_id == 0));
+    assert!(!env.exists(&request));
+
+    let metrics: UserMetrics = env.account(&pda::user_metrics(&creator.pubkey()));
+    assert_eq!(metrics.total_inferences, 1);
+    assert_eq!(metrics.total_cost, MODEL_COST);
+    assert_eq!(metrics.successful_inferences, 1);
+    assert_eq!(metrics.next_request_id, 1);
+}
+
+#[test]
+fn batch_inference_lifecycle() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+    let owner = env.owner.pubkey();
+    let batch = env.create_batch(5);
+
+    // Only the model's operators report results, not the batch's user
+    let self_report = instructions::record_batch_results(&creator.pubkey(), &batch, &creator.pubkey(), MODEL_ID, 5, 0);
+    env.set_owner_operator();
+    assert_error(env.try_send(&[self_report], &[&creator]), ErrorCode::Unauthorized);
+
+    for (completed, failed) in [(3, 0), (1, 1)] {
+        env.send(
+            &[instructions::record_batch_results(
+                &owner,
+                &batch,
+                &creator.pubkey(),
+                MODEL_ID,
+                completed,
+                failed,
+            )],
+            &[],
+        );
+    }
+    let recorded: BatchInference = env.account(&batch);
+    assert_eq!((recorded.completed_count, recorded.failed_count), (4, 1));
+
+    env.send(&[instructions::close_batch(&creator.pubkey(), &batch)], &[&creator]);
+    assert!(!env.exists(&batch));
+
+    let metrics: UserMetrics = env.account(&pda::user_metrics(&creator.pubkey()));
+    assert_eq!(metrics.total_inferences, 5);
+    assert_eq!(metrics.total_cost, 5 * MODEL_COST);
+    assert_eq!(metrics.failed_inferences, 1);
+}
@@ -0,0 +1,288 @@
+//! Accounts created before `AccountVersion` and their `migrate_*` upgrades.
+
+mod common;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, Space};
+use common::*;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{
+    decode, AccountVersion, BatchInference, BridgeConfig, ChainConfig, FeeVault, InferenceRequest, Intent, IntentFill,
+    ModelMetadata, PreviousVolume, Solver, UserMetrics, VersionPin, Versioned,
+};
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::instructions::Payment;
+use project_silence_client::{instructions, pda};
+use silence_intent::CHAIN_ID_NEAR;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// Rewrite the account at `address` the way the program laid it out before
+/// versioning: without the version byte, the fields `revert` drops and the
+/// rent for them
+fn make_legacy<T: AccountSerialize + AccountDeserialize>(env: &mut Env, address: &Pubkey, revert: fn(&mut T)) {
+    let mut account = env.svm.get_account(address).expect("account exists");
+    let mut state: T = decode(&account.data).unwrap();
+    revert(&mut state);
+    let len = account.data.len();
+    let mut data = Vec::with_capacity(len);
+    state.try_serialize(&mut data).unwrap();
+    let legacy_len = data.len();
+    account.data = data;
+    account.lamports -= env.rent_exempt(len) - env.rent_exempt(legacy_len);
+    env.svm.set_account(*address, account).unwrap();
+}
+
+fn legacy_chain(chain: &mut ChainConfig) {
+    chain.layout_version = AccountVersion::LEGACY;
+    chain.previous_window_volume = PreviousVolume(None);
+}
+
+fn legacy_solver(solver: &mut Solver) {
+    solver.layout_version = AccountVersion::LEGACY;
+    solver.previous_window_volume = PreviousVolume(None);
+}
+
+fn data_len(env: &Env, address: &Pubkey) -> usize {
+    env.svm.get_account(address).expect("account exists").data.len()
+}
+
+/// Whether the account at `address` has its type's current layout version
+fn is_current<T: AccountDeserialize + Versioned>(
+    env: &Env,
+    address: &Pubkey,
+    version: fn(&T) -> AccountVersion,
+) -> bool {
+    version(&env.account(address)) == T::LAYOUT_VERSION
+}
+
+fn anchor_error(code: anchor_lang::error::ErrorCode) -> u32 {
+    code.into()
+}
+
+#[test]
+fn new_accounts_use_the_current_layout() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let intent_id = env.create_intent(SOL, false);
+    env.match_intent(&solver, intent_id, SOL, None);
+    let request = env.create_request();
+
+    let creator = env.creator.pubkey();
+    let intent = pda::intent(intent_id);
+    let chain = pda::chain_config(CHAIN_ID_NEAR);
+    let fill = pda::fill(&intent, &solver.pubkey());
+    let solver = pda::solver(&solver.pubkey());
+    assert!(is_current::<BridgeConfig>(&env, &pda::bridge_config(), |a| a.layout_version));
+    assert!(is_current::<FeeVault>(&env, &pda::fee_vault(), |a| a.layout_version));
+    assert!(is_current::<ChainConfig>(&env, &chain, |a| a.layout_version));
+    assert!(is_current::<ModelMetadata>(&env, &pda::model(MODEL_ID), |a| a.layout_version));
+    assert!(is_current::<Solver>(&env, &solver, |a| a.layout_version));
+    assert!(is_current::<Intent>(&env, &intent, |a| a.layout_version));
+    assert!(is_current::<IntentFill>(&env, &fill, |a| a.layout_version));
+    assert!(is_current::<UserMetrics>(&env, &pda::user_metrics(&creator), |a| a.layout_version));
+    assert!(is_current::<InferenceRequest>(&env, &request, |a| a.layout_version));
+    assert_eq!(data_len(&env, &pda::bridge_config()), 8 + BridgeConfig::INIT_SPACE);
+}
+
+#[test]
+fn versions_are_counted_per_account_type() {
+    let mut env = Env::new();
+    let payer = env.funded(SOL);
+    let intent_id = env.create_intent(SOL, false);
+    assert_eq!(Intent::LAYOUT_VERSION, AccountVersion(1));
+    assert_eq!(BatchInference::LAYOUT_VERSION, AccountVersion(2));
+    assert_eq!(InferenceRequest::LAYOUT_VERSION, AccountVersion::LATEST);
+
+    // Intents are unchanged since version 1, so a version 1 intent is current
+    let ix = instructions::migrate_intent(&payer.pubkey(), &pda::intent(intent_id));
+    assert_error(env.try_send(&[ix], &[&payer]), ErrorCode::AccountUpToDate);
+}
+
+#[test]
+fn legacy_accounts_keep_working_until_migrated() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let solver_pda = pda::solver(&solver.pubkey());
+    make_legacy::<BridgeConfig>(&mut env, &pda::bridge_config(), |a| {
+        a.layout_version = AccountVersion::LEGACY
+    });
+    make_legacy::<FeeVault>(&mut env, &pda::fee_vault(), |a| {
+        a.layout_version = AccountVersion::LEGACY
+    });
+    make_legacy::<ChainConfig>(&mut env, &pda::chain_config(CHAIN_ID_NEAR), legacy_chain);
+    make_legacy::<Solver>(&mut env, &solver_pda, legacy_solver);
+    let config_len = data_len(&env, &pda::bridge_config());
+    assert_eq!(config_len, 8 + BridgeConfig::INIT_SPACE - 1);
+
+    // Every legacy account is read and written back by a full fill
+    let intent_id = env.create_intent(SOL, false);
+    env.match_and_execute(&solver, intent_id, SOL);
+    env.warp(FINALITY_SECS as i64);
+    env.send(&[env.settle_ix(&solver.pubkey(), intent_id)], &[]);
+
+    let config: BridgeConfig = env.account(&pda::bridge_config());
+    assert_eq!(config.layout_version, AccountVersion::LEGACY);
+    assert_eq!(config.total_intents, intent_id + 1);
+    assert_eq!(data_len(&env, &pda::bridge_config()), config_len);
+    let solver_state: Solver = env.account(&solver_pda);
+    assert_eq!(solver_state.layout_version, AccountVersion::LEGACY);
+    assert_eq!(solver_state.successful_intents, 1);
+    // Fixed windows until migrated: no previous volume is written back
+    assert_eq!(solver_state.previous_window_volume, PreviousVolume(None));
+    assert!(env.account::<FeeVault>(&pda::fee_vault()).total_collected > 0);
+}
+
+#[test]
+fn migration_reallocates_and_stamps_the_version() {
+    let mut env = Env::new();
+    let payer = env.funded(SOL);
+    make_legacy::<BridgeConfig>(&mut env, &pda::bridge_config(), |a| {
+        a.layout_version = AccountVersion::LEGACY
+    });
+    let before: BridgeConfig = env.account(&pda::bridge_config());
+    let legacy_rent = env.lamports(&pda::bridge_config());
+
+    let events = env.send(
+        &[instructions::migrate_bridge_config(
+            &payer.pubkey(),
+            &pda::bridge_config(),
+        )],
+        &[&payer],
+    );
+    match events.as_slice() {
+        [ProgramEvent::AccountMigrated(event)] => {
+            assert_eq!(event.account, pda::bridge_config());
+            let to_version = BridgeConfig::LAYOUT_VERSION.0;
+            assert_eq!((event.from_version, event.to_version), (0, to_version));
+        }
+        _ => panic!("expected one AccountMigrated event"),
+    }
+
+    let space = 8 + BridgeConfig::INIT_SPACE;
+    assert_eq!(data_len(&env, &pda::bridge_config()), space);
+    assert_eq!(env.lamports(&pda::bridge_config()), env.rent_exempt(space));
+    assert_eq!(
+        env.lamports(&payer.pubkey()),
+        SOL - (env.rent_exempt(space) - legacy_rent)
+    );
+
+    let after: BridgeConfig = env.account(&pda::bridge_config());
+    assert_eq!(after.layout_version, BridgeConfig::LAYOUT_VERSION);
+    assert_eq!(after.owner, before.owner);
+    assert_eq!(after.total_intents, before.total_intents);
+    assert_eq!(after.max_execution_secs, before.max_execution_secs);
+    assert_eq!(after.bump, before.bump);
+
+    let again = instructions::migrate_bridge_config(&payer.pubkey(), &pda::bridge_config());
+    assert_error(env.try_send(&[again], &[&payer]), ErrorCode::AccountUpToDate);
+}
+
+#[test]
+fn migrated_solver_keeps_its_state() {
+    let mut env = Env::new();
+    let solver = env.solver.insecure_clone();
+    let solver_pda = pda::solver(&solver.pubkey());
+    let before: Solver = env.account(&solver_pda);
+    make_legacy::<Solver>(&mut env, &solver_pda, legacy_solver);
+
+    env.send(
+        &[instructions::migrate_solver(&solver.pubkey(), &solver_pda)],
+        &[&solver],
+    );
+    let after: Solver = env.account(&solver_pda);
+    assert_eq!(after.layout_version, Solver::LAYOUT_VERSION);
+    assert_eq!(after.supported_chains, before.supported_chains);
+    assert_eq!(after.stake, before.stake);
+    assert_eq!(after.reputation_score, before.reputation_score);
+    assert_eq!(after.metadata_uri, before.metadata_uri);
+    assert_eq!(after.previous_window_volume, PreviousVolume(Some(0)));
+    assert_eq!(data_len(&env, &solver_pda), 8 + Solver::INIT_SPACE);
+
+    let intent_id = env.create_intent(SOL, false);
+    env.match_and_execute(&solver, intent_id, SOL);
+}
+
+#[test]
+fn legacy_user_metrics_must_be_migrated_before_reuse() {
+    let mut env = Env::new();
+    env.create_request();
+    let creator = env.creator.insecure_clone();
+    let metrics = pda::user_metrics(&creator.pubkey());
+    make_legacy::<UserMetrics>(&mut env, &metrics, |a| a.layout_version = AccountVersion::LEGACY);
+
+    // `init_if_needed` checks the existing size against the current layout
+    let owner = env.owner.pubkey();
+    let ix = instructions::create_inference_request(
+        &creator.pubkey(),
+        MODEL_ID,
+        &owner,
+        MODEL_VERSION,
+        Payment::Lamports,
+        1,
+        request_args(),
+    );
+    let Err(failure) = env.try_send(&[ix], &[&creator]) else {
+        panic!("legacy size accepted");
+    };
+    assert_eq!(
+        failure.custom_code(),
+        Some(anchor_error(anchor_lang::error::ErrorCode::ConstraintSpace))
+    );
+
+    env.send(
+        &[instructions::migrate_user_metrics(&creator.pubkey(), &metrics)],
+        &[&creator],
+    );
+    env.create_request();
+    assert_eq!(env.account::<UserMetrics>(&metrics).next_request_id, 2);
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+    let mut env = Env::new();
+    let mut account = env.svm.get_account(&pda::bridge_config()).unwrap();
+    *account.data.last_mut().unwrap() = AccountVersion::LATEST.0 + 1;
+    env.svm.set_account(pda::bridge_config(), account).unwrap();
+
+    let owner = env.owner.pubkey();
+    let Err(failure) = env.try_send(&[instructions::set_protocol_fee(&owner, 10)], &[]) else {
+        panic!("future layout accepted");
+    };
+    assert_eq!(
+        failure.custom_code(),
+        Some(anchor_error(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))
+    );
+}
+
+#[test]
+fn version_one_requests_decode_unpinned_until_migrated() {
+    let mut env = Env::new();
+    let request = env.create_request();
+    let payer = env.funded(SOL);
+
+    // Version 1 ended at `layout_version`, before the version pin
+    let mut account = env.svm.get_account(&request).unwrap();
+    let len = account.data.len();
+    let v1_len = len - VersionPin::INIT_SPACE;
+    let mut state: InferenceRequest = decode(&account.data).unwrap();
+    state.layout_version = AccountVersion(1);
+    let mut data = Vec::with_capacity(len);
+    state.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), v1_len);
+    account.data = data;
+    account.lamports -= env.rent_exempt(len) - env.rent_exempt(v1_len);
+    env.svm.set_account(request, account).unwrap();
+
+    let v1: InferenceRequest = env.account(&request);
+    assert_eq!(v1.model_version, VersionPin(None));
+    assert_eq!(v1.prompt_hash, state.prompt_hash);
+
+    env.send(
+        &[instructions::migrate_inference_request(&payer.pubkey(), &request)],
+        &[&payer],
+    );
+    let migrated: InferenceRequest = env.account(&request);
+    assert_eq!(migrated.layout_version, InferenceRequest::LAYOUT_VERSION);
+    assert_eq!(migrated.model_version, VersionPin(None));
+    assert_eq!(data_len(&env, &request), 8 + InferenceRequest::INIT_SPACE);
+}
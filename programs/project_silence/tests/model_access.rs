@@ -0,0 +1,256 @@
+//! Two-step model ownership transfer, delegates, operators and payout address.
+
+mod common;
+
+use common::*;
+use project_silence::instruction as args;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{ModelAccess, ModelMetadata, ModelOperators};
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::instructions::Payment;
+use project_silence_client::{instructions, pda};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn rename(authority: &Pubkey, name: &str) -> Instruction {
+    instructions::update_model(
+        authority,
+        MODEL_ID,
+        Some(0),
+        args::UpdateModel {
+            name: Some(name.to_string()),
+            description: None,
+            cost_per_inference: None,
+            is_active: None,
+        },
+    )
+}
+
+fn request(env: &Env, payee: &Pubkey) -> Instruction {
+    instructions::create_inference_request(
+        &env.creator.pubkey(),
+        MODEL_ID,
+        payee,
+        MODEL_VERSION,
+        Payment::Lamports,
+        0,
+        request_args(),
+    )
+}
+
+fn propose(env: &mut Env, new_owner: Option<Pubkey>) {
+    let owner = env.owner.pubkey();
+    env.send(
+        &[instructions::transfer_model_ownership(&owner, MODEL_ID, new_owner)],
+        &[],
+    );
+}
+
+fn accept(env: &mut Env, new_owner: &Keypair) -> Vec<ProgramEvent> {
+    env.send(
+        &[instructions::accept_model_ownership(&new_owner.pubkey(), MODEL_ID)],
+        &[new_owner],
+    )
+}
+
+#[test]
+fn ownership_moves_only_once_accepted() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let new_owner = env.funded(SOL);
+    propose(&mut env, Some(new_owner.pubkey()));
+
+    // Proposing changes nothing yet
+    assert_eq!(env.account::<ModelMetadata>(&pda::model(MODEL_ID)).owner, owner);
+    assert_error(
+        env.try_send(&[rename(&new_owner.pubkey(), "early")], &[&new_owner]),
+        ErrorCode::Unauthorized,
+    );
+
+    match accept(&mut env, &new_owner).as_slice() {
+        [ProgramEvent::ModelOwnershipTransferred(event)] => {
+            assert_eq!(event.model_id, MODEL_ID);
+            assert_eq!((event.previous_owner, event.new_owner), (owner, new_owner.pubkey()));
+        }
+        _ => panic!("expected one ModelOwnershipTransferred event"),
+    }
+    assert_eq!(
+        env.account::<ModelMetadata>(&pda::model(MODEL_ID)).owner,
+        new_owner.pubkey()
+    );
+    assert_eq!(
+        env.account::<ModelAccess>(&pda::model_access(MODEL_ID)).pending_owner,
+        None
+    );
+
+    assert_error(env.try_send(&[rename(&owner, "old")], &[]), ErrorCode::Unauthorized);
+    env.send(&[rename(&new_owner.pubkey(), "new")], &[&new_owner]);
+    assert_error(
+        env.try_send(
+            &[instructions::accept_model_ownership(&new_owner.pubkey(), MODEL_ID)],
+            &[&new_owner],
+        ),
+        ErrorCode::NotPendingOwner,
+    );
+}
+
+#[test]
+fn cancelled_transfer_cannot_be_accepted() {
+    let mut env = Env::new();
+    let new_owner = env.funded(SOL);
+    propose(&mut env, Some(new_owner.pubkey()));
+    propose(&mut env, None);
+
+    assert_error(
+        env.try_send(
+            &[instructions::accept_model_ownership(&new_owner.pubkey(), MODEL_ID)],
+            &[&new_owner],
+        ),
+        ErrorCode::NotPendingOwner,
+    );
+}
+
+#[test]
+fn delegates_update_but_do_not_manage() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let delegate = env.funded(SOL);
+    env.send(
+        &[instructions::set_model_delegates(
+            &owner,
+            MODEL_ID,
+            vec![delegate.pubkey()],
+        )],
+        &[],
+    );
+
+    env.send(&[rename(&delegate.pubkey(), "delegated")], &[&delegate]);
+    assert_eq!(env.account::<ModelMetadata>(&pda::model(MODEL_ID)).name, "delegated");
+
+    let payout = instructions::set_model_payout(&delegate.pubkey(), MODEL_ID, Some(delegate.pubkey()));
+    let transfer = instructions::transfer_model_ownership(&delegate.pubkey(), MODEL_ID, Some(delegate.pubkey()));
+    let delegates = instructions::set_model_delegates(&delegate.pubkey(), MODEL_ID, vec![]);
+    for ix in [payout, transfer, delegates] {
+        assert_error(env.try_send(&[ix], &[&delegate]), ErrorCode::Unauthorized);
+    }
+
+    env.send(&[instructions::set_model_delegates(&owner, MODEL_ID, vec![])], &[]);
+    assert_error(
+        env.try_send(&[rename(&delegate.pubkey(), "revoked")], &[&delegate]),
+        ErrorCode::Unauthorized,
+    );
+}
+
+#[test]
+fn delegates_manage_operators() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let delegate = env.funded(SOL);
+    let operator = Pubkey::new_unique();
+    let stranger = env.funded(SOL);
+    let unauthorized = instructions::set_model_operators(&stranger.pubkey(), MODEL_ID, vec![stranger.pubkey()]);
+    assert_error(env.try_send(&[unauthorized], &[&stranger]), ErrorCode::Unauthorized);
+
+    env.send(
+        &[instructions::set_model_delegates(
+            &owner,
+            MODEL_ID,
+            vec![delegate.pubkey()],
+        )],
+        &[],
+    );
+    let events = env.send(
+        &[instructions::set_model_operators(
+            &delegate.pubkey(),
+            MODEL_ID,
+            vec![operator],
+        )],
+        &[&delegate],
+    );
+    match events.as_slice() {
+        [ProgramEvent::ModelOperatorsUpdated(event)] => {
+            assert_eq!(event.model_id, MODEL_ID);
+            assert_eq!(event.operators, vec![operator]);
+        }
+        _ => panic!("expected one ModelOperatorsUpdated event"),
+    }
+    let operators: ModelOperators = env.account(&pda::model_operators(MODEL_ID));
+    assert_eq!(operators.model_id, MODEL_ID);
+    assert_eq!(operators.operators, vec![operator]);
+
+    env.send(&[instructions::set_model_operators(&owner, MODEL_ID, vec![])], &[]);
+    assert!(env
+        .account::<ModelOperators>(&pda::model_operators(MODEL_ID))
+        .operators
+        .is_empty());
+}
+
+#[test]
+fn payments_go_to_the_payout_address() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let creator = env.creator.insecure_clone();
+    let payout = env.funded(SOL).pubkey();
+    let events = env.send(&[instructions::set_model_payout(&owner, MODEL_ID, Some(payout))], &[]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::ModelPayoutUpdated(event)] if event.payout == payout
+    ));
+
+    let unpaid = request(&env, &owner);
+    assert_error(env.try_send(&[unpaid], &[&creator]), ErrorCode::InvalidOwner);
+    let owner_before = env.lamports(&owner);
+    let paid = request(&env, &payout);
+    env.send(&[paid], &[&creator]);
+    assert_eq!(env.lamports(&payout), SOL + MODEL_COST);
+
+    let batch = instructions::create_batch_inference(
+        &creator.pubkey(),
+        MODEL_ID,
+        &payout,
+        MODEL_VERSION,
+        Payment::Lamports,
+        0,
+        batch_args(3),
+    );
+    env.send(&[batch], &[&creator]);
+    assert_eq!(env.lamports(&payout), SOL + 4 * MODEL_COST);
+
+    // The owner only paid transaction fees
+    assert!(env.lamports(&owner) <= owner_before);
+
+    env.send(&[instructions::set_model_payout(&owner, MODEL_ID, None)], &[]);
+    env.create_request();
+}
+
+#[test]
+fn accepted_transfer_resets_delegates_and_payout() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let delegate = env.funded(SOL);
+    let new_owner = env.funded(SOL);
+    env.send(
+        &[
+            instructions::set_model_delegates(&owner, MODEL_ID, vec![delegate.pubkey()]),
+            instructions::set_model_payout(&owner, MODEL_ID, Some(delegate.pubkey())),
+            instructions::transfer_model_ownership(&owner, MODEL_ID, Some(new_owner.pubkey())),
+        ],
+        &[],
+    );
+    accept(&mut env, &new_owner);
+
+    let access: ModelAccess = env.account(&pda::model_access(MODEL_ID));
+    assert!(access.delegates.is_empty());
+    assert_eq!(access.payout, None);
+    assert_error(
+        env.try_send(&[rename(&delegate.pubkey(), "stale")], &[&delegate]),
+        ErrorCode::Unauthorized,
+    );
+
+    let creator = env.creator.insecure_clone();
+    let before = env.lamports(&new_owner.pubkey());
+    let ix = request(&env, &new_owner.pubkey());
+    env.send(&[ix], &[&creator]);
+    assert_eq!(env.lamports(&new_owner.pubkey()), before + MODEL_COST);
+}
@@ -0,0 +1,173 @@
+//! The paged model discovery index.
+
+mod common;
+
+use common::*;
+use project_silence::instruction as args;
+use project_silence::{ErrorCode, MODEL_INDEX_PAGE_SIZE};
+use project_silence_client::accounts::{ModelDirectory, ModelIndex, ModelListing, ModelMetadata, ModelSummary};
+use project_silence_client::discovery::{index_pages, ModelFilter};
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::{instructions, pda};
+use solana_sdk::account::Account;
+use solana_sdk::signature::Signer;
+
+fn register(env: &mut Env, model_id: u64, update: impl FnOnce(&mut args::RegisterModel)) -> Vec<ProgramEvent> {
+    let mut args = model_args(model_id);
+    update(&mut args);
+    let owner = env.owner.pubkey();
+    let page = env.next_index_page();
+    env.send(&[instructions::register_model(&owner, page, args)], &[])
+}
+
+fn pages(env: &Env) -> Vec<ModelIndex> {
+    let directory: ModelDirectory = env.account(&pda::model_directory());
+    index_pages(&directory).iter().map(|page| env.account(page)).collect()
+}
+
+/// Drop the discovery accounts, as for a model registered before the index
+fn unlist(env: &mut Env) {
+    for address in [
+        pda::model_directory(),
+        pda::model_index(0),
+        pda::model_listing(MODEL_ID),
+    ] {
+        env.svm.set_account(address, Account::default()).unwrap();
+    }
+}
+
+#[test]
+fn registering_lists_the_model() {
+    let mut env = Env::new();
+    let directory: ModelDirectory = env.account(&pda::model_directory());
+    assert_eq!(directory.model_count, 1);
+    let listing: ModelListing = env.account(&pda::model_listing(MODEL_ID));
+    assert_eq!((listing.model_id, listing.page), (MODEL_ID, 0));
+
+    let index: ModelIndex = env.account(&pda::model_index(0));
+    assert_eq!(index.page, 0);
+    assert_eq!(
+        index.entries,
+        vec![ModelSummary {
+            model_id: MODEL_ID,
+            model_type: 0,
+            tee_required: true,
+            is_active: true,
+            cost_per_inference: MODEL_COST,
+        }]
+    );
+
+    let events = register(&mut env, MODEL_ID + 1, |_| {});
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::ModelListed(event), ProgramEvent::ModelRegistered(_)]
+            if (event.model_id, event.page) == (MODEL_ID + 1, 0)
+    ));
+}
+
+#[test]
+fn full_pages_roll_over() {
+    let mut env = Env::new();
+    let first = MODEL_ID + 1;
+    for model_id in first..first + MODEL_INDEX_PAGE_SIZE as u64 {
+        register(&mut env, model_id, |_| {});
+    }
+
+    let pages = pages(&env);
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0].entries.len(), MODEL_INDEX_PAGE_SIZE);
+    let last = first + MODEL_INDEX_PAGE_SIZE as u64 - 1;
+    assert_eq!(pages[1].page, 1);
+    assert_eq!(pages[1].entries.len(), 1);
+    assert_eq!(pages[1].entries[0].model_id, last);
+    assert_eq!(env.account::<ModelListing>(&pda::model_listing(last)).page, 1);
+}
+
+#[test]
+fn updates_refresh_the_summary() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let ix = instructions::update_model(
+        &owner,
+        MODEL_ID,
+        Some(0),
+        args::UpdateModel {
+            name: None,
+            description: None,
+            cost_per_inference: Some(2 * MODEL_COST),
+            is_active: Some(false),
+        },
+    );
+    env.send(&[ix], &[]);
+
+    let entry = &env.account::<ModelIndex>(&pda::model_index(0)).entries[0];
+    assert_eq!(entry.cost_per_inference, 2 * MODEL_COST);
+    assert!(!entry.is_active);
+}
+
+#[test]
+fn unlisted_models_are_indexed_once() {
+    let mut env = Env::new();
+    unlist(&mut env);
+    let payer = env.funded(SOL);
+
+    // Unlisted models still update, without an index page
+    let owner = env.owner.pubkey();
+    let update = |index_page| {
+        let args = args::UpdateModel {
+            name: None,
+            description: None,
+            cost_per_inference: None,
+            is_active: Some(false),
+        };
+        instructions::update_model(&owner, MODEL_ID, index_page, args)
+    };
+    let (unlisted, listed) = (update(None), update(Some(0)));
+    env.send(&[unlisted.clone()], &[]);
+    assert!(!env.account::<ModelMetadata>(&pda::model(MODEL_ID)).is_active);
+
+    let events = env.send(&[instructions::index_model(&payer.pubkey(), MODEL_ID, 0)], &[&payer]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::ModelListed(event)] if (event.model_id, event.page) == (MODEL_ID, 0)
+    ));
+    assert_eq!(env.account::<ModelDirectory>(&pda::model_directory()).model_count, 1);
+    // Once listed, updates must keep the index page in sync
+    assert_error(env.try_send(&[unlisted], &[]), ErrorCode::InvalidModelIndex);
+    env.send(&[listed], &[]);
+    assert!(!env.account::<ModelIndex>(&pda::model_index(0)).entries[0].is_active);
+
+    let again = instructions::index_model(&payer.pubkey(), MODEL_ID, 0);
+    assert!(env.try_send(&[again], &[&payer]).is_err());
+    assert_eq!(env.account::<ModelIndex>(&pda::model_index(0)).entries.len(), 1);
+}
+
+#[test]
+fn filters_select_listed_models() {
+    let mut env = Env::new();
+    register(&mut env, MODEL_ID + 1, |m| {
+        m.model_type = 1;
+        m.tee_required = false;
+    });
+    register(&mut env, MODEL_ID + 2, |m| m.cost_per_inference = 5 * MODEL_COST);
+
+    let pages = pages(&env);
+    let ids = |filter: ModelFilter| filter.apply(&pages).map(|model| model.model_id).collect::<Vec<_>>();
+    assert_eq!(ids(ModelFilter::default()), [MODEL_ID, MODEL_ID + 1, MODEL_ID + 2]);
+    let tee = ModelFilter {
+        tee_required: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(ids(tee), [MODEL_ID, MODEL_ID + 2]);
+    let cheap_text = ModelFilter {
+        model_type: Some(0),
+        max_cost: Some(MODEL_COST),
+        ..Default::default()
+    };
+    assert_eq!(ids(cheap_text), [MODEL_ID]);
+    let pricey = ModelFilter {
+        min_cost: Some(2 * MODEL_COST),
+        ..Default::default()
+    };
+    assert_eq!(ids(pricey), [MODEL_ID + 2]);
+}
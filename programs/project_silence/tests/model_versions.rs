@@ -0,0 +1,204 @@
+//! Published model versions and requests pinned to them.
+
+mod common;
+
+use common::*;
+use project_silence::ErrorCode;
+use project_silence_client::accounts::{BatchInference, InferenceRequest, ModelMetadata, ModelVersion, VersionPin};
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::instructions::Payment;
+use project_silence_client::{instructions, pda};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signer;
+
+const VERSION: &str = "2.0.0";
+
+fn publish(env: &mut Env, version: &str) -> Vec<ProgramEvent> {
+    let owner = env.owner.pubkey();
+    env.send(
+        &[instructions::publish_model_version(
+            &owner, MODEL_ID, version, [1; 32], [2; 32],
+        )],
+        &[],
+    )
+}
+
+fn pinned_request(env: &Env, request_id: u64, version: &str) -> Instruction {
+    instructions::create_inference_request(
+        &env.creator.pubkey(),
+        MODEL_ID,
+        &env.owner.pubkey(),
+        version,
+        Payment::Lamports,
+        request_id,
+        request_args(),
+    )
+}
+
+#[test]
+fn publishing_records_an_immutable_release() {
+    let mut env = Env::new();
+    match publish(&mut env, VERSION).as_slice() {
+        [ProgramEvent::ModelVersionPublished(event)] => {
+            assert_eq!((event.model_id, event.version.as_str()), (MODEL_ID, VERSION));
+            assert_eq!((event.weights_commitment, event.changelog_hash), ([1; 32], [2; 32]));
+        }
+        _ => panic!("expected one ModelVersionPublished event"),
+    }
+
+    let release: ModelVersion = env.account(&pda::model_version(MODEL_ID, VERSION));
+    assert_eq!(release.model_id, MODEL_ID);
+    assert_eq!(release.version, VERSION);
+    assert_eq!(release.weights_commitment, [1; 32]);
+    assert_eq!(release.changelog_hash, [2; 32]);
+    assert_eq!(release.publisher, env.owner.pubkey());
+    assert_eq!(release.deprecated_at, None);
+    assert_eq!(env.account::<ModelMetadata>(&pda::model(MODEL_ID)).version, VERSION);
+
+    // A published version cannot be overwritten
+    let owner = env.owner.pubkey();
+    let again = instructions::publish_model_version(&owner, MODEL_ID, VERSION, [3; 32], [4; 32]);
+    assert!(env.try_send(&[again], &[]).is_err());
+    let release: ModelVersion = env.account(&pda::model_version(MODEL_ID, VERSION));
+    assert_eq!(release.weights_commitment, [1; 32]);
+}
+
+#[test]
+fn only_managers_publish_and_deprecate() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let delegate = env.funded(SOL);
+    let stranger = env.funded(SOL);
+    env.send(
+        &[instructions::set_model_delegates(
+            &owner,
+            MODEL_ID,
+            vec![delegate.pubkey()],
+        )],
+        &[],
+    );
+
+    let ix = instructions::publish_model_version(&stranger.pubkey(), MODEL_ID, VERSION, [1; 32], [2; 32]);
+    assert_error(env.try_send(&[ix], &[&stranger]), ErrorCode::Unauthorized);
+
+    let ix = instructions::publish_model_version(&delegate.pubkey(), MODEL_ID, VERSION, [1; 32], [2; 32]);
+    env.send(&[ix], &[&delegate]);
+    let release: ModelVersion = env.account(&pda::model_version(MODEL_ID, VERSION));
+    assert_eq!(release.publisher, delegate.pubkey());
+
+    let ix = instructions::deprecate_model_version(&stranger.pubkey(), MODEL_ID, VERSION);
+    assert_error(env.try_send(&[ix], &[&stranger]), ErrorCode::Unauthorized);
+    let ix = instructions::deprecate_model_version(&delegate.pubkey(), MODEL_ID, VERSION);
+    env.send(&[ix], &[&delegate]);
+}
+
+#[test]
+fn requests_and_batches_pin_the_version() {
+    let mut env = Env::new();
+    publish(&mut env, VERSION);
+    let creator = env.creator.insecure_clone();
+    let release = pda::model_version(MODEL_ID, VERSION);
+
+    let ix = pinned_request(&env, 0, VERSION);
+    env.send(&[ix], &[&creator]);
+    let request: InferenceRequest = env.account(&pda::request(&creator.pubkey(), 0));
+    assert_eq!(request.model_version, VersionPin(Some(release)));
+
+    let owner = env.owner.pubkey();
+    let ix = instructions::create_batch_inference(
+        &creator.pubkey(),
+        MODEL_ID,
+        &owner,
+        VERSION,
+        Payment::Lamports,
+        0,
+        batch_args(2),
+    );
+    env.send(&[ix], &[&creator]);
+    let batch: BatchInference = env.account(&pda::batch(&creator.pubkey(), 0));
+    assert_eq!(batch.model_version, VersionPin(Some(release)));
+
+    // Publishing a newer version does not move existing pins
+    publish(&mut env, "2.1.0");
+    let newer = env.create_request();
+    assert_eq!(
+        env.account::<InferenceRequest>(&newer).model_version,
+        VersionPin(Some(pda::model_version(MODEL_ID, "2.1.0")))
+    );
+    let request: InferenceRequest = env.account(&pda::request(&creator.pubkey(), 0));
+    assert_eq!(request.model_version, VersionPin(Some(release)));
+}
+
+#[test]
+fn requests_pin_the_current_version_once_published() {
+    let mut env = Env::new();
+    let creator = env.creator.insecure_clone();
+
+    // The registered version was never published, so there is nothing to pin
+    let unpinned = env.create_request();
+    assert_eq!(
+        env.account::<InferenceRequest>(&unpinned).model_version,
+        VersionPin(None)
+    );
+    // ...but only for the current version, not any unpublished address
+    let ix = pinned_request(&env, 1, "0.9.0");
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::InvalidModelVersion);
+
+    publish(&mut env, VERSION);
+    let ix = pinned_request(&env, 1, MODEL_VERSION);
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::InvalidModelVersion);
+    let pinned = env.create_request();
+    assert_eq!(
+        env.account::<InferenceRequest>(&pinned).model_version,
+        VersionPin(Some(pda::model_version(MODEL_ID, VERSION)))
+    );
+}
+
+#[test]
+fn deprecated_versions_take_no_new_requests() {
+    let mut env = Env::new();
+    publish(&mut env, VERSION);
+    let creator = env.creator.insecure_clone();
+    let ix = pinned_request(&env, 0, VERSION);
+    env.send(&[ix], &[&creator]);
+
+    let owner = env.owner.pubkey();
+    let events = env.send(&[instructions::deprecate_model_version(&owner, MODEL_ID, VERSION)], &[]);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::ModelVersionDeprecated(event)] if event.version == VERSION
+    ));
+    let release: ModelVersion = env.account(&pda::model_version(MODEL_ID, VERSION));
+    assert!(release.deprecated_at.is_some());
+
+    let ix = pinned_request(&env, 1, VERSION);
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::DeprecatedModelVersion);
+    let request: InferenceRequest = env.account(&pda::request(&creator.pubkey(), 0));
+    assert_eq!(
+        request.model_version,
+        VersionPin(Some(pda::model_version(MODEL_ID, VERSION)))
+    );
+}
+
+#[test]
+fn versions_of_other_models_are_rejected() {
+    let mut env = Env::new();
+    let owner = env.owner.pubkey();
+    let other = MODEL_ID + 1;
+    let page = env.next_index_page();
+    env.send(&[instructions::register_model(&owner, page, model_args(other))], &[]);
+    env.send(
+        &[instructions::publish_model_version(
+            &owner, other, VERSION, [1; 32], [2; 32],
+        )],
+        &[],
+    );
+
+    let creator = env.creator.insecure_clone();
+    let ix = replace_account(
+        pinned_request(&env, 0, VERSION),
+        &pda::model_version(MODEL_ID, VERSION),
+        pda::model_version(other, VERSION),
+    );
+    assert_error(env.try_send(&[ix], &[&creator]), ErrorCode::InvalidModelVersion);
+}
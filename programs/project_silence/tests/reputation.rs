@@ -0,0 +1,98 @@
+//! Time-weighted solver reputation and its decay.
+
+mod common;
+
+use common::*;
+use project_silence::ReputationParams;
+use project_silence_client::accounts::Solver;
+use project_silence_client::events::ProgramEvent;
+use project_silence_client::{instructions, pda};
+use solana_sdk::clock::Clock;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+const HOUR: i64 = 3_600;
+const DAY: i64 = 86_400;
+
+fn solver(env: &Env, authority: &Pubkey) -> Solver {
+    env.account(&pda::solver(authority))
+}
+
+fn now(env: &Env) -> i64 {
+    env.svm.get_sysvar::<Clock>().unix_timestamp
+}
+
+#[test]
+fn decay_halves_every_half_life() {
+    let params = ReputationParams::default();
+    let half_life = params.half_life_secs;
+    assert_eq!(params.decay(1000, 0), 1000);
+    assert_eq!(params.decay(1000, half_life / 2), 750);
+    assert_eq!(params.decay(1000, half_life), 500);
+    assert_eq!(params.decay(1000, 3 * half_life), 125);
+    assert_eq!(params.decay(1000, 40 * half_life), 0);
+
+    let no_decay = ReputationParams {
+        half_life_secs: 0,
+        ..ReputationParams::default()
+    };
+    assert_eq!(no_decay.decay(1000, 40 * half_life), 1000);
+}
+
+#[test]
+fn decay_applies_only_the_time_that_cost_points() {
+    let params = ReputationParams::default();
+    let half_life = params.half_life_secs;
+    // At 100 points a point drops every 2 * half_life / 100 seconds
+    let per_point = 2 * half_life / 100;
+    assert_eq!(params.decay_applied(100, HOUR), (100, 0));
+    assert_eq!(params.decay_applied(100, per_point), (99, per_point));
+    assert_eq!(params.decay_applied(100, per_point + HOUR), (99, per_point));
+    assert_eq!(params.decay_applied(100, half_life + HOUR), (50, half_life));
+    assert_eq!(params.decay_applied(1, 40 * half_life), (0, 40 * half_life));
+}
+
+#[test]
+fn frequent_refreshes_decay_like_one_refresh() {
+    let mut env = Env::new();
+    let (frequent, idle) = (env.solver.pubkey(), env.solver2.pubkey());
+    for _ in 0..(45 * DAY / (3 * HOUR)) {
+        env.warp(3 * HOUR);
+        env.send(&[instructions::refresh_reputation(&frequent)], &[]);
+    }
+    let events = env.send(&[instructions::refresh_reputation(&idle)], &[]);
+
+    let params = ReputationParams::default();
+    let expected = params.decay(100, 45 * DAY);
+    assert_eq!(expected, 38);
+    assert!(matches!(
+        events.as_slice(),
+        [ProgramEvent::ReputationRefreshed(event)] if event.reputation_score == expected
+    ));
+    let now = now(&env);
+    for authority in [frequent, idle] {
+        let solver = solver(&env, &authority);
+        assert_eq!(solver.current_reputation(&params, now), expected);
+        // The whole half-life is stored; the rest stays interpolated
+        assert_eq!(solver.reputation_score, 50);
+    }
+}
+
+#[test]
+fn fills_keep_decay_time_that_cost_nothing() {
+    let mut env = Env::new();
+    let authority = env.solver.insecure_clone();
+    let updated_at = solver(&env, &authority.pubkey()).reputation_updated_at;
+
+    // Ten idle hours cost no point at 100, so they keep counting after the fill
+    env.warp(10 * HOUR);
+    env.send(&[instructions::solver_heartbeat(&authority.pubkey())], &[&authority]);
+    let intent_id = env.create_intent(SOL, false);
+    env.match_and_execute(&authority, intent_id, SOL);
+    env.warp(FINALITY_SECS as i64);
+    env.send(&[env.settle_ix(&authority.pubkey(), intent_id)], &[]);
+
+    let solver = solver(&env, &authority.pubkey());
+    assert_eq!(solver.reputation_score, 101);
+    assert_eq!(solver.reputation_updated_at, updated_at);
+}
@@ -0,0 +1,15 @@
+//! Program account types and decoding.
+
+use anchor_lang::{AccountDeserialize, Result};
+
+pub use project_silence::{
+    AccountVersion, BatchInference, BridgeConfig, ChainConfig, CreditAccount, FeeVault, FillStatus, InferenceRequest,
+    Intent, IntentFill, IntentStatus, ModelAccess, ModelDirectory, ModelIndex, ModelListing, ModelMetadata,
+    ModelOperators, ModelSummary, ModelVersion, PreviousVolume, ReceiptAttestation, ReceiptRecipient, RelayerSet,
+    ReputationParams, Solver, Subscription, SubscriptionPlan, UserMetrics, VersionPin, Versioned, VolumeWindow,
+};
+
+/// Decode account data, checking the discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
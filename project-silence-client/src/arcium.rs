@@ -0,0 +1,63 @@
+//! Arcium accounts used by the computation definition and queue instructions.
+//!
+//! Addresses come from the same `arcium_anchor` derivations the program
+//! checks them against. The cluster is not derivable without reading the
+//! MXE account, so callers pass it in (see `getArciumEnv` in the TS tests).
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Id;
+use arcium_anchor::prelude::*;
+
+#[allow(unused_imports)]
+use crate::{ID, ID_CONST};
+
+pub const COMP_DEF_OFFSET_PROCESS_INFERENCE: u32 = comp_def_offset("process_inference");
+pub const COMP_DEF_OFFSET_VERIFY_INTENT_AMOUNTS: u32 = comp_def_offset("verify_intent_amounts");
+pub const COMP_DEF_OFFSET_COMPUTE_SETTLEMENT: u32 = comp_def_offset("compute_settlement");
+pub const COMP_DEF_OFFSET_CALCULATE_REPUTATION: u32 = comp_def_offset("calculate_reputation");
+pub const COMP_DEF_OFFSET_VERIFY_ATTESTATION: u32 = comp_def_offset("verify_attestation");
+pub const COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF: u32 = comp_def_offset("generate_privacy_proof");
+
+pub fn arcium_program() -> Pubkey {
+    Arcium::id()
+}
+
+/// Program signer PDA used when queueing computations
+pub fn sign_pda() -> Pubkey {
+    derive_sign_pda!()
+}
+
+pub fn mxe_account() -> Pubkey {
+    derive_mxe_pda!()
+}
+
+pub fn mempool_account() -> Pubkey {
+    derive_mempool_pda!()
+}
+
+pub fn executing_pool() -> Pubkey {
+    derive_execpool_pda!()
+}
+
+pub fn computation_account(computation_offset: u64) -> Pubkey {
+    derive_comp_pda!(computation_offset)
+}
+
+pub fn comp_def_account(comp_def_offset: u32) -> Pubkey {
+    derive_comp_def_pda!(comp_def_offset)
+}
+
+pub fn fee_pool_account() -> Pubkey {
+    ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+}
+
+pub fn clock_account() -> Pubkey {
+    ARCIUM_CLOCK_ACCOUNT_ADDRESS
+}
+
+/// Accounts shared by every queue-computation instruction
+pub struct QueueAccounts {
+    pub payer: Pubkey,
+    /// Arcium cluster assigned to the MXE
+    pub cluster: Pubkey,
+}
@@ -0,0 +1,45 @@
+//! Listing models from the `ModelIndex` pages.
+//!
+//! Fetch the `ModelDirectory`, then every page from [`index_pages`], and
+//! filter the summaries locally with a [`ModelFilter`].
+
+use anchor_lang::prelude::Pubkey;
+use project_silence::{ModelDirectory, ModelIndex, ModelSummary, MODEL_INDEX_PAGE_SIZE};
+
+use crate::pda;
+
+/// Addresses of every index page in use
+pub fn index_pages(directory: &ModelDirectory) -> Vec<Pubkey> {
+    let pages = directory.model_count.div_ceil(MODEL_INDEX_PAGE_SIZE as u64) as u32;
+    (0..pages).map(pda::model_index).collect()
+}
+
+/// Criteria for listed models; `None` fields match everything
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModelFilter {
+    pub model_type: Option<u8>,
+    pub tee_required: Option<bool>,
+    pub is_active: Option<bool>,
+    /// Lowest `cost_per_inference`, inclusive
+    pub min_cost: Option<u64>,
+    /// Highest `cost_per_inference`, inclusive
+    pub max_cost: Option<u64>,
+}
+
+impl ModelFilter {
+    pub fn matches(&self, model: &ModelSummary) -> bool {
+        self.model_type.is_none_or(|model_type| model.model_type == model_type)
+            && self.tee_required.is_none_or(|tee| model.tee_required == tee)
+            && self.is_active.is_none_or(|active| model.is_active == active)
+            && self.min_cost.is_none_or(|min| model.cost_per_inference >= min)
+            && self.max_cost.is_none_or(|max| model.cost_per_inference <= max)
+    }
+
+    /// Matching models of `pages`, in listing order
+    pub fn apply<'a>(&'a self, pages: &'a [ModelIndex]) -> impl Iterator<Item = &'a ModelSummary> + 'a {
+        pages
+            .iter()
+            .flat_map(|page| &page.entries)
+            .filter(move |model| self.matches(model))
+    }
+}
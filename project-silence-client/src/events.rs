@@ -0,0 +1,151 @@
+//! Decoding of the events the program emits with `emit!`.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+
+use crate::ID;
+
+pub use project_silence::{
+    AccountMigrated, BatchClosed, BatchInferenceCreated, BatchResultsRecorded, BridgeInitialized, BridgePauseUpdated,
+    ChainRegistered, ChainStatusUpdated, ChainUpdated, CreditEarningsClaimed, CreditsToppedUp, CreditsWithdrawn,
+    ExecutionWindowUpdated, FeeSplitUpdated, FeesWithdrawn, GuardianUpdated, HeartbeatTimeoutUpdated,
+    InferenceProcessed, InferenceRequestClosed, InferenceRequestCreated, InferenceResultStored, IntentAmountsVerified,
+    IntentCancelled, IntentClosed, IntentCreated, IntentExecuted, IntentFailed, IntentMatched, IntentSettled,
+    IntentTimedOut, IntentsBatchSettled, ModelDelegatesUpdated, ModelListed, ModelOperatorsUpdated,
+    ModelOwnershipTransferStarted, ModelOwnershipTransferred, ModelPayoutUpdated, ModelRegistered, ModelUpdated,
+    ModelVersionDeprecated, ModelVersionPublished, PrivacyProofGenerated, ProtocolFeeUpdated, RateLimitsUpdated,
+    ReceiptAttested, ReceiptClosed, RelayersUpdated, ReputationParamsUpdated, ReputationRefreshed, SolverDeactivated,
+    SolverHeartbeatRecorded, SolverReactivated, SolverRegistered, SolverRewardsClaimed, SolverUpdated, Subscribed,
+    SubscriptionPlanUpdated, VolumeCapReached,
+};
+
+macro_rules! program_events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event emitted by the program
+        pub enum ProgramEvent {
+            $($name($name),)*
+        }
+
+        impl ProgramEvent {
+            /// Decode discriminator-prefixed event data; `None` for unknown or malformed events
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                if data.len() < 8 {
+                    return None;
+                }
+                let (discriminator, mut body) = data.split_at(8);
+                $(
+                    if discriminator == <$name as Discriminator>::DISCRIMINATOR {
+                        return $name::deserialize(&mut body).ok().map(ProgramEvent::$name);
+                    }
+                )*
+                None
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(ProgramEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+program_events!(
+    BridgeInitialized,
+    ModelRegistered,
+    ModelUpdated,
+    ModelOwnershipTransferStarted,
+    ModelOwnershipTransferred,
+    ModelDelegatesUpdated,
+    ModelOperatorsUpdated,
+    ModelPayoutUpdated,
+    ModelVersionPublished,
+    ModelVersionDeprecated,
+    ModelListed,
+    InferenceRequestCreated,
+    InferenceProcessed,
+    InferenceResultStored,
+    InferenceRequestClosed,
+    BatchResultsRecorded,
+    BatchClosed,
+    BatchInferenceCreated,
+    CreditsToppedUp,
+    CreditsWithdrawn,
+    CreditEarningsClaimed,
+    SubscriptionPlanUpdated,
+    Subscribed,
+    SolverRegistered,
+    SolverDeactivated,
+    SolverReactivated,
+    SolverUpdated,
+    SolverHeartbeatRecorded,
+    HeartbeatTimeoutUpdated,
+    ReputationParamsUpdated,
+    ReputationRefreshed,
+    IntentCreated,
+    IntentMatched,
+    IntentExecuted,
+    IntentSettled,
+    IntentsBatchSettled,
+    IntentFailed,
+    IntentTimedOut,
+    ExecutionWindowUpdated,
+    IntentCancelled,
+    IntentClosed,
+    IntentAmountsVerified,
+    PrivacyProofGenerated,
+    ProtocolFeeUpdated,
+    FeeSplitUpdated,
+    FeesWithdrawn,
+    SolverRewardsClaimed,
+    GuardianUpdated,
+    BridgePauseUpdated,
+    ChainStatusUpdated,
+    RelayersUpdated,
+    ReceiptAttested,
+    ReceiptClosed,
+    RateLimitsUpdated,
+    ChainRegistered,
+    ChainUpdated,
+    VolumeCapReached,
+    AccountMigrated,
+);
+
+/// Raw event data from this program's `Program data:` log lines.
+///
+/// Lines are attributed to the innermost invoked program, so events logged
+/// by other programs in the same transaction are ignored.
+pub fn program_data<S: AsRef<str>>(logs: &[S]) -> Vec<Vec<u8>> {
+    let program_id = ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut data = Vec::new();
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(encoded) = line.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(encoded) {
+                data.push(bytes);
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (Some(id), Some(action)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match action {
+                "invoke" => stack.push(id),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    data
+}
+
+/// Decode this program's events from transaction log messages
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ProgramEvent> {
+    program_data(logs).iter().filter_map(|data| ProgramEvent::decode(data)).collect()
+}
@@ -0,0 +1,954 @@
+//! Instruction builders for every instruction a client sends.
+//!
+//! PDAs are derived here; ids the program reads from on-chain state
+//! (`BridgeConfig.total_intents`, `UserMetrics.next_request_id`, ...) are
+//! passed in by the caller. Builders with many arguments take the program's
+//! generated `instruction::*` struct instead of positional parameters.
+//!
+//! The `*_callback` instructions are invoked by the Arcium cluster and have
+//! no builder.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use project_silence::{accounts, instruction, ReputationParams};
+
+use crate::arcium::{self, QueueAccounts};
+use crate::{pda, ID};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// ---- Bridge setup and administration ----
+
+pub fn initialize_bridge(owner: &Pubkey, min_solver_stake: u64, protocol_fee_bps: u16) -> Instruction {
+    build(
+        accounts::InitializeBridge {
+            owner: *owner,
+            config: pda::bridge_config(),
+            fee_vault: pda::fee_vault(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeBridge {
+            min_solver_stake,
+            protocol_fee_bps,
+        },
+    )
+}
+
+fn admin(owner: &Pubkey) -> accounts::AdminConfig {
+    accounts::AdminConfig {
+        owner: *owner,
+        config: pda::bridge_config(),
+    }
+}
+
+pub fn set_protocol_fee(owner: &Pubkey, fee_bps: u16) -> Instruction {
+    build(admin(owner), instruction::SetProtocolFee { fee_bps })
+}
+
+pub fn set_heartbeat_timeout(owner: &Pubkey, timeout_secs: i64) -> Instruction {
+    build(admin(owner), instruction::SetHeartbeatTimeout { timeout_secs })
+}
+
+pub fn set_reputation_params(owner: &Pubkey, params: ReputationParams) -> Instruction {
+    build(admin(owner), instruction::SetReputationParams { params })
+}
+
+pub fn set_execution_window(owner: &Pubkey, default_execution_secs: i64, max_execution_secs: i64) -> Instruction {
+    build(
+        admin(owner),
+        instruction::SetExecutionWindow {
+            default_execution_secs,
+            max_execution_secs,
+        },
+    )
+}
+
+pub fn set_fee_split(owner: &Pubkey, staker_fee_share_bps: u16) -> Instruction {
+    build(admin(owner), instruction::SetFeeSplit { staker_fee_share_bps })
+}
+
+pub fn set_guardian(owner: &Pubkey, guardian: Pubkey) -> Instruction {
+    build(admin(owner), instruction::SetGuardian { guardian })
+}
+
+pub fn set_rate_limits(owner: &Pubkey, window_secs: i64, solver_volume_cap: u64) -> Instruction {
+    build(
+        admin(owner),
+        instruction::SetRateLimits {
+            window_secs,
+            solver_volume_cap,
+        },
+    )
+}
+
+/// `authority` is the owner or the guardian
+pub fn set_paused(authority: &Pubkey, paused: bool) -> Instruction {
+    build(
+        accounts::GuardianConfig {
+            authority: *authority,
+            config: pda::bridge_config(),
+        },
+        instruction::SetPaused { paused },
+    )
+}
+
+pub fn withdraw_fees(owner: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawFees {
+            owner: *owner,
+            config: pda::bridge_config(),
+            fee_vault: pda::fee_vault(),
+            recipient: *recipient,
+        },
+        instruction::WithdrawFees { amount },
+    )
+}
+
+pub fn set_relayers(owner: &Pubkey, relayers: Vec<Pubkey>, threshold: u8) -> Instruction {
+    build(
+        accounts::SetRelayers {
+            owner: *owner,
+            config: pda::bridge_config(),
+            relayer_set: pda::relayer_set(),
+            system_program: system_program::ID,
+        },
+        instruction::SetRelayers { relayers, threshold },
+    )
+}
+
+/// `args.receipt_hash` is the `FulfillmentReceipt::hash` of the other fields
+pub fn attest_receipt(relayer: &Pubkey, args: instruction::AttestReceipt) -> Instruction {
+    build(
+        accounts::AttestReceipt {
+            relayer: *relayer,
+            relayer_set: pda::relayer_set(),
+            receipt: pda::receipt(&args.receipt_hash),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// `relayer` is the first attester of the receipt
+pub fn close_receipt(relayer: &Pubkey, receipt_hash: &[u8; 32]) -> Instruction {
+    build(
+        accounts::CloseReceipt {
+            relayer: *relayer,
+            receipt: pda::receipt(receipt_hash),
+            config: pda::bridge_config(),
+        },
+        instruction::CloseReceipt {},
+    )
+}
+
+// ---- Destination chains ----
+
+pub fn register_chain(owner: &Pubkey, args: instruction::RegisterChain) -> Instruction {
+    build(
+        accounts::RegisterChain {
+            owner: *owner,
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(args.chain_id),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn update_chain(owner: &Pubkey, chain_id: u32, args: instruction::UpdateChain) -> Instruction {
+    build(
+        accounts::UpdateChain {
+            owner: *owner,
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(chain_id),
+        },
+        args,
+    )
+}
+
+/// `authority` is the owner, or the guardian when disabling
+pub fn set_chain_enabled(authority: &Pubkey, chain_id: u32, enabled: bool) -> Instruction {
+    build(
+        accounts::SetChainEnabled {
+            authority: *authority,
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(chain_id),
+        },
+        instruction::SetChainEnabled { enabled },
+    )
+}
+
+// ---- Models and inference ----
+
+/// `index_page` is `ModelDirectory::next_page` (0 before the first model)
+pub fn register_model(owner: &Pubkey, index_page: u32, args: instruction::RegisterModel) -> Instruction {
+    build(
+        accounts::RegisterModel {
+            owner: *owner,
+            model: pda::model(args.model_id),
+            directory: pda::model_directory(),
+            model_index: pda::model_index(index_page),
+            model_listing: pda::model_listing(args.model_id),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// List a model registered before the discovery index; `index_page` as in [`register_model`]
+pub fn index_model(payer: &Pubkey, model_id: u64, index_page: u32) -> Instruction {
+    build(
+        accounts::IndexModel {
+            payer: *payer,
+            model: pda::model(model_id),
+            directory: pda::model_directory(),
+            model_index: pda::model_index(index_page),
+            model_listing: pda::model_listing(model_id),
+            system_program: system_program::ID,
+        },
+        instruction::IndexModel {},
+    )
+}
+
+/// `authority` is the model owner or one of its delegates; `index_page` is the model's
+/// `ModelListing.page`, `None` while the model is not indexed
+pub fn update_model(
+    authority: &Pubkey,
+    model_id: u64,
+    index_page: Option<u32>,
+    args: instruction::UpdateModel,
+) -> Instruction {
+    build(
+        accounts::UpdateModel {
+            authority: *authority,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_listing: pda::model_listing(model_id),
+            model_index: index_page.map(pda::model_index),
+        },
+        args,
+    )
+}
+
+/// `authority` is the model owner or one of its delegates
+pub fn publish_model_version(
+    authority: &Pubkey,
+    model_id: u64,
+    version: &str,
+    weights_commitment: [u8; 32],
+    changelog_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::PublishModelVersion {
+            authority: *authority,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_version: pda::model_version(model_id, version),
+            system_program: system_program::ID,
+        },
+        instruction::PublishModelVersion {
+            version: version.to_string(),
+            weights_commitment,
+            changelog_hash,
+        },
+    )
+}
+
+pub fn deprecate_model_version(authority: &Pubkey, model_id: u64, version: &str) -> Instruction {
+    build(
+        accounts::DeprecateModelVersion {
+            authority: *authority,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_version: pda::model_version(model_id, version),
+        },
+        instruction::DeprecateModelVersion {},
+    )
+}
+
+fn manage_model(owner: &Pubkey, model_id: u64) -> accounts::ManageModel {
+    accounts::ManageModel {
+        owner: *owner,
+        model: pda::model(model_id),
+        model_access: pda::model_access(model_id),
+        system_program: system_program::ID,
+    }
+}
+
+/// `new_owner: None` cancels a pending transfer
+pub fn transfer_model_ownership(owner: &Pubkey, model_id: u64, new_owner: Option<Pubkey>) -> Instruction {
+    build(
+        manage_model(owner, model_id),
+        instruction::TransferModelOwnership { new_owner },
+    )
+}
+
+pub fn accept_model_ownership(new_owner: &Pubkey, model_id: u64) -> Instruction {
+    build(
+        accounts::AcceptModelOwnership {
+            new_owner: *new_owner,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+        },
+        instruction::AcceptModelOwnership {},
+    )
+}
+
+pub fn set_model_delegates(owner: &Pubkey, model_id: u64, delegates: Vec<Pubkey>) -> Instruction {
+    build(
+        manage_model(owner, model_id),
+        instruction::SetModelDelegates { delegates },
+    )
+}
+
+/// `authority` is the model owner or one of its delegates
+pub fn set_model_operators(authority: &Pubkey, model_id: u64, operators: Vec<Pubkey>) -> Instruction {
+    build(
+        accounts::SetModelOperators {
+            authority: *authority,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_operators: pda::model_operators(model_id),
+            system_program: system_program::ID,
+        },
+        instruction::SetModelOperators { operators },
+    )
+}
+
+/// `payout: None` pays the owner again
+pub fn set_model_payout(owner: &Pubkey, model_id: u64, payout: Option<Pubkey>) -> Instruction {
+    build(manage_model(owner, model_id), instruction::SetModelPayout { payout })
+}
+
+/// How [`create_inference_request`] and [`create_batch_inference`] pay for inferences
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Payment {
+    /// Transfer the cost to `model_owner`
+    Lamports,
+    /// Debit the user's credits with `model_owner`, the model's payout address
+    Credits,
+    /// Use the user's subscription to the model
+    Subscription,
+}
+
+impl Payment {
+    /// `credit_account` and `subscription` of the create instructions
+    fn accounts(self, user: &Pubkey, model_id: u64, model_owner: &Pubkey) -> (Option<Pubkey>, Option<Pubkey>) {
+        match self {
+            Payment::Lamports => (None, None),
+            Payment::Credits => (Some(pda::credit_account(user, model_owner)), None),
+            Payment::Subscription => (None, Some(pda::subscription(user, model_id))),
+        }
+    }
+}
+
+/// `request_id` is the user's current `UserMetrics.next_request_id` (0 before the first request);
+/// `model_owner` is the model's payout address (`ModelMetadata::payout`); `version` is the
+/// published model version to pin, normally the model's current `ModelMetadata.version`
+pub fn create_inference_request(
+    user: &Pubkey,
+    model_id: u64,
+    model_owner: &Pubkey,
+    version: &str,
+    payment: Payment,
+    request_id: u64,
+    args: instruction::CreateInferenceRequest,
+) -> Instruction {
+    let (credit_account, subscription) = payment.accounts(user, model_id, model_owner);
+    build(
+        accounts::CreateInferenceRequest {
+            user: *user,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_owner: *model_owner,
+            model_version: pda::model_version(model_id, version),
+            credit_account,
+            subscription,
+            user_metrics: pda::user_metrics(user),
+            request: pda::request(user, request_id),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn process_inference(
+    queue: &QueueAccounts,
+    computation_offset: u64,
+    encrypted_input: [u8; 32],
+    observer_pub_key: [u8; 32],
+    observer_nonce: u128,
+) -> Instruction {
+    build(
+        accounts::ProcessInference {
+            payer: queue.payer,
+            sign_pda_account: arcium::sign_pda(),
+            mxe_account: arcium::mxe_account(),
+            mempool_account: arcium::mempool_account(),
+            executing_pool: arcium::executing_pool(),
+            computation_account: arcium::computation_account(computation_offset),
+            comp_def_account: arcium::comp_def_account(arcium::COMP_DEF_OFFSET_PROCESS_INFERENCE),
+            cluster_account: queue.cluster,
+            pool_account: arcium::fee_pool_account(),
+            clock_account: arcium::clock_account(),
+            system_program: system_program::ID,
+            arcium_program: arcium::arcium_program(),
+        },
+        instruction::ProcessInference {
+            computation_offset,
+            encrypted_input,
+            observer_pub_key,
+            observer_nonce,
+        },
+    )
+}
+
+/// `request_user` is the `user` recorded on the request
+pub fn store_inference_result(
+    authority: &Pubkey,
+    request: &Pubkey,
+    request_user: &Pubkey,
+    result_hash: [u8; 32],
+    attestation_hash: [u8; 32],
+    verified: bool,
+) -> Instruction {
+    build(
+        accounts::StoreInferenceResult {
+            authority: *authority,
+            request: *request,
+            user_metrics: pda::user_metrics(request_user),
+        },
+        instruction::StoreInferenceResult {
+            result_hash,
+            attestation_hash,
+            verified,
+        },
+    )
+}
+
+pub fn close_inference_request(user: &Pubkey, request: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseInferenceRequest {
+            user: *user,
+            request: *request,
+        },
+        instruction::CloseInferenceRequest {},
+    )
+}
+
+/// `batch_id` is the user's current `UserMetrics.next_batch_id`; `model_owner`, `version` and
+/// `payment` as in [`create_inference_request`]
+pub fn create_batch_inference(
+    user: &Pubkey,
+    model_id: u64,
+    model_owner: &Pubkey,
+    version: &str,
+    payment: Payment,
+    batch_id: u64,
+    args: instruction::CreateBatchInference,
+) -> Instruction {
+    let (credit_account, subscription) = payment.accounts(user, model_id, model_owner);
+    build(
+        accounts::CreateBatchInference {
+            user: *user,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_owner: *model_owner,
+            model_version: pda::model_version(model_id, version),
+            credit_account,
+            subscription,
+            user_metrics: pda::user_metrics(user),
+            batch: pda::batch(user, batch_id),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+// ---- Credits and subscriptions ----
+
+/// Prepay `amount` lamports for models paying out to `provider` (`ModelMetadata::payout`)
+pub fn top_up_credits(user: &Pubkey, provider: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::TopUpCredits {
+            user: *user,
+            provider: *provider,
+            credit_account: pda::credit_account(user, provider),
+            system_program: system_program::ID,
+        },
+        instruction::TopUpCredits { amount },
+    )
+}
+
+pub fn withdraw_credits(user: &Pubkey, provider: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawCredits {
+            user: *user,
+            credit_account: pda::credit_account(user, provider),
+        },
+        instruction::WithdrawCredits { amount },
+    )
+}
+
+/// Claim what `user` has spent from their credits with `provider`
+pub fn claim_credit_earnings(provider: &Pubkey, user: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimCreditEarnings {
+            provider: *provider,
+            credit_account: pda::credit_account(user, provider),
+        },
+        instruction::ClaimCreditEarnings {},
+    )
+}
+
+/// `authority` is the model owner or one of its delegates
+pub fn set_subscription_plan(authority: &Pubkey, model_id: u64, args: instruction::SetSubscriptionPlan) -> Instruction {
+    build(
+        accounts::SetSubscriptionPlan {
+            authority: *authority,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            plan: pda::subscription_plan(model_id, args.plan_id),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// `model_owner` as in [`create_inference_request`]
+pub fn subscribe(user: &Pubkey, model_id: u64, model_owner: &Pubkey, plan_id: u8) -> Instruction {
+    build(
+        accounts::Subscribe {
+            user: *user,
+            model: pda::model(model_id),
+            model_access: pda::model_access(model_id),
+            model_owner: *model_owner,
+            plan: pda::subscription_plan(model_id, plan_id),
+            subscription: pda::subscription(user, model_id),
+            system_program: system_program::ID,
+        },
+        instruction::Subscribe {},
+    )
+}
+
+/// `authority` is one of the model's operators; `batch_user` and `model_id`
+/// are the `user` and `model_id` recorded on the batch
+pub fn record_batch_results(
+    authority: &Pubkey,
+    batch: &Pubkey,
+    batch_user: &Pubkey,
+    model_id: u64,
+    completed: u32,
+    failed: u32,
+) -> Instruction {
+    build(
+        accounts::RecordBatchResults {
+            authority: *authority,
+            batch: *batch,
+            model_operators: pda::model_operators(model_id),
+            user_metrics: pda::user_metrics(batch_user),
+        },
+        instruction::RecordBatchResults { completed, failed },
+    )
+}
+
+pub fn close_batch(user: &Pubkey, batch: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseBatch {
+            user: *user,
+            batch: *batch,
+        },
+        instruction::CloseBatch {},
+    )
+}
+
+// ---- Solvers ----
+
+pub fn register_solver(user: &Pubkey, supported_chains: Vec<u32>) -> Instruction {
+    build(
+        accounts::RegisterSolver {
+            user: *user,
+            config: pda::bridge_config(),
+            solver: pda::solver(user),
+            fee_vault: pda::fee_vault(),
+            system_program: system_program::ID,
+        },
+        instruction::RegisterSolver { supported_chains },
+    )
+}
+
+pub fn update_solver(solver_authority: &Pubkey, args: instruction::UpdateSolver) -> Instruction {
+    build(
+        accounts::UpdateSolver {
+            solver_authority: *solver_authority,
+            solver: pda::solver(solver_authority),
+        },
+        args,
+    )
+}
+
+pub fn solver_heartbeat(solver_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::SolverHeartbeat {
+            solver_authority: *solver_authority,
+            solver: pda::solver(solver_authority),
+        },
+        instruction::SolverHeartbeat {},
+    )
+}
+
+/// Permissionless; applies reputation decay to the solver of `solver_authority`
+pub fn refresh_reputation(solver_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::RefreshReputation {
+            solver: pda::solver(solver_authority),
+            config: pda::bridge_config(),
+        },
+        instruction::RefreshReputation {},
+    )
+}
+
+pub fn claim_solver_rewards(solver_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimSolverRewards {
+            solver_authority: *solver_authority,
+            solver: pda::solver(solver_authority),
+            fee_vault: pda::fee_vault(),
+        },
+        instruction::ClaimSolverRewards {},
+    )
+}
+
+pub fn deactivate_solver(owner: &Pubkey, solver_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::DeactivateSolver {
+            owner: *owner,
+            config: pda::bridge_config(),
+            solver: pda::solver(solver_authority),
+            fee_vault: pda::fee_vault(),
+        },
+        instruction::DeactivateSolver {},
+    )
+}
+
+pub fn reactivate_solver(owner: &Pubkey, solver_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ReactivateSolver {
+            owner: *owner,
+            config: pda::bridge_config(),
+            solver: pda::solver(solver_authority),
+            solver_authority: *solver_authority,
+            fee_vault: pda::fee_vault(),
+        },
+        instruction::ReactivateSolver {},
+    )
+}
+
+// ---- Intents ----
+
+/// `intent_id` is the current `BridgeConfig.total_intents`
+pub fn create_intent(creator: &Pubkey, intent_id: u64, args: instruction::CreateIntent) -> Instruction {
+    build(
+        accounts::CreateIntent {
+            creator: *creator,
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(args.destination_chain_id),
+            intent: pda::intent(intent_id),
+            intent_vault: pda::intent_vault(intent_id),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn match_intent(
+    solver_authority: &Pubkey,
+    intent_id: u64,
+    destination_chain_id: u32,
+    fill_amount: u64,
+    estimated_time_secs: Option<i64>,
+) -> Instruction {
+    let intent = pda::intent(intent_id);
+    build(
+        accounts::MatchIntent {
+            solver_authority: *solver_authority,
+            intent,
+            fill: pda::fill(&intent, solver_authority),
+            solver: pda::solver(solver_authority),
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(destination_chain_id),
+            system_program: system_program::ID,
+        },
+        instruction::MatchIntent {
+            fill_amount,
+            estimated_time_secs,
+        },
+    )
+}
+
+/// `receipt_hash` of the attested receipt is required on chains that need one
+pub fn execute_intent(
+    solver_authority: &Pubkey,
+    intent_id: u64,
+    destination_chain_id: u32,
+    receipt_hash: Option<&[u8; 32]>,
+    destination_tx_hash: [u8; 32],
+    privacy_proof: Option<[u8; 32]>,
+) -> Instruction {
+    let intent = pda::intent(intent_id);
+    build(
+        accounts::ExecuteIntent {
+            solver_authority: *solver_authority,
+            intent,
+            fill: pda::fill(&intent, solver_authority),
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(destination_chain_id),
+            receipt: receipt_hash.map(pda::receipt),
+        },
+        instruction::ExecuteIntent {
+            destination_tx_hash,
+            privacy_proof,
+        },
+    )
+}
+
+/// Settle the fill of `solver_authority` after the chain's finality delay
+pub fn settle_intent(
+    authority: &Pubkey,
+    intent_id: u64,
+    destination_chain_id: u32,
+    solver_authority: &Pubkey,
+) -> Instruction {
+    let intent = pda::intent(intent_id);
+    build(
+        accounts::SettleIntent {
+            authority: *authority,
+            intent,
+            fill: pda::fill(&intent, solver_authority),
+            intent_vault: pda::intent_vault(intent_id),
+            solver: pda::solver(solver_authority),
+            solver_authority: *solver_authority,
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(destination_chain_id),
+            fee_vault: pda::fee_vault(),
+        },
+        instruction::SettleIntent {},
+    )
+}
+
+/// Settle executed fills of one solver on one destination chain
+pub fn settle_intents_batch(
+    authority: &Pubkey,
+    solver_authority: &Pubkey,
+    destination_chain_id: u32,
+    intent_ids: &[u64],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleIntentsBatch {
+            authority: *authority,
+            solver: pda::solver(solver_authority),
+            solver_authority: *solver_authority,
+            config: pda::bridge_config(),
+            chain_config: pda::chain_config(destination_chain_id),
+            fee_vault: pda::fee_vault(),
+        },
+        instruction::SettleIntentsBatch {},
+    );
+    for &intent_id in intent_ids {
+        let intent = pda::intent(intent_id);
+        ix.accounts.extend([
+            AccountMeta::new(intent, false),
+            AccountMeta::new(pda::fill(&intent, solver_authority), false),
+            AccountMeta::new(pda::intent_vault(intent_id), false),
+        ]);
+    }
+    ix
+}
+
+pub fn fail_intent(solver_authority: &Pubkey, intent_id: u64, creator: &Pubkey) -> Instruction {
+    let intent = pda::intent(intent_id);
+    build(
+        accounts::FailIntent {
+            solver_authority: *solver_authority,
+            intent,
+            fill: pda::fill(&intent, solver_authority),
+            intent_vault: pda::intent_vault(intent_id),
+            creator: *creator,
+            solver: pda::solver(solver_authority),
+            config: pda::bridge_config(),
+        },
+        instruction::FailIntent {},
+    )
+}
+
+/// Permissionless once the fill's execution deadline has passed
+pub fn timeout_intent(
+    caller: &Pubkey,
+    intent_id: u64,
+    creator: &Pubkey,
+    solver_authority: &Pubkey,
+    reopen: bool,
+) -> Instruction {
+    let intent = pda::intent(intent_id);
+    build(
+        accounts::TimeoutIntent {
+            caller: *caller,
+            intent,
+            fill: pda::fill(&intent, solver_authority),
+            intent_vault: pda::intent_vault(intent_id),
+            creator: *creator,
+            solver: pda::solver(solver_authority),
+            config: pda::bridge_config(),
+        },
+        instruction::TimeoutIntent { reopen },
+    )
+}
+
+pub fn cancel_intent(creator: &Pubkey, intent_id: u64) -> Instruction {
+    build(
+        accounts::CancelIntent {
+            creator: *creator,
+            intent: pda::intent(intent_id),
+            intent_vault: pda::intent_vault(intent_id),
+        },
+        instruction::CancelIntent {},
+    )
+}
+
+pub fn close_intent(creator: &Pubkey, intent_id: u64) -> Instruction {
+    build(
+        accounts::CloseIntent {
+            creator: *creator,
+            intent: pda::intent(intent_id),
+            intent_vault: pda::intent_vault(intent_id),
+        },
+        instruction::CloseIntent {},
+    )
+}
+
+pub fn verify_intent_amounts(queue: &QueueAccounts, args: instruction::VerifyIntentAmounts) -> Instruction {
+    build(
+        accounts::VerifyIntentAmounts {
+            payer: queue.payer,
+            sign_pda_account: arcium::sign_pda(),
+            mxe_account: arcium::mxe_account(),
+            mempool_account: arcium::mempool_account(),
+            executing_pool: arcium::executing_pool(),
+            computation_account: arcium::computation_account(args.computation_offset),
+            comp_def_account: arcium::comp_def_account(arcium::COMP_DEF_OFFSET_VERIFY_INTENT_AMOUNTS),
+            cluster_account: queue.cluster,
+            pool_account: arcium::fee_pool_account(),
+            clock_account: arcium::clock_account(),
+            system_program: system_program::ID,
+            arcium_program: arcium::arcium_program(),
+        },
+        args,
+    )
+}
+
+pub fn generate_privacy_proof(queue: &QueueAccounts, args: instruction::GeneratePrivacyProof) -> Instruction {
+    build(
+        accounts::GeneratePrivacyProof {
+            payer: queue.payer,
+            sign_pda_account: arcium::sign_pda(),
+            mxe_account: arcium::mxe_account(),
+            mempool_account: arcium::mempool_account(),
+            executing_pool: arcium::executing_pool(),
+            computation_account: arcium::computation_account(args.computation_offset),
+            comp_def_account: arcium::comp_def_account(arcium::COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF),
+            cluster_account: queue.cluster,
+            pool_account: arcium::fee_pool_account(),
+            clock_account: arcium::clock_account(),
+            system_program: system_program::ID,
+            arcium_program: arcium::arcium_program(),
+        },
+        args,
+    )
+}
+
+// ---- Computation definitions ----
+
+macro_rules! init_comp_def {
+    ($name:ident, $accounts:ident, $offset:expr) => {
+        pub fn $name(payer: &Pubkey) -> Instruction {
+            build(
+                accounts::$accounts {
+                    payer: *payer,
+                    mxe_account: arcium::mxe_account(),
+                    comp_def_account: arcium::comp_def_account($offset),
+                    arcium_program: arcium::arcium_program(),
+                    system_program: system_program::ID,
+                },
+                instruction::$accounts {},
+            )
+        }
+    };
+}
+
+init_comp_def!(
+    init_process_inference_comp_def,
+    InitProcessInferenceCompDef,
+    arcium::COMP_DEF_OFFSET_PROCESS_INFERENCE
+);
+init_comp_def!(
+    init_verify_intent_amounts_comp_def,
+    InitVerifyIntentAmountsCompDef,
+    arcium::COMP_DEF_OFFSET_VERIFY_INTENT_AMOUNTS
+);
+init_comp_def!(
+    init_compute_settlement_comp_def,
+    InitComputeSettlementCompDef,
+    arcium::COMP_DEF_OFFSET_COMPUTE_SETTLEMENT
+);
+init_comp_def!(
+    init_calculate_reputation_comp_def,
+    InitCalculateReputationCompDef,
+    arcium::COMP_DEF_OFFSET_CALCULATE_REPUTATION
+);
+init_comp_def!(
+    init_verify_attestation_comp_def,
+    InitVerifyAttestationCompDef,
+    arcium::COMP_DEF_OFFSET_VERIFY_ATTESTATION
+);
+init_comp_def!(
+    init_generate_privacy_proof_comp_def,
+    InitGeneratePrivacyProofCompDef,
+    arcium::COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF
+);
+
+// ---- Account migrations ----
+
+macro_rules! migrate {
+    ($name:ident, $accounts:ident) => {
+        pub fn $name(payer: &Pubkey, account: &Pubkey) -> Instruction {
+            build(
+                accounts::$accounts {
+                    payer: *payer,
+                    account: *account,
+                    system_program: system_program::ID,
+                },
+                instruction::$accounts {},
+            )
+        }
+    };
+}
+
+migrate!(migrate_bridge_config, MigrateBridgeConfig);
+migrate!(migrate_fee_vault, MigrateFeeVault);
+migrate!(migrate_chain_config, MigrateChainConfig);
+migrate!(migrate_relayer_set, MigrateRelayerSet);
+migrate!(migrate_model, MigrateModel);
+migrate!(migrate_inference_request, MigrateInferenceRequest);
+migrate!(migrate_batch, MigrateBatch);
+migrate!(migrate_user_metrics, MigrateUserMetrics);
+migrate!(migrate_solver, MigrateSolver);
+migrate!(migrate_intent, MigrateIntent);
+migrate!(migrate_intent_fill, MigrateIntentFill);
+migrate!(migrate_receipt, MigrateReceipt);
@@ -0,0 +1,21 @@
+//! Rust client for the `project_silence` program.
+//!
+//! - [`pda`]: addresses for every PDA seed the program uses
+//! - [`instructions`]: typed instruction builders
+//! - [`accounts`]: account types and decoding
+//! - [`events`]: event decoding from transaction logs
+//! - [`discovery`]: filtering the model discovery index
+//! - [`arcium`]: Arcium accounts for queueing computations
+//!
+//! Account and instruction layouts come from the program crate itself, so
+//! the client cannot drift from the deployed IDL.
+
+pub mod accounts;
+pub mod arcium;
+pub mod discovery;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+
+// The `arcium_anchor` derive macros resolve `crate::ID`
+pub use project_silence::{ID, ID_CONST};
@@ -0,0 +1,103 @@
+//! PDA derivation for every seed used by the program.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::ID;
+
+pub fn bridge_config() -> Pubkey {
+    Pubkey::find_program_address(&[b"bridge_config"], &ID).0
+}
+
+pub fn fee_vault() -> Pubkey {
+    Pubkey::find_program_address(&[b"fee_vault"], &ID).0
+}
+
+pub fn relayer_set() -> Pubkey {
+    Pubkey::find_program_address(&[b"relayer_set"], &ID).0
+}
+
+pub fn chain_config(chain_id: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"chain_config", &chain_id.to_le_bytes()], &ID).0
+}
+
+pub fn model(model_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"model", &model_id.to_le_bytes()], &ID).0
+}
+
+/// Ownership transfer, delegates and payout of a model (may not exist)
+pub fn model_access(model_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"model_access", &model_id.to_le_bytes()], &ID).0
+}
+
+/// Keys that process a model's inferences (may not exist)
+pub fn model_operators(model_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"model_operators", &model_id.to_le_bytes()], &ID).0
+}
+
+/// Published `version` of a model
+pub fn model_version(model_id: u64, version: &str) -> Pubkey {
+    Pubkey::find_program_address(&[b"model_version", &model_id.to_le_bytes(), version.as_bytes()], &ID).0
+}
+
+pub fn model_directory() -> Pubkey {
+    Pubkey::find_program_address(&[b"model_directory"], &ID).0
+}
+
+/// Page `page` of the model discovery index
+pub fn model_index(page: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"model_index", &page.to_le_bytes()], &ID).0
+}
+
+/// Index page of a model (`ModelListing`); absent for models not listed yet
+pub fn model_listing(model_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"model_listing", &model_id.to_le_bytes()], &ID).0
+}
+
+/// Prepaid credits of `user` with the model owner `provider`
+pub fn credit_account(user: &Pubkey, provider: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"credit", user.as_ref(), provider.as_ref()], &ID).0
+}
+
+pub fn subscription_plan(model_id: u64, plan_id: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"subscription_plan", &model_id.to_le_bytes(), &[plan_id]], &ID).0
+}
+
+pub fn subscription(user: &Pubkey, model_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"subscription", user.as_ref(), &model_id.to_le_bytes()], &ID).0
+}
+
+pub fn user_metrics(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user_metrics", user.as_ref()], &ID).0
+}
+
+/// Inference request `request_id` of `user` (from `UserMetrics.next_request_id`)
+pub fn request(user: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"request", user.as_ref(), &request_id.to_le_bytes()], &ID).0
+}
+
+/// Batch `batch_id` of `user` (from `UserMetrics.next_batch_id`)
+pub fn batch(user: &Pubkey, batch_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"batch", user.as_ref(), &batch_id.to_le_bytes()], &ID).0
+}
+
+pub fn solver(solver_authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"solver", solver_authority.as_ref()], &ID).0
+}
+
+/// Intent `intent_id` (from `BridgeConfig.total_intents`)
+pub fn intent(intent_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"intent", &intent_id.to_le_bytes()], &ID).0
+}
+
+pub fn intent_vault(intent_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"intent_vault", &intent_id.to_le_bytes()], &ID).0
+}
+
+pub fn fill(intent: &Pubkey, solver_authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fill", intent.as_ref(), solver_authority.as_ref()], &ID).0
+}
+
+/// Attestation of the receipt whose `FulfillmentReceipt::hash` is `receipt_hash`
+pub fn receipt(receipt_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"receipt", receipt_hash], &ID).0
+}
@@ -0,0 +1,223 @@
+//! Runs the SDK against the compiled program in LiteSVM.
+//!
+//! Requires `anchor build` so that `target/deploy/project_silence.so` exists.
+
+use anchor_lang::{Event, Space};
+use base64::Engine;
+use litesvm::LiteSVM;
+use project_silence::instruction as args;
+use project_silence_client::accounts::{decode, BridgeConfig, Intent, IntentStatus, Solver};
+use project_silence_client::events::{parse_logs, IntentCreated, ProgramEvent};
+use project_silence_client::{instructions, pda, ID};
+use silence_intent::CHAIN_ID_NEAR;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+const PROGRAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy/project_silence.so");
+const SOL: u64 = 1_000_000_000;
+const FINALITY_SECS: u32 = 60;
+
+struct Env {
+    svm: LiteSVM,
+    owner: Keypair,
+    solver: Keypair,
+    creator: Keypair,
+}
+
+impl Env {
+    fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(ID, PROGRAM_PATH)
+            .expect("run `anchor build` first");
+        let (owner, solver, creator) = (Keypair::new(), Keypair::new(), Keypair::new());
+        for key in [&owner, &solver, &creator] {
+            svm.airdrop(&key.pubkey(), 100 * SOL).unwrap();
+        }
+        let mut env = Self {
+            svm,
+            owner,
+            solver,
+            creator,
+        };
+        let owner = env.owner.pubkey();
+        env.send(&[instructions::initialize_bridge(&owner, SOL, 50)], &[]);
+        env.send(
+            &[instructions::register_chain(
+                &owner,
+                args::RegisterChain {
+                    chain_id: CHAIN_ID_NEAR,
+                    name: "near".to_string(),
+                    min_amount: 1_000,
+                    finality_seconds: FINALITY_SECS,
+                    fee_bps_override: None,
+                    volume_cap: 0,
+                },
+            )],
+            &[],
+        );
+        let solver = env.solver.insecure_clone();
+        env.send(&[instructions::register_solver(&solver.pubkey(), vec![CHAIN_ID_NEAR])], &[&solver]);
+        env
+    }
+
+    /// Send as the owner plus `signers`, returning the decoded events
+    fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Vec<ProgramEvent> {
+        let mut all: Vec<&Keypair> = vec![&self.owner];
+        all.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.owner.pubkey()), &all, self.svm.latest_blockhash());
+        let meta = self.svm.send_transaction(tx).unwrap_or_else(|e| panic!("{:?}\n{:#?}", e.err, e.meta.logs));
+        self.svm.expire_blockhash();
+        parse_logs(&meta.logs)
+    }
+
+    fn account<T: anchor_lang::AccountDeserialize>(&self, address: &Pubkey) -> T {
+        decode(&self.svm.get_account(address).expect("account exists").data).unwrap()
+    }
+
+    fn warp(&mut self, secs: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += secs;
+        self.svm.set_sysvar(&clock);
+    }
+
+    fn create_intent(&mut self, source_amount: u64) -> u64 {
+        let intent_id = self.account::<BridgeConfig>(&pda::bridge_config()).total_intents;
+        let creator = self.creator.insecure_clone();
+        let events = self.send(
+            &[instructions::create_intent(
+                &creator.pubkey(),
+                intent_id,
+                args::CreateIntent {
+                    destination_chain_id: CHAIN_ID_NEAR,
+                    destination_amount_commitment: silence_intent::amount_commitment(5, &[7; 32]),
+                    destination_token_hash: silence_intent::token_hash("near"),
+                    recipient_hash: silence_intent::recipient_hash("alice.near"),
+                    is_shielded: false,
+                    ttl_seconds: 3_600,
+                    source_amount,
+                    allow_partial_fill: false,
+                },
+            )],
+            &[&creator],
+        );
+        assert!(matches!(
+            events.as_slice(),
+            [ProgramEvent::IntentCreated(e)] if e.intent_id == intent_id && e.source_amount == source_amount
+        ));
+        intent_id
+    }
+
+    fn match_and_execute(&mut self, intent_id: u64, amount: u64) {
+        let solver = self.solver.insecure_clone();
+        let authority = solver.pubkey();
+        self.send(
+            &[
+                instructions::match_intent(&authority, intent_id, CHAIN_ID_NEAR, amount, None),
+                instructions::execute_intent(&authority, intent_id, CHAIN_ID_NEAR, None, [9; 32], None),
+            ],
+            &[&solver],
+        );
+    }
+}
+
+#[test]
+fn intent_lifecycle_settles_to_solver() {
+    let mut env = Env::new();
+    let authority = env.solver.pubkey();
+    let intent_id = env.create_intent(2 * SOL);
+    let intent: Intent = env.account(&pda::intent(intent_id));
+    assert!(intent.status == IntentStatus::Created);
+    assert_eq!(intent.creator, env.creator.pubkey());
+
+    env.match_and_execute(intent_id, 2 * SOL);
+    let intent: Intent = env.account(&pda::intent(intent_id));
+    assert!(intent.status == IntentStatus::Executed);
+    assert_eq!(intent.destination_tx_hash, [9; 32]);
+
+    env.warp(FINALITY_SECS as i64);
+    let owner = env.owner.pubkey();
+    let events = env.send(
+        &[instructions::settle_intent(&owner, intent_id, CHAIN_ID_NEAR, &authority)],
+        &[],
+    );
+    let [ProgramEvent::IntentSettled(settled)] = events.as_slice() else {
+        panic!("expected IntentSettled");
+    };
+    assert_eq!(settled.fill_amount, 2 * SOL);
+    assert_eq!(settled.protocol_fee, 2 * SOL * 50 / 10_000);
+
+    let intent: Intent = env.account(&pda::intent(intent_id));
+    assert!(intent.status == IntentStatus::Settled);
+    let solver: Solver = env.account(&pda::solver(&authority));
+    assert_eq!(solver.successful_intents, 1);
+    assert!(env.svm.get_account(&pda::fill(&pda::intent(intent_id), &authority)).is_none());
+}
+
+#[test]
+fn batch_settlement_covers_every_intent() {
+    let mut env = Env::new();
+    let authority = env.solver.pubkey();
+    let ids: Vec<u64> = (0..3).map(|_| env.create_intent(SOL)).collect();
+    for &id in &ids {
+        env.match_and_execute(id, SOL);
+    }
+    env.warp(FINALITY_SECS as i64);
+
+    let owner = env.owner.pubkey();
+    let events = env.send(
+        &[instructions::settle_intents_batch(&owner, &authority, CHAIN_ID_NEAR, &ids)],
+        &[],
+    );
+    let batch = events
+        .iter()
+        .find_map(|event| match event {
+            ProgramEvent::IntentsBatchSettled(batch) => Some(batch),
+            _ => None,
+        })
+        .expect("IntentsBatchSettled emitted");
+    assert_eq!(batch.settled_count, 3);
+    assert_eq!(batch.total_volume, 3 * SOL);
+    for id in ids {
+        assert!(env.account::<Intent>(&pda::intent(id)).status == IntentStatus::Settled);
+    }
+}
+
+#[test]
+fn account_space_matches_on_chain_size() {
+    let env = Env::new();
+    let data = env.svm.get_account(&pda::bridge_config()).unwrap().data;
+    assert_eq!(data.len(), 8 + BridgeConfig::INIT_SPACE);
+}
+
+#[test]
+fn parse_logs_ignores_other_programs() {
+    let event = IntentCreated {
+        intent_id: 4,
+        creator: Pubkey::new_unique(),
+        destination_chain_id: CHAIN_ID_NEAR,
+        source_amount: 10,
+        is_shielded: true,
+    };
+    let data = format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(event.data()));
+    let other = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {ID} invoke [1]"),
+        format!("Program {other} invoke [2]"),
+        data.clone(),
+        format!("Program {other} success"),
+        data,
+        "Program data: not base64".to_string(),
+        format!("Program {ID} success"),
+    ];
+    let events = parse_logs(&logs);
+    assert_eq!(events.len(), 1);
+    let ProgramEvent::IntentCreated(decoded) = &events[0] else {
+        panic!("decoded {}", events[0].name());
+    };
+    assert_eq!(decoded.intent_id, 4);
+    assert_eq!(decoded.creator, event.creator);
+    assert!(decoded.is_shielded);
+}
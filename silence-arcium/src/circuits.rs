@@ -0,0 +1,126 @@
+//! Mirrors of the structs in `encrypted-ixs`, in the same field order.
+//!
+//! Keep these in sync with the circuits: a reordered or retyped field
+//! silently changes which ciphertext the MXE reads as which value.
+
+use crate::field::{FieldElement, Pack};
+
+macro_rules! circuit_struct {
+    ($(#[$meta:meta])* $name:ident { $($(#[$field_meta:meta])* $field:ident: $ty:ty,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: $ty,)*
+        }
+
+        impl Pack for $name {
+            const LEN: usize = 0 $(+ <$ty as Pack>::LEN)*;
+
+            fn pack(&self, out: &mut Vec<FieldElement>) {
+                $(self.$field.pack(out);)*
+            }
+
+            fn unpack(elements: &mut std::slice::Iter<'_, FieldElement>) -> Option<Self> {
+                Some(Self {
+                    $($field: <$ty as Pack>::unpack(elements)?,)*
+                })
+            }
+        }
+    };
+}
+
+circuit_struct!(
+    /// `process_inference` input
+    InferenceInput {
+        prompt_hash: [u8; 32],
+        model_id: u64,
+        nonce: u128,
+    }
+);
+
+circuit_struct!(
+    /// `process_inference` output
+    InferenceOutput {
+        result_hash: [u8; 32],
+        timestamp: u64,
+        verified: bool,
+    }
+);
+
+circuit_struct!(
+    /// `verify_intent_amounts` input
+    IntentAmounts {
+        source_amount: u128,
+        destination_amount: u128,
+    }
+);
+
+circuit_struct!(
+    /// `verify_intent_amounts` output
+    IntentVerification {
+        rate_valid: bool,
+        amount_sufficient: bool,
+        fee: u128,
+    }
+);
+
+circuit_struct!(
+    /// `compute_settlement` input
+    SettlementAmounts {
+        total_amount: u128,
+        protocol_fee_bps: u64,
+    }
+);
+
+circuit_struct!(
+    /// `compute_settlement` output
+    SettlementDistribution {
+        solver_reward: u128,
+        protocol_fee: u128,
+    }
+);
+
+circuit_struct!(
+    /// `calculate_reputation` input
+    SolverMetrics {
+        total_executed: u64,
+        successful: u64,
+        failed: u64,
+        total_volume: u128,
+    }
+);
+
+circuit_struct!(
+    /// `calculate_reputation` output
+    ReputationScore {
+        score: u32,
+        tier: u8,
+        high_value_eligible: bool,
+    }
+);
+
+circuit_struct!(
+    /// `verify_attestation` input; the output is a single `bool`
+    AttestationData {
+        enclave_id: [u8; 32],
+        quote_signature: [u8; 64],
+        timestamp: u64,
+    }
+);
+
+circuit_struct!(
+    /// `generate_privacy_proof` input
+    ShieldedTransfer {
+        amount: u128,
+        blinding: [u8; 32],
+        recipient_hash: [u8; 32],
+    }
+);
+
+circuit_struct!(
+    /// `generate_privacy_proof` output
+    PrivacyProof {
+        commitment: [u8; 32],
+        range_valid: bool,
+    }
+);
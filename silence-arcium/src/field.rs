@@ -0,0 +1,94 @@
+//! Field element packing.
+//!
+//! Arcis flattens a struct into one Curve25519 base field element per
+//! scalar, in declaration order: integers and `bool`s take one element
+//! each and a `[u8; N]` takes `N`. Elements are 32-byte little-endian, the
+//! same encoding as the ciphertexts passed to and emitted by the program.
+
+/// Little-endian encoding of an element of GF(2^255 - 19)
+pub type FieldElement = [u8; 32];
+
+/// A value with a fixed layout in circuit field elements
+pub trait Pack: Sized {
+    /// Number of field elements
+    const LEN: usize;
+
+    fn pack(&self, out: &mut Vec<FieldElement>);
+
+    /// Read `Self::LEN` elements; `None` if any is out of range for its type
+    fn unpack(elements: &mut std::slice::Iter<'_, FieldElement>) -> Option<Self>;
+
+    fn to_elements(&self) -> Vec<FieldElement> {
+        let mut out = Vec::with_capacity(Self::LEN);
+        self.pack(&mut out);
+        out
+    }
+
+    /// Decode exactly `Self::LEN` elements
+    fn from_elements(elements: &[FieldElement]) -> Option<Self> {
+        if elements.len() != Self::LEN {
+            return None;
+        }
+        Self::unpack(&mut elements.iter())
+    }
+}
+
+fn from_u128(value: u128) -> FieldElement {
+    let mut element = [0u8; 32];
+    element[..16].copy_from_slice(&value.to_le_bytes());
+    element
+}
+
+/// The element's value if it is below `2^bits`
+fn to_u128(element: &FieldElement, bits: u32) -> Option<u128> {
+    if element[16..].iter().any(|&b| b != 0) {
+        return None;
+    }
+    let value = u128::from_le_bytes(element[..16].try_into().unwrap());
+    (bits == 128 || value >> bits == 0).then_some(value)
+}
+
+macro_rules! pack_uint {
+    ($($ty:ty),*) => {$(
+        impl Pack for $ty {
+            const LEN: usize = 1;
+
+            fn pack(&self, out: &mut Vec<FieldElement>) {
+                out.push(from_u128(*self as u128));
+            }
+
+            fn unpack(elements: &mut std::slice::Iter<'_, FieldElement>) -> Option<Self> {
+                to_u128(elements.next()?, <$ty>::BITS).map(|value| value as $ty)
+            }
+        }
+    )*};
+}
+
+pack_uint!(u8, u16, u32, u64, u128);
+
+impl Pack for bool {
+    const LEN: usize = 1;
+
+    fn pack(&self, out: &mut Vec<FieldElement>) {
+        out.push(from_u128(*self as u128));
+    }
+
+    fn unpack(elements: &mut std::slice::Iter<'_, FieldElement>) -> Option<Self> {
+        to_u128(elements.next()?, 1).map(|value| value == 1)
+    }
+}
+
+impl<T: Pack, const N: usize> Pack for [T; N] {
+    const LEN: usize = T::LEN * N;
+
+    fn pack(&self, out: &mut Vec<FieldElement>) {
+        for item in self {
+            item.pack(out);
+        }
+    }
+
+    fn unpack(elements: &mut std::slice::Iter<'_, FieldElement>) -> Option<Self> {
+        let items = (0..N).map(|_| T::unpack(elements)).collect::<Option<Vec<T>>>()?;
+        items.try_into().ok()
+    }
+}
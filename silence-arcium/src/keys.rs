@@ -0,0 +1,34 @@
+//! x25519 key agreement with the MXE.
+
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use rand_core::{OsRng, RngCore};
+
+/// An x25519 key pair. One-time input keys and observer keys are both
+/// plain key pairs; only the MXE's public key is long-lived.
+pub struct KeyPair {
+    secret: [u8; 32],
+    public: [u8; 32],
+}
+
+impl KeyPair {
+    pub fn generate() -> Self {
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        Self::from_secret(secret)
+    }
+
+    /// Clamping is applied when the key is used, as in RFC 7748
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        let public = MontgomeryPoint::mul_base_clamped(secret).to_bytes();
+        Self { secret, public }
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public
+    }
+
+    /// Shared secret with `peer`, e.g. the MXE public key from its account
+    pub fn shared_secret(&self, peer: &[u8; 32]) -> [u8; 32] {
+        MontgomeryPoint(*peer).mul_clamped(self.secret).to_bytes()
+    }
+}
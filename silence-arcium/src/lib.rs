@@ -0,0 +1,28 @@
+//! Client side of the `encrypted-ixs` circuits.
+//!
+//! An `Enc<Shared, T>` circuit argument is encrypted to the MXE with a
+//! one-time x25519 key and a 128-bit nonce; outputs are re-encrypted to an
+//! observer key supplied in the same call. This crate handles the parts
+//! that are specific to this project:
+//!
+//! - [`keys`]: x25519 key pairs and shared secrets with the MXE
+//! - [`field`]: packing values into field elements the way Arcis lays out
+//!   circuit structs
+//! - [`circuits`]: mirrors of every struct in `encrypted-ixs`
+//! - [`session`]: encrypting inputs, ordering queue arguments and
+//!   decrypting callback outputs
+//! - [`reference`]: plaintext implementations of every circuit
+//!
+//! The Rescue cipher itself is provided by the caller through
+//! [`SharedCipher`], so the crate follows whatever cipher version the
+//! cluster runs (`RescueCipher` in `@arcium-hq/client`).
+
+pub mod circuits;
+pub mod field;
+pub mod keys;
+pub mod reference;
+pub mod session;
+
+pub use field::{FieldElement, Pack};
+pub use keys::KeyPair;
+pub use session::{Encrypted, Observer, Sender, SharedCipher};
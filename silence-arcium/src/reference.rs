@@ -0,0 +1,112 @@
+//! Plaintext reference implementations of the `encrypted-ixs` circuits.
+//!
+//! Each function computes what the matching `#[instruction]` returns for
+//! the same inputs, without encryption. They are the specification the
+//! property tests check and can be used to predict a callback's output.
+//!
+//! Keep these in step with `encrypted-ixs/src/lib.rs`. Products of a
+//! `u128` amount and a basis-point value must fit in `u128`, which holds
+//! for every amount below 2^64. Fees must not exceed 10000 basis points
+//! and `total_volume * 100` must fit in `u128`; the circuits do not guard
+//! beyond that.
+
+use crate::circuits::*;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Score of a solver without executed intents, before the volume bonus
+pub const DEFAULT_SUCCESS_RATE: u32 = 500;
+
+/// Highest reputation score
+pub const MAX_SCORE: u32 = 1000;
+
+/// `process_inference`: echoes the prompt hash, the nonce truncated to
+/// `u64` becomes the timestamp
+pub fn process_inference(input: &InferenceInput) -> InferenceOutput {
+    InferenceOutput {
+        result_hash: input.prompt_hash,
+        timestamp: input.nonce as u64,
+        verified: true,
+    }
+}
+
+/// `verify_intent_amounts`
+pub fn verify_intent_amounts(
+    amounts: &IntentAmounts,
+    expected_rate_bps: u64,
+    min_source_amount: u128,
+    protocol_fee_bps: u64,
+) -> IntentVerification {
+    let expected_destination = amounts.source_amount * expected_rate_bps as u128 / BPS_DENOMINATOR as u128;
+    IntentVerification {
+        rate_valid: amounts.destination_amount <= expected_destination,
+        amount_sufficient: amounts.source_amount >= min_source_amount,
+        fee: fee(amounts.source_amount, protocol_fee_bps),
+    }
+}
+
+/// `compute_settlement`
+pub fn compute_settlement(amounts: &SettlementAmounts) -> SettlementDistribution {
+    let protocol_fee = fee(amounts.total_amount, amounts.protocol_fee_bps);
+    SettlementDistribution {
+        solver_reward: amounts.total_amount - protocol_fee,
+        protocol_fee,
+    }
+}
+
+/// `calculate_reputation`
+pub fn calculate_reputation(metrics: &SolverMetrics, volume_threshold: u128) -> ReputationScore {
+    let success_rate = if metrics.total_executed > 0 {
+        let successful = metrics.successful.min(metrics.total_executed);
+        (successful as u128 * 1000 / metrics.total_executed as u128) as u32
+    } else {
+        DEFAULT_SUCCESS_RATE
+    };
+
+    let volume_bonus = if metrics.total_volume >= volume_threshold {
+        100
+    } else {
+        (metrics.total_volume * 100 / volume_threshold) as u32
+    };
+
+    let score = (success_rate + volume_bonus).min(MAX_SCORE);
+    let tier = tier(score);
+    ReputationScore {
+        score,
+        tier,
+        high_value_eligible: tier >= 4 && metrics.total_volume >= volume_threshold,
+    }
+}
+
+/// Tier (1-5) of a reputation score
+pub fn tier(score: u32) -> u8 {
+    match score {
+        900.. => 5,
+        700..=899 => 4,
+        500..=699 => 3,
+        300..=499 => 2,
+        _ => 1,
+    }
+}
+
+/// `verify_attestation`: the enclave matches, the quote is recent and its
+/// signature is not all zeroes
+pub fn verify_attestation(attestation: &AttestationData, expected_enclave_id: &[u8; 32], min_timestamp: u64) -> bool {
+    attestation.enclave_id == *expected_enclave_id
+        && attestation.timestamp >= min_timestamp
+        && attestation.quote_signature.iter().any(|&byte| byte != 0)
+}
+
+/// `generate_privacy_proof`: the blinding factor is the commitment
+pub fn generate_privacy_proof(transfer: &ShieldedTransfer, max_amount: u128) -> PrivacyProof {
+    PrivacyProof {
+        commitment: transfer.blinding,
+        range_valid: transfer.amount <= max_amount,
+    }
+}
+
+/// `amount * fee_bps / 10000`
+fn fee(amount: u128, fee_bps: u64) -> u128 {
+    amount * fee_bps as u128 / BPS_DENOMINATOR as u128
+}
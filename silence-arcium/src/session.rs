@@ -0,0 +1,126 @@
+//! Encrypting circuit inputs and decrypting outputs.
+//!
+//! Queue instructions take an `Enc<Shared, T>` argument as the sender's
+//! one-time public key, its nonce and one ciphertext per field element of
+//! `T`, followed by the circuit's plaintext parameters and finally the
+//! observer's public key and nonce:
+//!
+//! ```text
+//! one_time_pub_key, one_time_nonce, ciphertexts.., plaintext.., observer_pub_key, observer_nonce
+//! ```
+//!
+//! The callback emits the output ciphertexts with the nonce the MXE used,
+//! which the [`Observer`] needs to decrypt them.
+
+use rand_core::{OsRng, RngCore};
+
+use crate::field::{FieldElement, Pack};
+use crate::keys::KeyPair;
+
+/// Rescue cipher keyed by an x25519 shared secret, as run by the MXE
+pub trait SharedCipher: Sized {
+    fn new(shared_secret: &[u8; 32]) -> Self;
+
+    fn encrypt(&self, plaintext: &[FieldElement], nonce: &[u8; 16]) -> Vec<FieldElement>;
+
+    fn decrypt(&self, ciphertext: &[FieldElement], nonce: &[u8; 16]) -> Vec<FieldElement>;
+}
+
+/// An encrypted `Enc<Shared, T>` argument
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Encrypted {
+    pub public_key: [u8; 32],
+    pub nonce: u128,
+    pub ciphertexts: Vec<FieldElement>,
+}
+
+impl Encrypted {
+    /// Ciphertexts as the fixed-size array an instruction argument expects
+    pub fn ciphertext_array<const N: usize>(&self) -> Option<[FieldElement; N]> {
+        self.ciphertexts.as_slice().try_into().ok()
+    }
+}
+
+fn random_nonce() -> u128 {
+    let mut nonce = [0u8; 16];
+    OsRng.fill_bytes(&mut nonce);
+    u128::from_le_bytes(nonce)
+}
+
+/// Encrypts inputs to the MXE under a one-time key.
+///
+/// Every call to [`Sender::encrypt`] draws a fresh random nonce, so one
+/// sender may encrypt several inputs without reusing a keystream.
+pub struct Sender<C> {
+    key: KeyPair,
+    cipher: C,
+}
+
+impl<C: SharedCipher> Sender<C> {
+    /// `mxe_public_key` is the x25519 key stored in the program's MXE account
+    pub fn new(mxe_public_key: &[u8; 32]) -> Self {
+        Self::with_key(KeyPair::generate(), mxe_public_key)
+    }
+
+    pub fn with_key(key: KeyPair, mxe_public_key: &[u8; 32]) -> Self {
+        let cipher = C::new(&key.shared_secret(mxe_public_key));
+        Self { key, cipher }
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.public_key()
+    }
+
+    pub fn encrypt<T: Pack>(&self, value: &T) -> Encrypted {
+        self.encrypt_with_nonce(value, random_nonce())
+    }
+
+    /// Encrypt under a caller-chosen nonce, which must never repeat for this key
+    pub fn encrypt_with_nonce<T: Pack>(&self, value: &T, nonce: u128) -> Encrypted {
+        Encrypted {
+            public_key: self.key.public_key(),
+            nonce,
+            ciphertexts: self.cipher.encrypt(&value.to_elements(), &nonce.to_le_bytes()),
+        }
+    }
+}
+
+/// Receives a computation's output, which the MXE encrypts to this key
+pub struct Observer<C> {
+    key: KeyPair,
+    cipher: C,
+    nonce: u128,
+}
+
+impl<C: SharedCipher> Observer<C> {
+    pub fn new(mxe_public_key: &[u8; 32]) -> Self {
+        Self::with_key(KeyPair::generate(), mxe_public_key)
+    }
+
+    pub fn with_key(key: KeyPair, mxe_public_key: &[u8; 32]) -> Self {
+        let cipher = C::new(&key.shared_secret(mxe_public_key));
+        Self {
+            key,
+            cipher,
+            nonce: random_nonce(),
+        }
+    }
+
+    /// `observer_pub_key` argument of the queue instruction
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.public_key()
+    }
+
+    /// `observer_nonce` argument of the queue instruction
+    pub fn nonce(&self) -> u128 {
+        self.nonce
+    }
+
+    /// Decrypt output ciphertexts using the nonce emitted with them.
+    ///
+    /// `None` if the ciphertexts do not decrypt to a valid `T`, e.g. when
+    /// they were encrypted to another observer.
+    pub fn decrypt<T: Pack>(&self, ciphertexts: &[FieldElement], nonce: &[u8; 16]) -> Option<T> {
+        T::from_elements(&self.cipher.decrypt(ciphertexts, nonce))
+    }
+}
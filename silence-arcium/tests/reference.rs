@@ -0,0 +1,176 @@
+//! Invariants of the plaintext circuit implementations.
+//!
+//! Equivalence with the compiled circuits is not checked here; running
+//! them needs an Arcium localnet (`tests/project_silence.ts`).
+
+use proptest::prelude::*;
+use silence_arcium::circuits::*;
+use silence_arcium::reference::*;
+
+/// Amounts for which every basis-point product fits in `u128`
+fn amount() -> impl Strategy<Value = u128> {
+    any::<u64>().prop_map(u128::from)
+}
+
+/// Fees of at most 100%
+fn fee_bps() -> impl Strategy<Value = u64> {
+    0..=BPS_DENOMINATOR
+}
+
+fn metrics() -> impl Strategy<Value = SolverMetrics> {
+    (any::<u64>(), any::<u64>(), any::<u64>(), amount()).prop_map(
+        |(total_executed, successful, failed, total_volume)| SolverMetrics {
+            total_executed,
+            successful,
+            failed,
+            total_volume,
+        },
+    )
+}
+
+/// Metrics where `successful` never exceeds `total_executed`
+fn consistent_metrics() -> impl Strategy<Value = SolverMetrics> {
+    (1..=u64::MAX, amount())
+        .prop_flat_map(|(total_executed, total_volume)| (Just(total_executed), 0..=total_executed, Just(total_volume)))
+        .prop_map(|(total_executed, successful, total_volume)| SolverMetrics {
+            total_executed,
+            successful,
+            failed: total_executed - successful,
+            total_volume,
+        })
+}
+
+proptest! {
+    #[test]
+    fn settlement_splits_the_total(total_amount in amount(), protocol_fee_bps in fee_bps()) {
+        let distribution = compute_settlement(&SettlementAmounts { total_amount, protocol_fee_bps });
+        prop_assert!(distribution.protocol_fee <= total_amount);
+        prop_assert_eq!(distribution.solver_reward + distribution.protocol_fee, total_amount);
+    }
+
+    #[test]
+    fn settlement_fee_is_monotonic_in_bps(total_amount in amount(), a in fee_bps(), b in fee_bps()) {
+        let fee = |protocol_fee_bps| compute_settlement(&SettlementAmounts { total_amount, protocol_fee_bps }).protocol_fee;
+        prop_assert!(fee(a.min(b)) <= fee(a.max(b)));
+    }
+
+    #[test]
+    fn intent_fee_never_exceeds_source(
+        source_amount in amount(),
+        destination_amount in amount(),
+        expected_rate_bps in any::<u64>(),
+        min_source_amount in any::<u128>(),
+        protocol_fee_bps in fee_bps(),
+    ) {
+        let amounts = IntentAmounts { source_amount, destination_amount };
+        let verification = verify_intent_amounts(&amounts, expected_rate_bps, min_source_amount, protocol_fee_bps);
+        prop_assert!(verification.fee <= source_amount);
+        prop_assert_eq!(verification.amount_sufficient, source_amount >= min_source_amount);
+
+        // The circuit's fee agrees with the settlement split of the same amount
+        let settlement = compute_settlement(&SettlementAmounts { total_amount: source_amount, protocol_fee_bps });
+        prop_assert_eq!(verification.fee, settlement.protocol_fee);
+    }
+
+    #[test]
+    fn reputation_is_bounded(metrics in metrics(), volume_threshold in any::<u128>()) {
+        let reputation = calculate_reputation(&metrics, volume_threshold);
+        prop_assert!(reputation.score <= MAX_SCORE);
+        prop_assert!((1..=5).contains(&reputation.tier));
+        prop_assert_eq!(reputation.tier, tier(reputation.score));
+        if reputation.high_value_eligible {
+            prop_assert!(reputation.tier >= 4);
+        }
+    }
+
+    #[test]
+    fn reputation_grows_with_successes(metrics in consistent_metrics(), volume_threshold in any::<u128>()) {
+        let better = SolverMetrics {
+            successful: metrics.total_executed,
+            failed: 0,
+            ..metrics.clone()
+        };
+        let score = calculate_reputation(&metrics, volume_threshold).score;
+        prop_assert!(calculate_reputation(&better, volume_threshold).score >= score);
+    }
+
+    #[test]
+    fn reputation_grows_with_volume(metrics in metrics(), extra in amount(), volume_threshold in any::<u128>()) {
+        let more = SolverMetrics {
+            total_volume: metrics.total_volume + extra,
+            ..metrics.clone()
+        };
+        let before = calculate_reputation(&metrics, volume_threshold);
+        let after = calculate_reputation(&more, volume_threshold);
+        prop_assert!(after.score >= before.score);
+        prop_assert!(after.high_value_eligible || !before.high_value_eligible);
+    }
+
+    #[test]
+    fn tier_is_monotonic_in_score(a in 0..=MAX_SCORE, b in 0..=MAX_SCORE) {
+        prop_assert!(tier(a.min(b)) <= tier(a.max(b)));
+    }
+
+    #[test]
+    fn inference_echoes_the_prompt(prompt_hash in any::<[u8; 32]>(), model_id in any::<u64>(), nonce in any::<u128>()) {
+        let output = process_inference(&InferenceInput { prompt_hash, model_id, nonce });
+        prop_assert_eq!(output.result_hash, prompt_hash);
+        prop_assert_eq!(output.timestamp, nonce as u64);
+        prop_assert!(output.verified);
+    }
+
+    #[test]
+    fn attestation_rejects_any_other_enclave(
+        enclave_id in any::<[u8; 32]>(),
+        index in 0..32usize,
+        timestamp in any::<u64>(),
+    ) {
+        let attestation = AttestationData { enclave_id, quote_signature: [1; 64], timestamp };
+        prop_assert!(verify_attestation(&attestation, &enclave_id, timestamp));
+        let mut other = enclave_id;
+        other[index] ^= 1;
+        prop_assert!(!verify_attestation(&attestation, &other, timestamp));
+        if let Some(later) = timestamp.checked_add(1) {
+            prop_assert!(!verify_attestation(&attestation, &enclave_id, later));
+        }
+    }
+
+    #[test]
+    fn privacy_proof_checks_the_range(amount in any::<u128>(), max_amount in any::<u128>(), blinding in any::<[u8; 32]>()) {
+        let transfer = ShieldedTransfer { amount, blinding, recipient_hash: [0; 32] };
+        let proof = generate_privacy_proof(&transfer, max_amount);
+        prop_assert_eq!(proof.commitment, blinding);
+        prop_assert_eq!(proof.range_valid, amount <= max_amount);
+    }
+}
+
+#[test]
+fn counts_above_u32_are_not_truncated() {
+    // (2^32 + 1) as u32 would be 1, turning 2^32 successes out of 2^32 + 1 into 0%
+    let metrics = SolverMetrics {
+        total_executed: (1 << 32) + 1,
+        successful: 1 << 32,
+        failed: 1,
+        total_volume: 0,
+    };
+    let reputation = calculate_reputation(&metrics, 1);
+    assert_eq!(reputation.score, 999);
+    assert_eq!(reputation.tier, 5);
+}
+
+#[test]
+fn more_successes_than_executions_count_as_all_successful() {
+    let metrics = SolverMetrics {
+        total_executed: 10,
+        successful: 20,
+        failed: 0,
+        total_volume: 0,
+    };
+    assert_eq!(calculate_reputation(&metrics, u128::MAX).score, MAX_SCORE);
+}
+
+#[test]
+fn tier_boundaries() {
+    let tiers: Vec<u8> = [0, 299, 300, 499, 500, 699, 700, 899, 900, 1000].map(tier).to_vec();
+    assert_eq!(tiers, [1, 1, 2, 2, 3, 3, 4, 4, 5, 5]);
+}
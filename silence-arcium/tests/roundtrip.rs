@@ -0,0 +1,208 @@
+//! Round trips through a simulated MXE that evaluates the circuits'
+//! plaintext semantics.
+//!
+//! `StreamCipher` stands in for the Rescue cipher; it exercises key
+//! agreement, nonce handling and packing, not the cipher itself.
+
+use sha2::{Digest, Sha256};
+use silence_arcium::circuits::*;
+use silence_arcium::{reference, FieldElement, KeyPair, Observer, Pack, Sender, SharedCipher};
+
+struct StreamCipher([u8; 32]);
+
+impl StreamCipher {
+    fn apply(&self, elements: &[FieldElement], nonce: &[u8; 16]) -> Vec<FieldElement> {
+        elements
+            .iter()
+            .enumerate()
+            .map(|(i, element)| {
+                let pad: [u8; 32] = Sha256::new()
+                    .chain_update(self.0)
+                    .chain_update(nonce)
+                    .chain_update((i as u32).to_le_bytes())
+                    .finalize()
+                    .into();
+                std::array::from_fn(|j| element[j] ^ pad[j])
+            })
+            .collect()
+    }
+}
+
+impl SharedCipher for StreamCipher {
+    fn new(shared_secret: &[u8; 32]) -> Self {
+        Self(*shared_secret)
+    }
+
+    fn encrypt(&self, plaintext: &[FieldElement], nonce: &[u8; 16]) -> Vec<FieldElement> {
+        self.apply(plaintext, nonce)
+    }
+
+    fn decrypt(&self, ciphertext: &[FieldElement], nonce: &[u8; 16]) -> Vec<FieldElement> {
+        self.apply(ciphertext, nonce)
+    }
+}
+
+/// Decrypts an input, runs `circuit` and encrypts the result to the observer
+struct Mxe(KeyPair);
+
+impl Mxe {
+    fn run<I: Pack, O: Pack>(
+        &self,
+        input: &silence_arcium::Encrypted,
+        observer_pub_key: &[u8; 32],
+        observer_nonce: u128,
+        circuit: impl FnOnce(I) -> O,
+    ) -> (Vec<FieldElement>, [u8; 16]) {
+        let cipher = StreamCipher::new(&self.0.shared_secret(&input.public_key));
+        let plaintext = cipher.decrypt(&input.ciphertexts, &input.nonce.to_le_bytes());
+        let output = circuit(I::from_elements(&plaintext).expect("valid input"));
+        let nonce = (observer_nonce + 1).to_le_bytes();
+        let cipher = StreamCipher::new(&self.0.shared_secret(observer_pub_key));
+        (cipher.encrypt(&output.to_elements(), &nonce), nonce)
+    }
+}
+
+fn hex32(s: &str) -> [u8; 32] {
+    let bytes: Vec<u8> = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect();
+    bytes.try_into().unwrap()
+}
+
+#[test]
+fn x25519_matches_rfc7748() {
+    let alice = KeyPair::from_secret(hex32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a"));
+    let bob = KeyPair::from_secret(hex32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb"));
+    assert_eq!(
+        alice.public_key(),
+        hex32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert_eq!(
+        bob.public_key(),
+        hex32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+    let shared = hex32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(alice.shared_secret(&bob.public_key()), shared);
+    assert_eq!(bob.shared_secret(&alice.public_key()), shared);
+}
+
+#[test]
+fn layouts_match_circuit_fields() {
+    assert_eq!(IntentAmounts::LEN, 2);
+    assert_eq!(IntentVerification::LEN, 3);
+    assert_eq!(InferenceInput::LEN, 34);
+    assert_eq!(AttestationData::LEN, 97);
+    assert_eq!(ShieldedTransfer::LEN, 65);
+    assert_eq!(PrivacyProof::LEN, 33);
+
+    let amounts = IntentAmounts {
+        source_amount: u128::MAX,
+        destination_amount: 7,
+    };
+    let elements = amounts.to_elements();
+    assert_eq!(elements[0][..16], [0xff; 16]);
+    assert_eq!(elements[0][16..], [0; 16]);
+    assert_eq!(elements[1][0], 7);
+    assert_eq!(IntentAmounts::from_elements(&elements), Some(amounts));
+}
+
+#[test]
+fn unpack_rejects_out_of_range_elements() {
+    let mut two = [0u8; 32];
+    two[0] = 2;
+    assert_eq!(bool::from_elements(&[two]), None);
+
+    let mut big = [0u8; 32];
+    big[1] = 1;
+    assert_eq!(u8::from_elements(&[big]), None);
+    assert_eq!(u16::from_elements(&[big]), Some(256));
+
+    let mut high = [0u8; 32];
+    high[16] = 1;
+    assert_eq!(u128::from_elements(&[high]), None);
+    assert_eq!(u64::from_elements(&[[0; 32], [0; 32]]), None);
+}
+
+#[test]
+fn verify_intent_amounts_round_trip() {
+    let mxe = Mxe(KeyPair::generate());
+    let mxe_public_key = mxe.0.public_key();
+    let sender = Sender::<StreamCipher>::new(&mxe_public_key);
+    let observer = Observer::<StreamCipher>::new(&mxe_public_key);
+
+    let input = sender.encrypt(&IntentAmounts {
+        source_amount: 1_000_000,
+        destination_amount: 950_000,
+    });
+    assert_eq!(input.public_key, sender.public_key());
+    assert!(input.ciphertext_array::<2>().is_some());
+    assert!(input.ciphertext_array::<3>().is_none());
+
+    let (expected_rate_bps, min_source_amount, protocol_fee_bps) = (9_600u64, 500_000u128, 30u64);
+    let (ciphertexts, nonce) = mxe.run(
+        &input,
+        &observer.public_key(),
+        observer.nonce(),
+        |amounts: IntentAmounts| {
+            reference::verify_intent_amounts(&amounts, expected_rate_bps, min_source_amount, protocol_fee_bps)
+        },
+    );
+
+    let result: IntentVerification = observer.decrypt(&ciphertexts, &nonce).unwrap();
+    assert_eq!(
+        result,
+        IntentVerification {
+            rate_valid: true,
+            amount_sufficient: true,
+            fee: 3_000,
+        }
+    );
+
+    // Only the observer named in the call can read the output
+    let other = Observer::<StreamCipher>::new(&mxe_public_key);
+    assert_ne!(other.decrypt::<IntentVerification>(&ciphertexts, &nonce), Some(result));
+}
+
+#[test]
+fn privacy_proof_round_trip() {
+    let mxe = Mxe(KeyPair::generate());
+    let sender = Sender::<StreamCipher>::new(&mxe.0.public_key());
+    let observer = Observer::<StreamCipher>::new(&mxe.0.public_key());
+    let transfer = ShieldedTransfer {
+        amount: 42,
+        blinding: [9; 32],
+        recipient_hash: [3; 32],
+    };
+
+    let input = sender.encrypt(&transfer);
+    assert_eq!(input.ciphertexts.len(), ShieldedTransfer::LEN);
+    let max_amount = 41u128;
+    let (ciphertexts, nonce) = mxe.run(
+        &input,
+        &observer.public_key(),
+        observer.nonce(),
+        |t: ShieldedTransfer| reference::generate_privacy_proof(&t, max_amount),
+    );
+
+    let proof: PrivacyProof = observer.decrypt(&ciphertexts, &nonce).unwrap();
+    assert_eq!(proof.commitment, [9; 32]);
+    assert!(!proof.range_valid);
+}
+
+#[test]
+fn fresh_nonce_per_input() {
+    let mxe = KeyPair::generate();
+    let sender = Sender::<StreamCipher>::new(&mxe.public_key());
+    let metrics = SolverMetrics {
+        total_executed: 10,
+        successful: 9,
+        failed: 1,
+        total_volume: 5_000,
+    };
+    let first = sender.encrypt(&metrics);
+    let second = sender.encrypt(&metrics);
+    assert_ne!(first.nonce, second.nonce);
+    assert_ne!(first.ciphertexts, second.ciphertexts);
+    assert_eq!(sender.encrypt_with_nonce(&metrics, 5), sender.encrypt_with_nonce(&metrics, 5));
+}
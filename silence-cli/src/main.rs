@@ -0,0 +1,623 @@
+mod rpc;
+mod render;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use project_silence::instruction as args;
+use project_silence_client::accounts::{
+    decode, BridgeConfig, Intent, ModelDirectory, ModelIndex, ModelListing, ModelMetadata, Solver,
+};
+use project_silence_client::discovery::{index_pages, ModelFilter};
+use project_silence_client::events::parse_logs;
+use project_silence_client::{arcium, instructions, pda, ID};
+use serde_json::{json, Value};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use std::path::{Path, PathBuf};
+
+use rpc::RpcClient;
+
+/// Offset of `Intent.creator`: discriminator + `intent_id`
+const INTENT_CREATOR_OFFSET: usize = 8 + 8;
+
+#[derive(Parser)]
+#[command(name = "silence", about = "Project Silence admin and operator CLI")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, short = 'u', env = "ANCHOR_PROVIDER_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Signer and fee payer (Solana CLI JSON format)
+    #[arg(long, short = 'k', env = "ANCHOR_WALLET", default_value = "~/.config/solana/id.json")]
+    keypair: PathBuf,
+
+    #[arg(long, default_value = "confirmed")]
+    commitment: String,
+
+    /// Simulate transactions instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the bridge config and fee vault
+    InitBridge {
+        #[arg(long)]
+        min_solver_stake: u64,
+        #[arg(long)]
+        protocol_fee_bps: u16,
+    },
+    /// Initialize Arcium computation definitions, skipping existing ones
+    InitCompDefs {
+        /// Only these circuits (default: all)
+        #[arg(long, value_enum)]
+        only: Vec<Circuit>,
+    },
+    /// Print the bridge configuration
+    Config,
+    /// Set the protocol fee in basis points
+    SetProtocolFee { fee_bps: u16 },
+    #[command(subcommand)]
+    Model(ModelCommand),
+    #[command(subcommand)]
+    Solver(SolverCommand),
+    #[command(subcommand)]
+    Intent(IntentCommand),
+}
+
+#[derive(Subcommand)]
+enum ModelCommand {
+    /// Register a model owned by the signer
+    Register {
+        #[arg(long)]
+        model_id: u64,
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        /// 0=LLM, 1=Embedding, 2=Classifier, 3=Other
+        #[arg(long, default_value_t = 0)]
+        model_type: u8,
+        #[arg(long)]
+        version: String,
+        #[arg(long)]
+        tee_required: bool,
+        #[arg(long)]
+        attestation_required: bool,
+        /// Lamports charged per inference
+        #[arg(long)]
+        cost_per_inference: u64,
+    },
+    /// Update fields of a model the signer owns or is a delegate of
+    Update {
+        #[arg(long)]
+        model_id: u64,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        cost_per_inference: Option<u64>,
+        #[arg(long)]
+        active: Option<bool>,
+    },
+    /// Publish a version and make it the model's current one
+    Publish {
+        model_id: u64,
+        version: String,
+        /// Commitment to the released weights, hex
+        #[arg(long, value_parser = parse_hash)]
+        weights_commitment: [u8; 32],
+        /// Hash of the release notes, hex
+        #[arg(long, value_parser = parse_hash)]
+        changelog_hash: [u8; 32],
+    },
+    /// Stop new requests from pinning a version
+    Deprecate { model_id: u64, version: String },
+    /// Propose a new owner; without `--new-owner` the pending proposal is cancelled
+    Transfer {
+        model_id: u64,
+        #[arg(long)]
+        new_owner: Option<Pubkey>,
+    },
+    /// Accept ownership proposed to the signer
+    Accept { model_id: u64 },
+    /// Replace the delegates allowed to update the model
+    Delegates {
+        model_id: u64,
+        /// Delegate keys, comma separated; empty removes all
+        #[arg(long, value_delimiter = ',')]
+        delegates: Vec<Pubkey>,
+    },
+    /// Replace the operators that process the model's inferences (owner or delegate)
+    Operators {
+        model_id: u64,
+        /// Operator keys, comma separated; empty removes all
+        #[arg(long, value_delimiter = ',')]
+        operators: Vec<Pubkey>,
+    },
+    /// Set the address paid for inferences; without `--payout` the owner is paid
+    Payout {
+        model_id: u64,
+        #[arg(long)]
+        payout: Option<Pubkey>,
+    },
+    /// Offer or change a subscription plan (N inferences per epoch for a fixed price)
+    Plan {
+        model_id: u64,
+        #[arg(long)]
+        plan_id: u8,
+        #[arg(long)]
+        inferences_per_epoch: u32,
+        /// Lamports per epoch
+        #[arg(long)]
+        price: u64,
+        /// Stop new subscriptions; paid epochs are unaffected
+        #[arg(long)]
+        inactive: bool,
+    },
+    /// Claim what `user` has spent from prepaid credits with the signer
+    Claim { user: Pubkey },
+    /// List a model registered before the discovery index
+    Index { model_id: u64 },
+    Show { model_id: u64 },
+    /// List indexed models, in listing order
+    List {
+        /// 0=LLM, 1=Embedding, 2=Classifier, 3=Other
+        #[arg(long)]
+        model_type: Option<u8>,
+        #[arg(long)]
+        tee_required: Option<bool>,
+        #[arg(long)]
+        active: Option<bool>,
+        /// Lowest cost per inference, inclusive
+        #[arg(long)]
+        min_cost: Option<u64>,
+        /// Highest cost per inference, inclusive
+        #[arg(long)]
+        max_cost: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SolverCommand {
+    /// Register the signer as a solver
+    Register {
+        /// Supported destination chain ids, comma separated
+        #[arg(long, value_delimiter = ',', required = true)]
+        chains: Vec<u32>,
+    },
+    /// Deactivate a solver (bridge owner)
+    Deactivate { authority: Pubkey },
+    Show { authority: Pubkey },
+}
+
+#[derive(Subcommand)]
+enum IntentCommand {
+    Show {
+        intent_id: u64,
+    },
+    /// List intents, oldest first
+    List {
+        #[arg(long)]
+        creator: Option<Pubkey>,
+        /// created, matched, executed, settled, failed, cancelled, ...
+        #[arg(long)]
+        status: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Circuit {
+    ProcessInference,
+    VerifyIntentAmounts,
+    ComputeSettlement,
+    CalculateReputation,
+    VerifyAttestation,
+    GeneratePrivacyProof,
+}
+
+impl Circuit {
+    fn name(self) -> &'static str {
+        match self {
+            Circuit::ProcessInference => "process_inference",
+            Circuit::VerifyIntentAmounts => "verify_intent_amounts",
+            Circuit::ComputeSettlement => "compute_settlement",
+            Circuit::CalculateReputation => "calculate_reputation",
+            Circuit::VerifyAttestation => "verify_attestation",
+            Circuit::GeneratePrivacyProof => "generate_privacy_proof",
+        }
+    }
+
+    fn offset(self) -> u32 {
+        match self {
+            Circuit::ProcessInference => arcium::COMP_DEF_OFFSET_PROCESS_INFERENCE,
+            Circuit::VerifyIntentAmounts => arcium::COMP_DEF_OFFSET_VERIFY_INTENT_AMOUNTS,
+            Circuit::ComputeSettlement => arcium::COMP_DEF_OFFSET_COMPUTE_SETTLEMENT,
+            Circuit::CalculateReputation => arcium::COMP_DEF_OFFSET_CALCULATE_REPUTATION,
+            Circuit::VerifyAttestation => arcium::COMP_DEF_OFFSET_VERIFY_ATTESTATION,
+            Circuit::GeneratePrivacyProof => arcium::COMP_DEF_OFFSET_GENERATE_PRIVACY_PROOF,
+        }
+    }
+
+    fn instruction(self, payer: &Pubkey) -> Instruction {
+        match self {
+            Circuit::ProcessInference => instructions::init_process_inference_comp_def(payer),
+            Circuit::VerifyIntentAmounts => instructions::init_verify_intent_amounts_comp_def(payer),
+            Circuit::ComputeSettlement => instructions::init_compute_settlement_comp_def(payer),
+            Circuit::CalculateReputation => instructions::init_calculate_reputation_comp_def(payer),
+            Circuit::VerifyAttestation => instructions::init_verify_attestation_comp_def(payer),
+            Circuit::GeneratePrivacyProof => instructions::init_generate_privacy_proof_comp_def(payer),
+        }
+    }
+}
+
+struct Client {
+    rpc: RpcClient,
+    keypair_path: PathBuf,
+    dry_run: bool,
+}
+
+impl Client {
+    /// The signer is only loaded by commands that send transactions
+    fn signer(&self) -> Result<Keypair> {
+        read_keypair(&self.keypair_path)
+    }
+
+    /// Send (or simulate) one instruction, returning a JSON report
+    async fn submit(&self, name: &str, ix: Instruction, signer: &Keypair) -> Result<Value> {
+        if self.dry_run {
+            let simulation = self.rpc.simulate(&[ix], signer).await?;
+            let events: Vec<&str> = parse_logs(&simulation.logs).iter().map(|event| event.name()).collect();
+            return Ok(json!({
+                "instruction": name,
+                "dry_run": true,
+                "success": simulation.err.is_none(),
+                "err": simulation.err,
+                "units_consumed": simulation.units_consumed,
+                "events": events,
+                "logs": simulation.logs,
+            }));
+        }
+        let signature = self.rpc.send_and_confirm(&[ix], signer).await?;
+        Ok(json!({ "instruction": name, "signature": signature.to_string() }))
+    }
+
+    async fn fetch<T: AccountDeserialize>(&self, address: &Pubkey, what: &str) -> Result<T> {
+        let data = self
+            .rpc
+            .account_data(address)
+            .await?
+            .ok_or_else(|| anyhow!("{what} {address} does not exist"))?;
+        decode(&data).map_err(|e| anyhow!("decode {what} {address}: {e}"))
+    }
+
+    /// Index page the next listed model goes to
+    async fn next_index_page(&self) -> Result<u32> {
+        let address = pda::model_directory();
+        if self.rpc.account_data(&address).await?.is_none() {
+            return Ok(0);
+        }
+        let directory: ModelDirectory = self.fetch(&address, "model directory").await?;
+        Ok(directory.next_page())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let client = Client {
+        rpc: RpcClient::new(&cli.url, &cli.commitment),
+        keypair_path: cli.keypair,
+        dry_run: cli.dry_run,
+    };
+    let output = run(&client, cli.command).await?;
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    if failed_simulation(&output) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run(client: &Client, command: Command) -> Result<Value> {
+    match command {
+        Command::InitBridge {
+            min_solver_stake,
+            protocol_fee_bps,
+        } => {
+            let signer = client.signer()?;
+            let ix = instructions::initialize_bridge(&signer.pubkey(), min_solver_stake, protocol_fee_bps);
+            client.submit("initialize_bridge", ix, &signer).await
+        }
+        Command::InitCompDefs { only } => {
+            let signer = client.signer()?;
+            let circuits = if only.is_empty() {
+                Circuit::value_variants().to_vec()
+            } else {
+                only
+            };
+            let mut reports = Vec::new();
+            for circuit in circuits {
+                let name = format!("init_{}_comp_def", circuit.name());
+                let address = arcium::comp_def_account(circuit.offset());
+                if client.rpc.account_data(&address).await?.is_some() {
+                    reports.push(json!({ "instruction": name, "skipped": "already initialized" }));
+                    continue;
+                }
+                reports.push(client.submit(&name, circuit.instruction(&signer.pubkey()), &signer).await?);
+            }
+            Ok(Value::Array(reports))
+        }
+        Command::Config => {
+            let address = pda::bridge_config();
+            let config: BridgeConfig = client.fetch(&address, "bridge config").await?;
+            Ok(render::bridge_config(&address, &config))
+        }
+        Command::SetProtocolFee { fee_bps } => {
+            let signer = client.signer()?;
+            let ix = instructions::set_protocol_fee(&signer.pubkey(), fee_bps);
+            client.submit("set_protocol_fee", ix, &signer).await
+        }
+        Command::Model(command) => run_model(client, command).await,
+        Command::Solver(command) => run_solver(client, command).await,
+        Command::Intent(command) => run_intent(client, command).await,
+    }
+}
+
+async fn run_model(client: &Client, command: ModelCommand) -> Result<Value> {
+    match command {
+        ModelCommand::Register {
+            model_id,
+            name,
+            description,
+            model_type,
+            version,
+            tee_required,
+            attestation_required,
+            cost_per_inference,
+        } => {
+            let signer = client.signer()?;
+            let ix = instructions::register_model(
+                &signer.pubkey(),
+                client.next_index_page().await?,
+                args::RegisterModel {
+                    model_id,
+                    name,
+                    description,
+                    model_type,
+                    version,
+                    tee_required,
+                    attestation_required,
+                    cost_per_inference,
+                },
+            );
+            client.submit("register_model", ix, &signer).await
+        }
+        ModelCommand::Update {
+            model_id,
+            name,
+            description,
+            cost_per_inference,
+            active,
+        } => {
+            let signer = client.signer()?;
+            let address = pda::model_listing(model_id);
+            let index_page = match client.rpc.account_data(&address).await? {
+                Some(_) => Some(client.fetch::<ModelListing>(&address, "model listing").await?.page),
+                None => None,
+            };
+            let ix = instructions::update_model(
+                &signer.pubkey(),
+                model_id,
+                index_page,
+                args::UpdateModel {
+                    name,
+                    description,
+                    cost_per_inference,
+                    is_active: active,
+                },
+            );
+            client.submit("update_model", ix, &signer).await
+        }
+        ModelCommand::Publish {
+            model_id,
+            version,
+            weights_commitment,
+            changelog_hash,
+        } => {
+            let signer = client.signer()?;
+            let ix = instructions::publish_model_version(
+                &signer.pubkey(),
+                model_id,
+                &version,
+                weights_commitment,
+                changelog_hash,
+            );
+            client.submit("publish_model_version", ix, &signer).await
+        }
+        ModelCommand::Deprecate { model_id, version } => {
+            let signer = client.signer()?;
+            let ix = instructions::deprecate_model_version(&signer.pubkey(), model_id, &version);
+            client.submit("deprecate_model_version", ix, &signer).await
+        }
+        ModelCommand::Transfer { model_id, new_owner } => {
+            let signer = client.signer()?;
+            let ix = instructions::transfer_model_ownership(&signer.pubkey(), model_id, new_owner);
+            client.submit("transfer_model_ownership", ix, &signer).await
+        }
+        ModelCommand::Accept { model_id } => {
+            let signer = client.signer()?;
+            let ix = instructions::accept_model_ownership(&signer.pubkey(), model_id);
+            client.submit("accept_model_ownership", ix, &signer).await
+        }
+        ModelCommand::Delegates { model_id, delegates } => {
+            let signer = client.signer()?;
+            let ix = instructions::set_model_delegates(&signer.pubkey(), model_id, delegates);
+            client.submit("set_model_delegates", ix, &signer).await
+        }
+        ModelCommand::Operators { model_id, operators } => {
+            let signer = client.signer()?;
+            let ix = instructions::set_model_operators(&signer.pubkey(), model_id, operators);
+            client.submit("set_model_operators", ix, &signer).await
+        }
+        ModelCommand::Payout { model_id, payout } => {
+            let signer = client.signer()?;
+            let ix = instructions::set_model_payout(&signer.pubkey(), model_id, payout);
+            client.submit("set_model_payout", ix, &signer).await
+        }
+        ModelCommand::Plan {
+            model_id,
+            plan_id,
+            inferences_per_epoch,
+            price,
+            inactive,
+        } => {
+            let signer = client.signer()?;
+            let ix = instructions::set_subscription_plan(
+                &signer.pubkey(),
+                model_id,
+                args::SetSubscriptionPlan {
+                    plan_id,
+                    inferences_per_epoch,
+                    price,
+                    is_active: !inactive,
+                },
+            );
+            client.submit("set_subscription_plan", ix, &signer).await
+        }
+        ModelCommand::Claim { user } => {
+            let signer = client.signer()?;
+            let ix = instructions::claim_credit_earnings(&signer.pubkey(), &user);
+            client.submit("claim_credit_earnings", ix, &signer).await
+        }
+        ModelCommand::Index { model_id } => {
+            let signer = client.signer()?;
+            let page = client.next_index_page().await?;
+            let ix = instructions::index_model(&signer.pubkey(), model_id, page);
+            client.submit("index_model", ix, &signer).await
+        }
+        ModelCommand::Show { model_id } => {
+            let address = pda::model(model_id);
+            let model: ModelMetadata = client.fetch(&address, "model").await?;
+            Ok(render::model(&address, &model))
+        }
+        ModelCommand::List {
+            model_type,
+            tee_required,
+            active,
+            min_cost,
+            max_cost,
+        } => {
+            let filter = ModelFilter {
+                model_type,
+                tee_required,
+                is_active: active,
+                min_cost,
+                max_cost,
+            };
+            let address = pda::model_directory();
+            if client.rpc.account_data(&address).await?.is_none() {
+                return Ok(Value::Array(vec![]));
+            }
+            let directory: ModelDirectory = client.fetch(&address, "model directory").await?;
+            let mut pages = Vec::new();
+            for page in index_pages(&directory) {
+                pages.push(client.fetch::<ModelIndex>(&page, "model index").await?);
+            }
+            Ok(Value::Array(
+                filter
+                    .apply(&pages)
+                    .map(|model| render::model_summary(&pda::model(model.model_id), model))
+                    .collect(),
+            ))
+        }
+    }
+}
+
+async fn run_solver(client: &Client, command: SolverCommand) -> Result<Value> {
+    match command {
+        SolverCommand::Register { chains } => {
+            let signer = client.signer()?;
+            let ix = instructions::register_solver(&signer.pubkey(), chains);
+            client.submit("register_solver", ix, &signer).await
+        }
+        SolverCommand::Deactivate { authority } => {
+            let signer = client.signer()?;
+            let ix = instructions::deactivate_solver(&signer.pubkey(), &authority);
+            client.submit("deactivate_solver", ix, &signer).await
+        }
+        SolverCommand::Show { authority } => {
+            let address = pda::solver(&authority);
+            let solver: Solver = client.fetch(&address, "solver").await?;
+            Ok(render::solver(&address, &solver))
+        }
+    }
+}
+
+async fn run_intent(client: &Client, command: IntentCommand) -> Result<Value> {
+    match command {
+        IntentCommand::Show { intent_id } => {
+            let address = pda::intent(intent_id);
+            let intent: Intent = client.fetch(&address, "intent").await?;
+            Ok(render::intent(&address, &intent))
+        }
+        IntentCommand::List { creator, status } => {
+            let creator_filter = creator.map(|creator| creator.to_bytes());
+            let filters: Vec<(usize, &[u8])> = creator_filter
+                .as_ref()
+                .map(|bytes| (INTENT_CREATOR_OFFSET, &bytes[..]))
+                .into_iter()
+                .collect();
+            let accounts = client.rpc.program_accounts(&ID, Intent::DISCRIMINATOR, &filters).await?;
+            let mut intents = Vec::with_capacity(accounts.len());
+            for (address, data) in accounts {
+                let intent: Intent = decode(&data).map_err(|e| anyhow!("decode intent {address}: {e}"))?;
+                if status
+                    .as_deref()
+                    .is_some_and(|status| render::intent_status(&intent.status) != status)
+                {
+                    continue;
+                }
+                intents.push((address, intent));
+            }
+            intents.sort_by_key(|(_, intent)| intent.intent_id);
+            Ok(Value::Array(
+                intents.iter().map(|(address, intent)| render::intent(address, intent)).collect(),
+            ))
+        }
+    }
+}
+
+/// A `--dry-run` report (or list of reports) whose simulation failed
+fn failed_simulation(output: &Value) -> bool {
+    match output {
+        Value::Array(reports) => reports.iter().any(failed_simulation),
+        report => report["dry_run"] == true && report["success"] == false,
+    }
+}
+
+/// Parse a 32-byte hash given as hex
+fn parse_hash(value: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(value)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow!("expected 32 bytes, got {}", bytes.len()))
+}
+
+/// Read a Solana CLI keypair file, expanding a leading `~/`
+fn read_keypair(path: &Path) -> Result<Keypair> {
+    let path = match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    };
+    if !path.exists() {
+        bail!("keypair {} does not exist", path.display());
+    }
+    read_keypair_file(&path).map_err(|e| anyhow!("read keypair {}: {e}", path.display()))
+}
@@ -0,0 +1,118 @@
+//! JSON views of program accounts.
+
+use project_silence_client::accounts::{BridgeConfig, Intent, IntentStatus, ModelMetadata, ModelSummary, Solver};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn intent_status(status: &IntentStatus) -> &'static str {
+    match status {
+        IntentStatus::Created => "created",
+        IntentStatus::Matched => "matched",
+        IntentStatus::Executing => "executing",
+        IntentStatus::Executed => "executed",
+        IntentStatus::Settling => "settling",
+        IntentStatus::Settled => "settled",
+        IntentStatus::Failed => "failed",
+        IntentStatus::Disputed => "disputed",
+        IntentStatus::Cancelled => "cancelled",
+    }
+}
+
+pub fn bridge_config(address: &Pubkey, config: &BridgeConfig) -> Value {
+    json!({
+        "address": address.to_string(),
+        "owner": config.owner.to_string(),
+        "guardian": config.guardian.to_string(),
+        "paused": config.paused,
+        "min_solver_stake": config.min_solver_stake,
+        "protocol_fee_bps": config.protocol_fee_bps,
+        "staker_fee_share_bps": config.staker_fee_share_bps,
+        "total_volume": config.total_volume,
+        "total_intents": config.total_intents,
+        "active_solvers": config.active_solvers,
+        "total_stake": config.total_stake,
+        "rate_limit_window_secs": config.rate_limit_window_secs,
+        "solver_volume_cap": config.solver_volume_cap,
+        "heartbeat_timeout_secs": config.heartbeat_timeout_secs,
+        "default_execution_secs": config.default_execution_secs,
+        "max_execution_secs": config.max_execution_secs,
+    })
+}
+
+pub fn intent(address: &Pubkey, intent: &Intent) -> Value {
+    json!({
+        "address": address.to_string(),
+        "intent_id": intent.intent_id,
+        "creator": intent.creator.to_string(),
+        "status": intent_status(&intent.status),
+        "source_chain_id": intent.source_chain_id,
+        "destination_chain_id": intent.destination_chain_id,
+        "source_amount": intent.source_amount,
+        "remaining_amount": intent.remaining_amount(),
+        "filled_amount": intent.filled_amount,
+        "settled_amount": intent.settled_amount,
+        "refunded_amount": intent.refunded_amount,
+        "matched_fills": intent.matched_fills,
+        "executed_fills": intent.executed_fills,
+        "allow_partial_fill": intent.allow_partial_fill,
+        "is_shielded": intent.is_shielded,
+        "source_token": intent.source_token.to_string(),
+        "destination_token_hash": hex::encode(intent.destination_token_hash),
+        "destination_amount_commitment": hex::encode(intent.destination_amount_commitment),
+        "recipient_hash": hex::encode(intent.recipient_hash),
+        "destination_tx_hash": hex::encode(intent.destination_tx_hash),
+        "created_at": intent.created_at,
+        "expires_at": intent.expires_at,
+        "executed_at": intent.executed_at,
+    })
+}
+
+pub fn model(address: &Pubkey, model: &ModelMetadata) -> Value {
+    json!({
+        "address": address.to_string(),
+        "model_id": model.model_id,
+        "name": model.name,
+        "description": model.description,
+        "model_type": model.model_type,
+        "version": model.version,
+        "owner": model.owner.to_string(),
+        "tee_required": model.tee_required,
+        "attestation_required": model.attestation_required,
+        "cost_per_inference": model.cost_per_inference,
+        "is_active": model.is_active,
+        "created_at": model.created_at,
+        "updated_at": model.updated_at,
+    })
+}
+
+pub fn model_summary(address: &Pubkey, model: &ModelSummary) -> Value {
+    json!({
+        "address": address.to_string(),
+        "model_id": model.model_id,
+        "model_type": model.model_type,
+        "tee_required": model.tee_required,
+        "cost_per_inference": model.cost_per_inference,
+        "is_active": model.is_active,
+    })
+}
+
+pub fn solver(address: &Pubkey, solver: &Solver) -> Value {
+    json!({
+        "address": address.to_string(),
+        "authority": solver.solver_id.to_string(),
+        "is_active": solver.is_active,
+        "supported_chains": solver.supported_chains,
+        "stake": solver.stake,
+        "reputation_score": solver.reputation_score,
+        "fee_bps": solver.fee_bps,
+        "min_fill_amount": solver.min_fill_amount,
+        "total_intents_executed": solver.total_intents_executed,
+        "successful_intents": solver.successful_intents,
+        "failed_intents": solver.failed_intents,
+        "total_volume": solver.total_volume,
+        "pending_rewards": solver.pending_rewards,
+        "metadata_uri": solver.metadata_uri,
+        "registered_at": solver.registered_at,
+        "last_heartbeat_at": solver.last_heartbeat_at,
+    })
+}
@@ -0,0 +1,203 @@
+//! Solana JSON-RPC calls used by the CLI.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How many times a sent transaction's status is polled before giving up
+const CONFIRM_ATTEMPTS: u32 = 60;
+const CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Result of `simulateTransaction`
+pub struct Simulation {
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+    commitment: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>, commitment: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            commitment: commitment.into(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .json()
+            .await
+            .with_context(|| format!("{method} returned invalid JSON"))?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+
+    /// Raw account data, or `None` if the account does not exist
+    pub async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self
+            .call(
+                "getAccountInfo",
+                json!([address.to_string(), { "encoding": "base64", "commitment": self.commitment }]),
+            )
+            .await?;
+        let Some(data) = result["value"]["data"][0].as_str() else {
+            return Ok(None);
+        };
+        Ok(Some(decode_base64(data)?))
+    }
+
+    /// Accounts owned by `program` whose data starts with `discriminator`
+    /// and matches every `(offset, bytes)` filter
+    pub async fn program_accounts(
+        &self,
+        program: &Pubkey,
+        discriminator: &[u8],
+        filters: &[(usize, &[u8])],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let memcmp = std::iter::once((0, discriminator))
+            .chain(filters.iter().copied())
+            .map(|(offset, bytes)| json!({ "memcmp": { "offset": offset, "bytes": bs58::encode(bytes).into_string() } }))
+            .collect::<Vec<_>>();
+        let result = self
+            .call(
+                "getProgramAccounts",
+                json!([program.to_string(), {
+                    "encoding": "base64",
+                    "commitment": self.commitment,
+                    "filters": memcmp
+                }]),
+            )
+            .await?;
+        let entries = result.as_array().ok_or_else(|| anyhow!("getProgramAccounts returned no accounts"))?;
+        entries
+            .iter()
+            .map(|entry| {
+                let address = entry["pubkey"]
+                    .as_str()
+                    .and_then(|s| Pubkey::from_str(s).ok())
+                    .ok_or_else(|| anyhow!("getProgramAccounts returned an invalid pubkey"))?;
+                let data = entry["account"]["data"][0]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("getProgramAccounts returned no data for {address}"))?;
+                Ok((address, decode_base64(data)?))
+            })
+            .collect()
+    }
+
+    async fn latest_blockhash(&self) -> Result<Hash> {
+        let result = self
+            .call("getLatestBlockhash", json!([{ "commitment": self.commitment }]))
+            .await?;
+        let hash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("getLatestBlockhash returned no blockhash"))?;
+        Hash::from_str(hash).map_err(|e| anyhow!("invalid blockhash: {e}"))
+    }
+
+    async fn signed(&self, instructions: &[Instruction], payer: &Keypair) -> Result<String> {
+        let blockhash = self.latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[payer], blockhash);
+        let wire = bincode::serialize(&tx).context("serialize transaction")?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(wire))
+    }
+
+    /// Sign, send and wait for confirmation of a transaction
+    pub async fn send_and_confirm(&self, instructions: &[Instruction], payer: &Keypair) -> Result<Signature> {
+        let encoded = self.signed(instructions, payer).await?;
+        let result = self
+            .call(
+                "sendTransaction",
+                json!([encoded, { "encoding": "base64", "preflightCommitment": self.commitment }]),
+            )
+            .await?;
+        let signature = result
+            .as_str()
+            .and_then(|s| Signature::from_str(s).ok())
+            .ok_or_else(|| anyhow!("sendTransaction returned no signature"))?;
+        self.confirm(&signature).await?;
+        Ok(signature)
+    }
+
+    /// Run a transaction against current state without submitting it
+    pub async fn simulate(&self, instructions: &[Instruction], payer: &Keypair) -> Result<Simulation> {
+        let encoded = self.signed(instructions, payer).await?;
+        let result = self
+            .call(
+                "simulateTransaction",
+                json!([encoded, {
+                    "encoding": "base64",
+                    "commitment": self.commitment,
+                    "replaceRecentBlockhash": true,
+                    "sigVerify": false
+                }]),
+            )
+            .await?;
+        let value = &result["value"];
+        Ok(Simulation {
+            err: value.get("err").filter(|err| !err.is_null()).cloned(),
+            logs: serde_json::from_value(value["logs"].clone()).unwrap_or_default(),
+            units_consumed: value["unitsConsumed"].as_u64(),
+        })
+    }
+
+    async fn confirm(&self, signature: &Signature) -> Result<()> {
+        for _ in 0..CONFIRM_ATTEMPTS {
+            let result = self
+                .call("getSignatureStatuses", json!([[signature.to_string()]]))
+                .await?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {signature} failed: {}", status["err"]);
+                }
+                let level = status["confirmationStatus"].as_str().unwrap_or_default();
+                if commitment_rank(level) >= commitment_rank(&self.commitment) {
+                    return Ok(());
+                }
+            }
+            tokio::time::sleep(CONFIRM_INTERVAL).await;
+        }
+        bail!("transaction {signature} was not confirmed in time")
+    }
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .context("account data is not base64")
+}
+
+fn commitment_rank(level: &str) -> u8 {
+    match level {
+        "processed" => 1,
+        "confirmed" => 2,
+        "finalized" => 3,
+        _ => 0,
+    }
+}
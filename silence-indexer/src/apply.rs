@@ -0,0 +1,430 @@
+//! Folding decoded events into the derived tables.
+//!
+//! Intent bookkeeping mirrors the program (`filled_amount`,
+//! `matched_fills`, ... and `Intent::refresh_status`), so the indexed
+//! status matches the account without reading it.
+
+use anyhow::Result;
+use project_silence_client::events::ProgramEvent;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Where an event was emitted
+pub struct At<'a> {
+    pub signature: &'a str,
+    pub slot: u64,
+}
+
+pub fn apply(conn: &Connection, event: &ProgramEvent, at: &At) -> Result<()> {
+    let slot = at.slot as i64;
+    match event {
+        // ---- Bridge settings ----
+        ProgramEvent::BridgeInitialized(e) => {
+            set(conn, "owner", &e.owner.to_string(), slot)?;
+            set(conn, "min_solver_stake", &e.min_solver_stake.to_string(), slot)?;
+            set(conn, "protocol_fee_bps", &e.protocol_fee_bps.to_string(), slot)?;
+            set(conn, "guardian", &e.owner.to_string(), slot)?;
+            set(conn, "paused", "false", slot)?;
+        }
+        ProgramEvent::ProtocolFeeUpdated(e) => set(conn, "protocol_fee_bps", &e.fee_bps.to_string(), slot)?,
+        ProgramEvent::FeeSplitUpdated(e) => {
+            set(conn, "staker_fee_share_bps", &e.staker_fee_share_bps.to_string(), slot)?
+        }
+        ProgramEvent::GuardianUpdated(e) => set(conn, "guardian", &e.guardian.to_string(), slot)?,
+        ProgramEvent::BridgePauseUpdated(e) => set(conn, "paused", &e.paused.to_string(), slot)?,
+        ProgramEvent::HeartbeatTimeoutUpdated(e) => {
+            set(conn, "heartbeat_timeout_secs", &e.timeout_secs.to_string(), slot)?
+        }
+        ProgramEvent::ExecutionWindowUpdated(e) => {
+            set(
+                conn,
+                "default_execution_secs",
+                &e.default_execution_secs.to_string(),
+                slot,
+            )?;
+            set(conn, "max_execution_secs", &e.max_execution_secs.to_string(), slot)?;
+        }
+        ProgramEvent::RateLimitsUpdated(e) => {
+            set(conn, "rate_limit_window_secs", &e.window_secs.to_string(), slot)?;
+            set(conn, "solver_volume_cap", &e.solver_volume_cap.to_string(), slot)?;
+        }
+        ProgramEvent::RelayersUpdated(e) => {
+            set(conn, "relayer_count", &e.relayer_count.to_string(), slot)?;
+            set(conn, "relayer_threshold", &e.threshold.to_string(), slot)?;
+        }
+
+        // ---- Chains ----
+        ProgramEvent::ChainRegistered(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO chains (chain_id, name, enabled, updated_slot) VALUES (?1, ?2, 1, ?3)",
+                params![e.chain_id, e.name, slot],
+            )?;
+        }
+        ProgramEvent::ChainStatusUpdated(e) => {
+            conn.execute(
+                "UPDATE chains SET enabled = ?2, updated_slot = ?3 WHERE chain_id = ?1",
+                params![e.chain_id, e.enabled, slot],
+            )?;
+        }
+        ProgramEvent::ChainUpdated(e) => {
+            conn.execute(
+                "UPDATE chains SET updated_slot = ?2 WHERE chain_id = ?1",
+                params![e.chain_id, slot],
+            )?;
+        }
+
+        // ---- Intents ----
+        ProgramEvent::IntentCreated(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO intents (intent_id, creator, destination_chain_id, source_amount, is_shielded,
+                    status, created_signature, created_slot, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'created', ?6, ?7, ?7)",
+                params![
+                    e.intent_id as i64,
+                    e.creator.to_string(),
+                    e.destination_chain_id,
+                    e.source_amount as i64,
+                    e.is_shielded,
+                    at.signature,
+                    slot
+                ],
+            )?;
+        }
+        ProgramEvent::IntentMatched(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO fills (intent_id, solver, amount, status, execution_deadline, updated_slot)
+                 VALUES (?1, ?2, ?3, 'matched', ?4, ?5)",
+                params![
+                    e.intent_id as i64,
+                    e.solver.to_string(),
+                    e.fill_amount as i64,
+                    e.execution_deadline,
+                    slot
+                ],
+            )?;
+            conn.execute(
+                "UPDATE intents SET filled_amount = filled_amount + ?2, matched_fills = matched_fills + 1
+                 WHERE intent_id = ?1",
+                params![e.intent_id as i64, e.fill_amount as i64],
+            )?;
+            refresh_status(conn, e.intent_id, None, slot)?;
+        }
+        ProgramEvent::IntentExecuted(e) => {
+            conn.execute(
+                "UPDATE fills SET status = 'executed', destination_tx_hash = ?3, updated_slot = ?4
+                 WHERE intent_id = ?1 AND solver = ?2",
+                params![
+                    e.intent_id as i64,
+                    e.solver.to_string(),
+                    hex::encode(e.destination_tx_hash),
+                    slot
+                ],
+            )?;
+            conn.execute(
+                "UPDATE intents SET matched_fills = matched_fills - 1, executed_fills = executed_fills + 1
+                 WHERE intent_id = ?1",
+                [e.intent_id as i64],
+            )?;
+            refresh_status(conn, e.intent_id, None, slot)?;
+        }
+        ProgramEvent::IntentSettled(e) => {
+            let solver = e.solver.to_string();
+            conn.execute(
+                "UPDATE fills SET status = 'settled', solver_reward = ?3, protocol_fee = ?4, updated_slot = ?5
+                 WHERE intent_id = ?1 AND solver = ?2",
+                params![
+                    e.intent_id as i64,
+                    solver,
+                    e.solver_reward as i64,
+                    e.protocol_fee as i64,
+                    slot
+                ],
+            )?;
+            conn.execute(
+                "UPDATE intents SET executed_fills = executed_fills - 1, settled_amount = settled_amount + ?2
+                 WHERE intent_id = ?1",
+                params![e.intent_id as i64, e.fill_amount as i64],
+            )?;
+            refresh_status(conn, e.intent_id, None, slot)?;
+            conn.execute(
+                "UPDATE solvers SET settled_fills = settled_fills + 1, volume = volume + ?2,
+                    rewards_earned = rewards_earned + ?3
+                 WHERE solver = ?1",
+                params![solver, e.fill_amount as i64, e.solver_reward as i64],
+            )?;
+            fee(conn, "protocol_fee", e.protocol_fee, &solver, Some(e.intent_id), at)?;
+        }
+        ProgramEvent::IntentFailed(e) => {
+            let solver = e.solver.to_string();
+            let counter = match fill_status(conn, e.intent_id, &solver)?.as_deref() {
+                Some("executed") => "executed_fills",
+                _ => "matched_fills",
+            };
+            conn.execute(
+                "UPDATE fills SET status = 'failed', updated_slot = ?3 WHERE intent_id = ?1 AND solver = ?2",
+                params![e.intent_id as i64, solver, slot],
+            )?;
+            conn.execute(
+                &format!(
+                    "UPDATE intents SET {counter} = {counter} - 1, filled_amount = filled_amount - ?2,
+                        refunded_amount = refunded_amount + ?2
+                     WHERE intent_id = ?1"
+                ),
+                params![e.intent_id as i64, e.refunded as i64],
+            )?;
+            refresh_status(conn, e.intent_id, Some("failed"), slot)?;
+            failure(conn, &solver)?;
+        }
+        ProgramEvent::IntentTimedOut(e) => {
+            let solver = e.solver.to_string();
+            conn.execute(
+                "UPDATE fills SET status = 'timed_out', updated_slot = ?3 WHERE intent_id = ?1 AND solver = ?2",
+                params![e.intent_id as i64, solver, slot],
+            )?;
+            let refunded = if e.reopened { 0 } else { e.amount as i64 };
+            conn.execute(
+                "UPDATE intents SET matched_fills = matched_fills - 1, filled_amount = filled_amount - ?2,
+                    refunded_amount = refunded_amount + ?3
+                 WHERE intent_id = ?1",
+                params![e.intent_id as i64, e.amount as i64, refunded],
+            )?;
+            refresh_status(conn, e.intent_id, Some("failed"), slot)?;
+            failure(conn, &solver)?;
+        }
+        ProgramEvent::IntentCancelled(e) => {
+            conn.execute(
+                "UPDATE intents SET refunded_amount = refunded_amount + ?2 WHERE intent_id = ?1",
+                params![e.intent_id as i64, e.refunded as i64],
+            )?;
+            refresh_status(conn, e.intent_id, Some("cancelled"), slot)?;
+        }
+        ProgramEvent::IntentClosed(e) => {
+            conn.execute(
+                "UPDATE intents SET closed = 1, updated_slot = ?2 WHERE intent_id = ?1",
+                params![e.intent_id as i64, slot],
+            )?;
+        }
+
+        // ---- Solvers ----
+        ProgramEvent::SolverRegistered(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO solvers (solver, supported_chains, active, registered_slot)
+                 VALUES (?1, ?2, 1, ?3)",
+                params![e.solver_id.to_string(), chains_json(&e.supported_chains), slot],
+            )?;
+        }
+        ProgramEvent::SolverUpdated(e) => {
+            conn.execute(
+                "UPDATE solvers SET supported_chains = ?2, fee_bps = ?3 WHERE solver = ?1",
+                params![e.solver_id.to_string(), chains_json(&e.supported_chains), e.fee_bps],
+            )?;
+        }
+        ProgramEvent::SolverDeactivated(e) => {
+            conn.execute(
+                "UPDATE solvers SET active = 0 WHERE solver = ?1",
+                [e.solver_id.to_string()],
+            )?;
+        }
+        ProgramEvent::SolverReactivated(e) => {
+            conn.execute(
+                "UPDATE solvers SET active = 1 WHERE solver = ?1",
+                [e.solver_id.to_string()],
+            )?;
+        }
+        ProgramEvent::SolverHeartbeatRecorded(e) => {
+            conn.execute(
+                "UPDATE solvers SET last_heartbeat = ?2 WHERE solver = ?1",
+                params![e.solver_id.to_string(), e.timestamp],
+            )?;
+        }
+        ProgramEvent::ReputationRefreshed(e) => {
+            conn.execute(
+                "UPDATE solvers SET reputation_score = ?2 WHERE solver = ?1",
+                params![e.solver_id.to_string(), e.reputation_score],
+            )?;
+        }
+        ProgramEvent::SolverRewardsClaimed(e) => {
+            let solver = e.solver_id.to_string();
+            conn.execute(
+                "UPDATE solvers SET rewards_claimed = rewards_claimed + ?2 WHERE solver = ?1",
+                params![solver, e.amount as i64],
+            )?;
+            fee(conn, "solver_claim", e.amount, &solver, None, at)?;
+        }
+        ProgramEvent::FeesWithdrawn(e) => fee(conn, "withdrawal", e.amount, &e.recipient.to_string(), None, at)?,
+
+        // ---- Models and inference ----
+        ProgramEvent::ModelRegistered(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO models (model_id, name, owner, registered_slot, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?4)",
+                params![e.model_id as i64, e.name, e.owner.to_string(), slot],
+            )?;
+        }
+        ProgramEvent::ModelUpdated(e) => {
+            conn.execute(
+                "UPDATE models SET update_count = update_count + 1, updated_slot = ?2 WHERE model_id = ?1",
+                params![e.model_id as i64, slot],
+            )?;
+        }
+        ProgramEvent::ModelVersionPublished(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO model_versions
+                 (model_id, version, weights_commitment, changelog_hash, published_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.model_id as i64,
+                    e.version,
+                    hex::encode(e.weights_commitment),
+                    hex::encode(e.changelog_hash),
+                    slot
+                ],
+            )?;
+        }
+        ProgramEvent::ModelVersionDeprecated(e) => {
+            conn.execute(
+                "UPDATE model_versions SET deprecated_slot = ?3 WHERE model_id = ?1 AND version = ?2",
+                params![e.model_id as i64, e.version, slot],
+            )?;
+        }
+        ProgramEvent::ModelOwnershipTransferred(e) => {
+            conn.execute(
+                "UPDATE models SET owner = ?2, updated_slot = ?3 WHERE model_id = ?1",
+                params![e.model_id as i64, e.new_owner.to_string(), slot],
+            )?;
+        }
+        ProgramEvent::InferenceRequestCreated(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO requests (user, request_id, model_id, status, created_slot)
+                 VALUES (?1, ?2, ?3, 'pending', ?4)",
+                params![e.user.to_string(), e.request_id as i64, e.model_id as i64, slot],
+            )?;
+        }
+        ProgramEvent::InferenceResultStored(e) => {
+            conn.execute(
+                "UPDATE requests SET status = ?3 WHERE user = ?1 AND request_id = ?2",
+                params![
+                    e.user.to_string(),
+                    e.request_id as i64,
+                    if e.verified { "completed" } else { "failed" }
+                ],
+            )?;
+        }
+        ProgramEvent::InferenceRequestClosed(e) => {
+            conn.execute(
+                "UPDATE requests SET closed = 1 WHERE user = ?1 AND request_id = ?2",
+                params![e.user.to_string(), e.request_id as i64],
+            )?;
+        }
+        ProgramEvent::BatchInferenceCreated(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO batches (user, batch_id, model_id, prompt_count, created_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.user.to_string(),
+                    e.batch_id as i64,
+                    e.model_id as i64,
+                    e.prompt_count,
+                    slot
+                ],
+            )?;
+        }
+        ProgramEvent::BatchResultsRecorded(e) => {
+            conn.execute(
+                "UPDATE batches SET completed = completed + ?3, failed = failed + ?4 WHERE user = ?1 AND batch_id = ?2",
+                params![e.user.to_string(), e.batch_id as i64, e.completed_count, e.failed_count],
+            )?;
+        }
+        ProgramEvent::BatchClosed(e) => {
+            conn.execute(
+                "UPDATE batches SET closed = 1 WHERE user = ?1 AND batch_id = ?2",
+                params![e.user.to_string(), e.batch_id as i64],
+            )?;
+        }
+
+        // Kept in the raw event log only: per-batch totals duplicate the
+        // per-intent `IntentSettled` events, the rest carry no state
+        // tracked here
+        ProgramEvent::IntentsBatchSettled(_)
+        | ProgramEvent::VolumeCapReached(_)
+        | ProgramEvent::ReputationParamsUpdated(_)
+        | ProgramEvent::ReceiptAttested(_)
+        | ProgramEvent::ReceiptClosed(_)
+        | ProgramEvent::InferenceProcessed(_)
+        | ProgramEvent::IntentAmountsVerified(_)
+        | ProgramEvent::PrivacyProofGenerated(_)
+        | ProgramEvent::ModelOwnershipTransferStarted(_)
+        | ProgramEvent::ModelDelegatesUpdated(_)
+        | ProgramEvent::ModelOperatorsUpdated(_)
+        | ProgramEvent::ModelPayoutUpdated(_)
+        | ProgramEvent::ModelListed(_)
+        | ProgramEvent::CreditsToppedUp(_)
+        | ProgramEvent::CreditsWithdrawn(_)
+        | ProgramEvent::CreditEarningsClaimed(_)
+        | ProgramEvent::SubscriptionPlanUpdated(_)
+        | ProgramEvent::Subscribed(_)
+        | ProgramEvent::AccountMigrated(_) => {}
+    }
+    Ok(())
+}
+
+fn set(conn: &Connection, key: &str, value: &str, slot: i64) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value, updated_slot) VALUES (?1, ?2, ?3)",
+        params![key, value, slot],
+    )?;
+    Ok(())
+}
+
+fn chains_json(chains: &[u32]) -> String {
+    serde_json::to_string(chains).expect("serializing integers cannot fail")
+}
+
+fn fill_status(conn: &Connection, intent_id: u64, solver: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT status FROM fills WHERE intent_id = ?1 AND solver = ?2",
+            params![intent_id as i64, solver],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+fn failure(conn: &Connection, solver: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE solvers SET failed_fills = failed_fills + 1 WHERE solver = ?1",
+        [solver],
+    )?;
+    Ok(())
+}
+
+fn fee(conn: &Connection, kind: &str, amount: u64, account: &str, intent_id: Option<u64>, at: &At) -> Result<()> {
+    conn.execute(
+        "INSERT INTO fees (kind, amount, account, intent_id, signature, slot) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            kind,
+            amount as i64,
+            account,
+            intent_id.map(|id| id as i64),
+            at.signature,
+            at.slot as i64
+        ],
+    )?;
+    Ok(())
+}
+
+/// `Intent::refresh_status`: `on_empty` applies once nothing is left open,
+/// `None` keeping the current status
+fn refresh_status(conn: &Connection, intent_id: u64, on_empty: Option<&str>, slot: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE intents SET updated_slot = ?3, status = CASE
+            WHEN source_amount - filled_amount - refunded_amount > 0 THEN 'created'
+            WHEN matched_fills > 0 THEN 'matched'
+            WHEN executed_fills > 0 THEN 'executed'
+            WHEN settled_amount > 0 THEN 'settled'
+            ELSE coalesce(?2, status)
+         END
+         WHERE intent_id = ?1",
+        params![intent_id as i64, on_empty, slot],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! Following a live cluster.
+//!
+//! Transactions are indexed as soon as they are `confirmed`. Each pass
+//! first re-checks the ones above the last finalized slot; if any has
+//! vanished or moved to another slot, its fork was abandoned and the store
+//! rolls back to just before it.
+
+use anyhow::Result;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::rpc::RpcSource;
+use crate::store::Store;
+
+/// Slot before the earliest indexed transaction that is no longer on the
+/// cluster at its indexed slot
+pub fn fork_slot(indexed: &[(String, u64)], current: &[Option<u64>]) -> Option<u64> {
+    indexed
+        .iter()
+        .zip(current)
+        .filter(|((_, slot), now)| **now != Some(*slot))
+        .map(|((_, slot), _)| slot.saturating_sub(1))
+        .min()
+}
+
+/// One pass: handle forks, then index new transactions. Returns the number of events applied.
+pub async fn sync(store: &mut Store, rpc: &RpcSource) -> Result<usize> {
+    let finalized = rpc.finalized_slot().await?;
+
+    let unfinalized = store.unfinalized()?;
+    if !unfinalized.is_empty() {
+        let signatures: Vec<String> = unfinalized.iter().map(|(signature, _)| signature.clone()).collect();
+        let current = rpc.signature_slots(&signatures).await?;
+        if let Some(slot) = fork_slot(&unfinalized, &current) {
+            warn!(slot, "fork detected, rolling back");
+            store.rollback_after(slot)?;
+        }
+    }
+
+    let cursor = store.cursor()?.map(|(signature, _)| signature);
+    let mut transactions = Vec::new();
+    for info in rpc.signatures_since(cursor.as_deref()).await? {
+        if let Some(transaction) = rpc.transaction(&info.signature).await? {
+            transactions.push(transaction);
+        }
+    }
+    let applied = store.ingest(&transactions)?;
+    store.set_finalized_slot(finalized)?;
+    if !transactions.is_empty() {
+        info!(transactions = transactions.len(), events = applied, "indexed");
+    }
+    Ok(applied)
+}
+
+/// Sync every `poll_interval` until interrupted
+pub async fn run(store: &mut Store, rpc: &RpcSource, poll_interval: Duration) -> Result<()> {
+    loop {
+        if let Err(err) = sync(store, rpc).await {
+            warn!("sync failed: {err:#}");
+        }
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(poll_interval) => {}
+        }
+    }
+}
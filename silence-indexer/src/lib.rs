@@ -0,0 +1,20 @@
+//! Event indexer for the project_silence program.
+//!
+//! Transactions are read either from an RPC node ([`rpc::RpcSource`]) or
+//! from JSON log fixtures ([`source::load_fixture`]). Their raw events are
+//! kept in SQLite and folded into queryable tables for intents, fills,
+//! solvers, models, inference requests, batches, chains and fees.
+//!
+//! The raw event log is the source of truth: when transactions that were
+//! indexed at `confirmed` commitment disappear in a fork, the indexer
+//! deletes everything after the fork slot and replays the remaining log
+//! ([`Store::rollback_after`]).
+
+pub mod apply;
+pub mod follow;
+pub mod rpc;
+pub mod source;
+pub mod store;
+
+pub use source::LoggedTransaction;
+pub use store::Store;
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
+
+use silence_indexer::rpc::RpcSource;
+use silence_indexer::{follow, source, Store};
+
+#[derive(Parser)]
+#[command(name = "silence-indexer", about = "Project Silence event indexer")]
+struct Cli {
+    /// SQLite database
+    #[arg(long, default_value = "silence-indexer.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index recorded transactions from JSON fixture files, in order
+    Replay { fixtures: Vec<PathBuf> },
+    /// Follow a cluster over JSON-RPC until interrupted
+    Follow {
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc_url: String,
+        #[arg(long, default_value_t = 2)]
+        poll_interval_secs: u64,
+    },
+    /// Print a derived table as a JSON array
+    Query { table: String },
+    /// Discard everything indexed after a slot and rebuild
+    Rollback {
+        #[arg(long)]
+        slot: u64,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::Replay { fixtures } => {
+            for path in fixtures {
+                let applied = store.ingest(&source::load_fixture(&path)?)?;
+                tracing::info!(path = %path.display(), events = applied, "replayed");
+            }
+        }
+        Command::Follow {
+            rpc_url,
+            poll_interval_secs,
+        } => {
+            let rpc = RpcSource::new(rpc_url);
+            follow::run(&mut store, &rpc, Duration::from_secs(poll_interval_secs)).await?;
+        }
+        Command::Query { table } => {
+            println!("{}", serde_json::to_string_pretty(&store.table(&table)?)?);
+        }
+        Command::Rollback { slot } => store.rollback_after(slot)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! Solana JSON-RPC source of program transactions.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::source::LoggedTransaction;
+
+/// Page size for `getSignaturesForAddress`
+const SIGNATURE_PAGE: usize = 1_000;
+/// Maximum signatures per `getSignatureStatuses` call
+const STATUS_BATCH: usize = 256;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+}
+
+pub struct RpcSource {
+    http: reqwest::Client,
+    url: String,
+    address: String,
+}
+
+impl RpcSource {
+    /// Reads transactions of the project_silence program at `confirmed` commitment
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            address: project_silence_client::ID.to_string(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("{method} request failed"))?
+            .json()
+            .await
+            .with_context(|| format!("{method} returned invalid JSON"))?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+
+    pub async fn finalized_slot(&self) -> Result<u64> {
+        let result = self.call("getSlot", json!([{ "commitment": "finalized" }])).await?;
+        result.as_u64().ok_or_else(|| anyhow!("getSlot returned no slot"))
+    }
+
+    /// Program signatures newer than `until`, oldest first
+    pub async fn signatures_since(&self, until: Option<&str>) -> Result<Vec<SignatureInfo>> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let mut options = json!({ "limit": SIGNATURE_PAGE, "commitment": "confirmed" });
+            if let Some(before) = &before {
+                options["before"] = json!(before);
+            }
+            if let Some(until) = until {
+                options["until"] = json!(until);
+            }
+            let result = self
+                .call("getSignaturesForAddress", json!([self.address, options]))
+                .await?;
+            let page: Vec<SignatureInfo> = serde_json::from_value(result)?;
+            let done = page.len() < SIGNATURE_PAGE;
+            before = page.last().map(|info| info.signature.clone());
+            signatures.extend(page);
+            if done {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// A confirmed transaction's logs, or `None` if the node no longer has it
+    pub async fn transaction(&self, signature: &str) -> Result<Option<LoggedTransaction>> {
+        let result = self
+            .call(
+                "getTransaction",
+                json!([signature, {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0
+                }]),
+            )
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        let meta = &result["meta"];
+        Ok(Some(LoggedTransaction {
+            signature: signature.to_string(),
+            slot: result["slot"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("transaction has no slot"))?,
+            block_time: result["blockTime"].as_i64(),
+            err: meta.get("err").filter(|err| !err.is_null()).cloned(),
+            logs: serde_json::from_value(meta["logMessages"].clone()).unwrap_or_default(),
+        }))
+    }
+
+    /// Current slot of each signature, `None` where the cluster no longer knows it
+    pub async fn signature_slots(&self, signatures: &[String]) -> Result<Vec<Option<u64>>> {
+        let mut slots = Vec::with_capacity(signatures.len());
+        for chunk in signatures.chunks(STATUS_BATCH) {
+            let result = self
+                .call(
+                    "getSignatureStatuses",
+                    json!([chunk, { "searchTransactionHistory": true }]),
+                )
+                .await?;
+            let values = result["value"]
+                .as_array()
+                .ok_or_else(|| anyhow!("getSignatureStatuses returned no value"))?;
+            slots.extend(values.iter().map(|status| status["slot"].as_u64()));
+        }
+        Ok(slots)
+    }
+}
@@ -0,0 +1,26 @@
+//! Transactions as the indexer consumes them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// A program transaction and its log messages
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// Transaction error; failed transactions are recorded but their events are not applied
+    #[serde(default)]
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+}
+
+/// Read a fixture: a JSON array of transactions, oldest first
+pub fn load_fixture(path: &Path) -> Result<Vec<LoggedTransaction>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("parse {}", path.display()))
+}
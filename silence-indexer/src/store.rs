@@ -0,0 +1,337 @@
+//! SQLite storage: the raw event log plus the tables derived from it.
+
+use anyhow::{bail, Context, Result};
+use project_silence_client::events::{program_data, ProgramEvent};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+use crate::apply::{apply, At};
+use crate::source::LoggedTransaction;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    position INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature TEXT NOT NULL UNIQUE,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    failed INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transactions_slot ON transactions(slot);
+CREATE TABLE IF NOT EXISTS events (
+    signature TEXT NOT NULL,
+    idx INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    name TEXT,
+    data BLOB NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+CREATE INDEX IF NOT EXISTS events_slot ON events(slot);
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS chains (
+    chain_id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    enabled INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS intents (
+    intent_id INTEGER PRIMARY KEY,
+    creator TEXT NOT NULL,
+    destination_chain_id INTEGER NOT NULL,
+    source_amount INTEGER NOT NULL,
+    is_shielded INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    filled_amount INTEGER NOT NULL DEFAULT 0,
+    settled_amount INTEGER NOT NULL DEFAULT 0,
+    refunded_amount INTEGER NOT NULL DEFAULT 0,
+    matched_fills INTEGER NOT NULL DEFAULT 0,
+    executed_fills INTEGER NOT NULL DEFAULT 0,
+    closed INTEGER NOT NULL DEFAULT 0,
+    created_signature TEXT NOT NULL,
+    created_slot INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS intents_status ON intents(status);
+CREATE TABLE IF NOT EXISTS fills (
+    intent_id INTEGER NOT NULL,
+    solver TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    execution_deadline INTEGER NOT NULL,
+    destination_tx_hash TEXT,
+    solver_reward INTEGER,
+    protocol_fee INTEGER,
+    updated_slot INTEGER NOT NULL,
+    PRIMARY KEY (intent_id, solver)
+);
+CREATE TABLE IF NOT EXISTS solvers (
+    solver TEXT PRIMARY KEY,
+    supported_chains TEXT NOT NULL,
+    fee_bps INTEGER,
+    active INTEGER NOT NULL,
+    reputation_score INTEGER,
+    last_heartbeat INTEGER,
+    settled_fills INTEGER NOT NULL DEFAULT 0,
+    failed_fills INTEGER NOT NULL DEFAULT 0,
+    volume INTEGER NOT NULL DEFAULT 0,
+    rewards_earned INTEGER NOT NULL DEFAULT 0,
+    rewards_claimed INTEGER NOT NULL DEFAULT 0,
+    registered_slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS models (
+    model_id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    owner TEXT NOT NULL,
+    update_count INTEGER NOT NULL DEFAULT 0,
+    registered_slot INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS model_versions (
+    model_id INTEGER NOT NULL,
+    version TEXT NOT NULL,
+    weights_commitment TEXT NOT NULL,
+    changelog_hash TEXT NOT NULL,
+    published_slot INTEGER NOT NULL,
+    deprecated_slot INTEGER,
+    PRIMARY KEY (model_id, version)
+);
+CREATE TABLE IF NOT EXISTS requests (
+    user TEXT NOT NULL,
+    request_id INTEGER NOT NULL,
+    model_id INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    closed INTEGER NOT NULL DEFAULT 0,
+    created_slot INTEGER NOT NULL,
+    PRIMARY KEY (user, request_id)
+);
+CREATE TABLE IF NOT EXISTS batches (
+    user TEXT NOT NULL,
+    batch_id INTEGER NOT NULL,
+    model_id INTEGER NOT NULL,
+    prompt_count INTEGER NOT NULL,
+    completed INTEGER NOT NULL DEFAULT 0,
+    failed INTEGER NOT NULL DEFAULT 0,
+    closed INTEGER NOT NULL DEFAULT 0,
+    created_slot INTEGER NOT NULL,
+    PRIMARY KEY (user, batch_id)
+);
+CREATE TABLE IF NOT EXISTS fees (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    account TEXT NOT NULL,
+    intent_id INTEGER,
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL
+);
+";
+
+/// Tables derived from the event log, cleared and rebuilt on rollback
+pub const DERIVED_TABLES: &[&str] = &[
+    "settings",
+    "chains",
+    "intents",
+    "fills",
+    "solvers",
+    "models",
+    "model_versions",
+    "requests",
+    "batches",
+    "fees",
+];
+
+const FINALIZED_SLOT_KEY: &str = "finalized_slot";
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path.as_ref()).with_context(|| format!("open {}", path.as_ref().display()))?;
+        Self::init(conn)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Record and apply transactions, oldest first, skipping ones already
+    /// indexed. Returns the number of events applied.
+    pub fn ingest(&mut self, transactions: &[LoggedTransaction]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut applied = 0;
+        for logged in transactions {
+            let known: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM transactions WHERE signature = ?1)",
+                [&logged.signature],
+                |row| row.get(0),
+            )?;
+            if known {
+                continue;
+            }
+            tx.execute(
+                "INSERT INTO transactions (signature, slot, block_time, failed) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    logged.signature,
+                    logged.slot as i64,
+                    logged.block_time,
+                    logged.err.is_some()
+                ],
+            )?;
+            if logged.err.is_some() {
+                continue;
+            }
+            let at = At {
+                signature: &logged.signature,
+                slot: logged.slot,
+            };
+            for (idx, data) in program_data(&logged.logs).into_iter().enumerate() {
+                let event = ProgramEvent::decode(&data);
+                tx.execute(
+                    "INSERT INTO events (signature, idx, slot, name, data) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        logged.signature,
+                        idx as i64,
+                        logged.slot as i64,
+                        event.as_ref().map(|e| e.name()),
+                        data
+                    ],
+                )?;
+                if let Some(event) = event {
+                    apply(&tx, &event, &at)?;
+                    applied += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(applied)
+    }
+
+    /// Forget every transaction after `slot` and rebuild the derived tables
+    /// from the remaining event log
+    pub fn rollback_after(&mut self, slot: u64) -> Result<()> {
+        let finalized = self.finalized_slot()?;
+        if slot < finalized {
+            bail!("refusing to roll back past finalized slot {finalized}");
+        }
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM events WHERE slot > ?1", [slot as i64])?;
+        tx.execute("DELETE FROM transactions WHERE slot > ?1", [slot as i64])?;
+        for table in DERIVED_TABLES {
+            tx.execute(&format!("DELETE FROM {table}"), [])?;
+        }
+        {
+            let mut stmt = tx.prepare(
+                "SELECT e.signature, e.slot, e.data FROM events e
+                 JOIN transactions t ON t.signature = e.signature
+                 ORDER BY t.position, e.idx",
+            )?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                let signature: String = row.get(0)?;
+                let event_slot: i64 = row.get(1)?;
+                let data: Vec<u8> = row.get(2)?;
+                if let Some(event) = ProgramEvent::decode(&data) {
+                    let at = At {
+                        signature: &signature,
+                        slot: event_slot as u64,
+                    };
+                    apply(&tx, &event, &at)?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Most recently indexed transaction, used as the `until` cursor
+    pub fn cursor(&self) -> Result<Option<(String, u64)>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature, slot FROM transactions ORDER BY position DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)),
+            )
+            .optional()?)
+    }
+
+    /// Indexed transactions that may still be rolled back, oldest first
+    pub fn unfinalized(&self) -> Result<Vec<(String, u64)>> {
+        let finalized = self.finalized_slot()?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT signature, slot FROM transactions WHERE slot > ?1 ORDER BY position")?;
+        let rows = stmt.query_map([finalized as i64], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn finalized_slot(&self) -> Result<u64> {
+        Ok(meta_u64(&self.conn, FINALIZED_SLOT_KEY)?.unwrap_or(0))
+    }
+
+    pub fn set_finalized_slot(&self, slot: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+            params![FINALIZED_SLOT_KEY, slot.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Rows of a derived table as JSON objects
+    pub fn table(&self, name: &str) -> Result<Vec<Map<String, Value>>> {
+        if !DERIVED_TABLES.contains(&name) {
+            bail!("unknown table {name}; expected one of {}", DERIVED_TABLES.join(", "));
+        }
+        self.query(&format!("SELECT * FROM {name} ORDER BY rowid"), [])
+    }
+
+    /// Run a read query, returning rows as JSON objects
+    pub fn query<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<Vec<Map<String, Value>>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt.query(params)?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next()? {
+            let mut object = Map::new();
+            for (i, column) in columns.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null => Value::Null,
+                    ValueRef::Integer(n) => Value::from(n),
+                    ValueRef::Real(f) => Value::from(f),
+                    ValueRef::Text(text) => Value::from(String::from_utf8_lossy(text).into_owned()),
+                    ValueRef::Blob(blob) => Value::from(hex::encode(blob)),
+                };
+                object.insert(column.clone(), value);
+            }
+            out.push(object);
+        }
+        Ok(out)
+    }
+}
+
+fn meta_u64(conn: &Connection, key: &str) -> Result<Option<u64>> {
+    let value: Option<String> = conn
+        .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| row.get(0))
+        .optional()?;
+    value.map(|v| v.parse().context("corrupt meta value")).transpose()
+}
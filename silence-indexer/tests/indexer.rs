@@ -0,0 +1,163 @@
+use serde_json::{json, Map, Value};
+use silence_indexer::follow::fork_slot;
+use silence_indexer::{source, LoggedTransaction, Store};
+use std::path::Path;
+
+const SOLVER: &str = "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR";
+const CREATOR: &str = "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8";
+const USER: &str = "GgBaCs3NCBuZN12kCJgAW63ydqohFkHEdfdEXBPzLHq";
+const USER2: &str = "LbUiWL3xVV8hTFYBVdbTNrpDo41NKS6o3LHHuDzjfcY";
+
+fn fixture(name: &str) -> Vec<LoggedTransaction> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{name}.json"));
+    source::load_fixture(&path).unwrap()
+}
+
+fn row<'a>(rows: &'a [Map<String, Value>], column: &str, value: Value) -> &'a Map<String, Value> {
+    rows.iter()
+        .find(|row| row[column] == value)
+        .unwrap_or_else(|| panic!("no row with {column} = {value}"))
+}
+
+#[test]
+fn lifecycle_materializes_intents_solvers_and_fees() {
+    let mut store = Store::in_memory().unwrap();
+    store.ingest(&fixture("lifecycle")).unwrap();
+
+    let intents = store.table("intents").unwrap();
+    // Intent 99 was logged by another program and intent 2's transaction failed
+    assert_eq!(intents.len(), 2);
+
+    let settled = row(&intents, "intent_id", json!(1));
+    assert_eq!(settled["status"], "settled");
+    assert_eq!(settled["creator"], CREATOR);
+    assert_eq!(settled["filled_amount"], 1000);
+    assert_eq!(settled["settled_amount"], 1000);
+    assert_eq!(settled["executed_fills"], 0);
+
+    let failed = row(&intents, "intent_id", json!(3));
+    assert_eq!(failed["status"], "failed");
+    assert_eq!(failed["is_shielded"], 1);
+    assert_eq!(failed["filled_amount"], 0);
+    assert_eq!(failed["refunded_amount"], 500);
+    assert_eq!(failed["matched_fills"], 0);
+
+    let fills = store.table("fills").unwrap();
+    let fill = row(&fills, "intent_id", json!(1));
+    assert_eq!(fill["status"], "settled");
+    assert_eq!(fill["destination_tx_hash"], "ab".repeat(32));
+    assert_eq!(fill["solver_reward"], 997);
+
+    let solvers = store.table("solvers").unwrap();
+    let solver = row(&solvers, "solver", json!(SOLVER));
+    assert_eq!(solver["supported_chains"], "[1]");
+    assert_eq!(solver["settled_fills"], 1);
+    assert_eq!(solver["failed_fills"], 1);
+    assert_eq!(solver["volume"], 1000);
+    assert_eq!(solver["rewards_earned"], 997);
+    assert_eq!(solver["rewards_claimed"], 997);
+
+    let fees = store.table("fees").unwrap();
+    let kinds: Vec<_> = fees
+        .iter()
+        .map(|fee| (fee["kind"].clone(), fee["amount"].clone()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![(json!("protocol_fee"), json!(3)), (json!("solver_claim"), json!(997))]
+    );
+
+    let settings = store.table("settings").unwrap();
+    assert_eq!(row(&settings, "key", json!("protocol_fee_bps"))["value"], "30");
+    let chains = store.table("chains").unwrap();
+    assert_eq!(row(&chains, "chain_id", json!(1))["name"], "near");
+}
+
+#[test]
+fn reingesting_is_idempotent() {
+    let mut store = Store::in_memory().unwrap();
+    let transactions = fixture("lifecycle");
+    let applied = store.ingest(&transactions).unwrap();
+    assert!(applied > 0);
+    let before = store.table("intents").unwrap();
+
+    assert_eq!(store.ingest(&transactions).unwrap(), 0);
+    assert_eq!(store.table("intents").unwrap(), before);
+    assert_eq!(store.table("fees").unwrap().len(), 2);
+    assert_eq!(store.cursor().unwrap(), Some(("sig-claim".to_string(), 109)));
+}
+
+#[test]
+fn failed_transactions_are_recorded_but_not_applied() {
+    let mut store = Store::in_memory().unwrap();
+    store.ingest(&fixture("lifecycle")).unwrap();
+
+    let failed = store
+        .query(
+            "SELECT failed FROM transactions WHERE signature = ?1",
+            ["sig-create-2-failed"],
+        )
+        .unwrap();
+    assert_eq!(failed[0]["failed"], 1);
+    let events = store
+        .query(
+            "SELECT COUNT(*) AS n FROM events WHERE signature = ?1",
+            ["sig-create-2-failed"],
+        )
+        .unwrap();
+    assert_eq!(events[0]["n"], 0);
+}
+
+#[test]
+fn rollback_replays_the_surviving_fork() {
+    let mut store = Store::in_memory().unwrap();
+    store.ingest(&fixture("lifecycle")).unwrap();
+    store.set_finalized_slot(102).unwrap();
+
+    // The fork diverged after the execution at slot 104
+    store.rollback_after(104).unwrap();
+    let intents = store.table("intents").unwrap();
+    assert_eq!(intents.len(), 1);
+    assert_eq!(intents[0]["status"], "executed");
+    assert!(store.table("fees").unwrap().is_empty());
+
+    store.ingest(&fixture("fork")).unwrap();
+    let intent = &store.table("intents").unwrap()[0];
+    assert_eq!(intent["status"], "failed");
+    assert_eq!(intent["refunded_amount"], 1000);
+    assert_eq!(intent["executed_fills"], 0);
+    let solver = &store.table("solvers").unwrap()[0];
+    assert_eq!(solver["settled_fills"], 0);
+    assert_eq!(solver["failed_fills"], 1);
+
+    assert!(store.rollback_after(101).is_err());
+}
+
+#[test]
+fn requests_are_keyed_by_user() {
+    let mut store = Store::in_memory().unwrap();
+    store.ingest(&fixture("inference")).unwrap();
+
+    let models = store.table("models").unwrap();
+    assert_eq!(models[0]["name"], "sentiment-v1");
+    assert_eq!(models[0]["update_count"], 1);
+
+    let requests = store.table("requests").unwrap();
+    assert_eq!(requests.len(), 2);
+    let first = row(&requests, "user", json!(USER));
+    assert_eq!(first["status"], "completed");
+    assert_eq!(first["closed"], 1);
+    let second = row(&requests, "user", json!(USER2));
+    assert_eq!(second["status"], "failed");
+    assert_eq!(second["closed"], 0);
+}
+
+#[test]
+fn fork_slot_finds_earliest_moved_transaction() {
+    let indexed = vec![("a".to_string(), 10), ("b".to_string(), 11), ("c".to_string(), 12)];
+    assert_eq!(fork_slot(&indexed, &[Some(10), Some(11), Some(12)]), None);
+    assert_eq!(fork_slot(&indexed, &[Some(10), None, Some(13)]), Some(10));
+    assert_eq!(fork_slot(&indexed, &[Some(10), Some(11), None]), Some(11));
+}
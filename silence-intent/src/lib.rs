@@ -0,0 +1,192 @@
+//! Canonical cross-chain intent message.
+//!
+//! The Solana program and the NEAR bridge store intents differently (`u64`
+//! ids and hashed fields on Solana, `String` ids and plaintext fields on
+//! NEAR). `CrossChainIntent` is the chain-neutral form both convert into, and
+//! its domain-separated hash is what solvers and relayers sign and prove.
+//!
+//! Off-chain clients creating Solana intents must compute
+//! `destination_token_hash` and `recipient_hash` with [`token_hash`] and
+//! [`recipient_hash`] so the canonical message matches the NEAR side.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+/// Well-known chain ids, shared by both contracts
+pub const CHAIN_ID_SOLANA: u32 = 1;
+pub const CHAIN_ID_NEAR: u32 = 2;
+pub const CHAIN_ID_ZCASH: u32 = 3;
+
+/// Current message version
+pub const MESSAGE_VERSION: u8 = 1;
+
+/// Domain tag for [`CrossChainIntent::hash`]
+pub const INTENT_DOMAIN: &[u8] = b"silence:cross-chain-intent:v1";
+/// Domain tag for canonical intent ids
+pub const INTENT_ID_DOMAIN: &[u8] = b"silence:intent-id:v1";
+/// Domain tag for hashed account ids (NEAR)
+pub const ACCOUNT_DOMAIN: &[u8] = b"silence:account:v1";
+/// Domain tag for token identifiers
+pub const TOKEN_DOMAIN: &[u8] = b"silence:token:v1";
+/// Domain tag for recipient addresses
+pub const RECIPIENT_DOMAIN: &[u8] = b"silence:recipient:v1";
+/// Domain tag for [`amount_commitment`]
+pub const AMOUNT_COMMITMENT_DOMAIN: &[u8] = b"silence:amount-commitment:v1";
+/// Domain tag for [`FulfillmentReceipt::hash`]
+pub const RECEIPT_DOMAIN: &[u8] = b"silence:fulfillment-receipt:v1";
+
+/// Destination amount, plaintext or hidden behind a commitment
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum DestinationAmount {
+    Plain(u128),
+    Commitment([u8; 32]),
+}
+
+/// Chain-neutral intent message
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrossChainIntent {
+    /// Message version (`MESSAGE_VERSION`)
+    pub version: u8,
+    /// Chain the intent was created on
+    pub source_chain_id: u32,
+    /// Chain the funds are delivered to
+    pub destination_chain_id: u32,
+    /// Canonical id, see [`solana_intent_id`] and [`near_intent_id`]
+    pub intent_id: [u8; 32],
+    /// Creator: Solana pubkey bytes, or [`account_hash`] of a NEAR account
+    pub creator: [u8; 32],
+    /// Source token: SPL mint bytes (zero for native SOL), or [`token_hash`] on NEAR
+    pub source_token: [u8; 32],
+    /// Source amount in the source chain's base units
+    pub source_amount: u128,
+    /// [`token_hash`] of the destination token identifier
+    pub destination_token_hash: [u8; 32],
+    /// Amount owed on the destination chain
+    pub destination_amount: DestinationAmount,
+    /// [`recipient_hash`] of the destination address
+    pub recipient_hash: [u8; 32],
+    /// Whether this is a shielded transfer
+    pub is_shielded: bool,
+    /// Expiry as unix seconds
+    pub expires_at: u64,
+}
+
+impl CrossChainIntent {
+    /// Borsh encoding of the message
+    pub fn encode(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("encoding into a Vec cannot fail")
+    }
+
+    /// Decode a Borsh-encoded message
+    pub fn decode(bytes: &[u8]) -> Result<Self, borsh::io::Error> {
+        borsh::from_slice(bytes)
+    }
+
+    /// Domain-separated hash: `sha256(INTENT_DOMAIN || encode())`
+    pub fn hash(&self) -> [u8; 32] {
+        tagged_hash(INTENT_DOMAIN, &[&self.encode()])
+    }
+}
+
+/// Proof that a solver delivered an intent on its destination chain.
+///
+/// Produced by the destination contract and attested by relayers on the
+/// source chain, where it lets the named solver execute the intent.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FulfillmentReceipt {
+    /// Message version (`MESSAGE_VERSION`)
+    pub version: u8,
+    /// [`CrossChainIntent::hash`] of the fulfilled intent
+    pub intent_hash: [u8; 32],
+    /// Chain the delivery happened on
+    pub destination_chain_id: u32,
+    /// Source-chain solver key allowed to consume the receipt
+    pub solver: [u8; 32],
+    /// [`recipient_hash`] of the paid recipient
+    pub recipient_hash: [u8; 32],
+    /// Amount delivered in destination base units
+    pub amount: u128,
+    /// Delivery time as unix seconds
+    pub fulfilled_at: u64,
+}
+
+impl FulfillmentReceipt {
+    /// Borsh encoding of the receipt
+    pub fn encode(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("encoding into a Vec cannot fail")
+    }
+
+    /// Decode a Borsh-encoded receipt
+    pub fn decode(bytes: &[u8]) -> Result<Self, borsh::io::Error> {
+        borsh::from_slice(bytes)
+    }
+
+    /// Domain-separated hash: `sha256(RECEIPT_DOMAIN || encode())`
+    pub fn hash(&self) -> [u8; 32] {
+        tagged_hash(RECEIPT_DOMAIN, &[&self.encode()])
+    }
+}
+
+/// `sha256(domain || parts...)`
+pub fn tagged_hash(domain: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Canonical id of a Solana intent
+pub fn solana_intent_id(intent_id: u64) -> [u8; 32] {
+    tagged_hash(
+        INTENT_ID_DOMAIN,
+        &[&CHAIN_ID_SOLANA.to_le_bytes(), &intent_id.to_le_bytes()],
+    )
+}
+
+/// Canonical id of a NEAR intent
+pub fn near_intent_id(intent_id: &str) -> [u8; 32] {
+    tagged_hash(
+        INTENT_ID_DOMAIN,
+        &[&CHAIN_ID_NEAR.to_le_bytes(), intent_id.as_bytes()],
+    )
+}
+
+/// Hash of a NEAR account id
+pub fn account_hash(account_id: &str) -> [u8; 32] {
+    tagged_hash(ACCOUNT_DOMAIN, &[account_id.as_bytes()])
+}
+
+/// Hash of a token identifier (NEAR contract id, SPL mint in base58, ...)
+pub fn token_hash(token: &str) -> [u8; 32] {
+    tagged_hash(TOKEN_DOMAIN, &[token.as_bytes()])
+}
+
+/// Hash of a destination address in its chain-native string form
+pub fn recipient_hash(recipient: &str) -> [u8; 32] {
+    tagged_hash(RECIPIENT_DOMAIN, &[recipient.as_bytes()])
+}
+
+/// Commitment to a destination amount, opened with `blinding`
+pub fn amount_commitment(amount: u128, blinding: &[u8; 32]) -> [u8; 32] {
+    tagged_hash(AMOUNT_COMMITMENT_DOMAIN, &[&amount.to_le_bytes(), blinding])
+}
+
+impl DestinationAmount {
+    /// Whether `amount` satisfies this destination amount. Commitments need
+    /// the `blinding` factor shared by the intent creator.
+    pub fn is_met_by(&self, amount: u128, blinding: Option<&[u8; 32]>) -> bool {
+        match self {
+            DestinationAmount::Plain(expected) => amount >= *expected,
+            DestinationAmount::Commitment(commitment) => {
+                blinding.is_some_and(|b| amount_commitment(amount, b) == *commitment)
+            }
+        }
+    }
+}
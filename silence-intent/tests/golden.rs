@@ -0,0 +1,169 @@
+//! Golden vectors for the canonical intent encoding and hashes.
+//!
+//! The expected values were produced independently of this crate; a change
+//! here means every deployed contract and solver disagrees on intent hashes.
+
+use silence_intent::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn solana_vector() -> CrossChainIntent {
+    CrossChainIntent {
+        version: MESSAGE_VERSION,
+        source_chain_id: CHAIN_ID_SOLANA,
+        destination_chain_id: CHAIN_ID_NEAR,
+        intent_id: solana_intent_id(7),
+        creator: [1u8; 32],
+        source_token: [0u8; 32],
+        source_amount: 1_000_000_000,
+        destination_token_hash: token_hash("wrap.near"),
+        destination_amount: DestinationAmount::Commitment([0xab; 32]),
+        recipient_hash: recipient_hash("alice.near"),
+        is_shielded: true,
+        expires_at: 1_700_003_600,
+    }
+}
+
+fn near_vector() -> CrossChainIntent {
+    CrossChainIntent {
+        version: MESSAGE_VERSION,
+        source_chain_id: CHAIN_ID_NEAR,
+        destination_chain_id: CHAIN_ID_SOLANA,
+        intent_id: near_intent_id("intent_42"),
+        creator: account_hash("bob.near"),
+        source_token: token_hash("near"),
+        source_amount: 5 * 10u128.pow(24),
+        destination_token_hash: token_hash("So11111111111111111111111111111111111111112"),
+        destination_amount: DestinationAmount::Plain(2_500_000_000),
+        recipient_hash: recipient_hash("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin"),
+        is_shielded: false,
+        expires_at: 1_700_000_000,
+    }
+}
+
+#[test]
+fn field_hashes() {
+    assert_eq!(
+        hex(&solana_intent_id(7)),
+        "6ebf0a990254f8e516e047b81d8281e7f8626ddcff982b6669be7299db3180a8"
+    );
+    assert_eq!(
+        hex(&near_intent_id("intent_42")),
+        "34d38ad4afed25d2939591c778dc8a9907af5eb1f7f0a17c2a5b7cee670d8692"
+    );
+    assert_eq!(
+        hex(&account_hash("bob.near")),
+        "c8aae4c8d35fed9fb8ef4ea3c82b788340aedf41f60438111d1d82e361f0928d"
+    );
+    assert_eq!(
+        hex(&token_hash("near")),
+        "a34151f3be018a1bc3d47a762253d228f6352346675ea636befab143611ba76c"
+    );
+    assert_eq!(
+        hex(&recipient_hash("alice.near")),
+        "4482385ec0c8a84fc6dd55e8ee6c51fd97fe8ec715c3f0f7a776a937cefe85de"
+    );
+}
+
+#[test]
+fn solana_intent_encoding() {
+    let intent = solana_vector();
+    let encoded = intent.encode();
+    assert_eq!(encoded.len(), 227);
+    assert_eq!(
+        hex(&encoded),
+        "0101000000020000006ebf0a990254f8e516e047b81d8281e7f8626ddcff982b6669be7299db3180a8\
+         0101010101010101010101010101010101010101010101010101010101010101\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         00ca9a3b000000000000000000000000\
+         36cf31e201a57d5f135e3e75dba1c061120c0b2c010369fa0540d2dc535389d7\
+         01abababababababababababababababababababababababababababababababab\
+         4482385ec0c8a84fc6dd55e8ee6c51fd97fe8ec715c3f0f7a776a937cefe85de\
+         01\
+         10ff536500000000"
+    );
+    assert_eq!(
+        hex(&intent.hash()),
+        "b855a4701286b4a2d3983151873a5db7ca4bf76b9f642ebc19f4a7b1a9836d6f"
+    );
+    assert_eq!(CrossChainIntent::decode(&encoded).unwrap(), intent);
+}
+
+#[test]
+fn near_intent_encoding() {
+    let intent = near_vector();
+    let encoded = intent.encode();
+    assert_eq!(encoded.len(), 211);
+    assert_eq!(
+        hex(&encoded),
+        "01020000000100000034d38ad4afed25d2939591c778dc8a9907af5eb1f7f0a17c2a5b7cee670d8692\
+         c8aae4c8d35fed9fb8ef4ea3c82b788340aedf41f60438111d1d82e361f0928d\
+         a34151f3be018a1bc3d47a762253d228f6352346675ea636befab143611ba76c\
+         00000025a4000a8bca22040000000000\
+         ca65de0e58b753172a24fed4993969d46dae54a7ece97c1af85869762d860665\
+         0000f90295000000000000000000000000\
+         82be29a711f5dec6a9fc9deb793dbcee7079ffec758134dbf65d756a331bf89c\
+         00\
+         00f1536500000000"
+    );
+    assert_eq!(
+        hex(&intent.hash()),
+        "b97e237f9c6ee8173ec7656dcff10289ad20c169581c7d7c720e4a56e1860bac"
+    );
+    assert_eq!(CrossChainIntent::decode(&encoded).unwrap(), intent);
+}
+
+#[test]
+fn amount_commitment_opening() {
+    let commitment = amount_commitment(2_500_000_000, &[7u8; 32]);
+    assert_eq!(
+        hex(&commitment),
+        "2af71a9da48aed45fbd0e862b5c5aab3a8a710c5a76b331cfb7cab8b1f06cd8f"
+    );
+    let amount = DestinationAmount::Commitment(commitment);
+    assert!(amount.is_met_by(2_500_000_000, Some(&[7u8; 32])));
+    assert!(!amount.is_met_by(2_500_000_001, Some(&[7u8; 32])));
+    assert!(!amount.is_met_by(2_500_000_000, None));
+    assert!(DestinationAmount::Plain(10).is_met_by(11, None));
+}
+
+#[test]
+fn fulfillment_receipt_encoding() {
+    let receipt = FulfillmentReceipt {
+        version: MESSAGE_VERSION,
+        intent_hash: solana_vector().hash(),
+        destination_chain_id: CHAIN_ID_NEAR,
+        solver: [9u8; 32],
+        recipient_hash: recipient_hash("alice.near"),
+        amount: 3 * 10u128.pow(24),
+        fulfilled_at: 1_700_001_000,
+    };
+    let encoded = receipt.encode();
+    assert_eq!(encoded.len(), 125);
+    assert_eq!(
+        hex(&encoded),
+        "01b855a4701286b4a2d3983151873a5db7ca4bf76b9f642ebc19f4a7b1a9836d6f02000000\
+         0909090909090909090909090909090909090909090909090909090909090909\
+         4482385ec0c8a84fc6dd55e8ee6c51fd97fe8ec715c3f0f7a776a937cefe85de\
+         000000e3c8666c53467b020000000000\
+         e8f4536500000000"
+    );
+    assert_eq!(
+        hex(&receipt.hash()),
+        "16ee40f33991d1262d9ad5393c0cdb66a293a2dcf5e928c4da64782a93ce798a"
+    );
+    assert_eq!(FulfillmentReceipt::decode(&encoded).unwrap(), receipt);
+}
+
+#[test]
+fn hash_depends_on_every_field() {
+    let base = solana_vector();
+    let mut shielded = base.clone();
+    shielded.is_shielded = false;
+    let mut expiry = base.clone();
+    expiry.expires_at += 1;
+    assert_ne!(base.hash(), shielded.hash());
+    assert_ne!(base.hash(), expiry.hash());
+}
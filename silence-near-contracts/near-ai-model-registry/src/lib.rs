@@ -1,4 +1,4 @@
-use near_sdk::{env, near, collections::{LookupMap, UnorderedSet}, json_types::{Base64VecU8, U128}, AccountId, PanicOnDefault, Promise};
+use near_sdk::{env, near, collections::{LookupMap, UnorderedSet}, json_types::{Base64VecU8, U128}, AccountId, NearToken, PanicOnDefault, Promise};
 
 /// Model metadata stored on-chain
 #[near(serializers=[borsh, json])]
@@ -17,6 +17,43 @@ pub struct ModelMetadata {
     pub is_active: bool,
 }
 
+/// Default page size of `get_models`
+const DEFAULT_PAGE_LIMIT: u64 = 50;
+/// Largest page size of `get_models`
+const MAX_PAGE_LIMIT: u64 = 100;
+
+/// Criteria for `get_models`; omitted fields match everything
+#[near(serializers=[json])]
+pub struct ModelFilter {
+    pub model_type: Option<String>,
+    pub tee_required: Option<bool>,
+    pub is_active: Option<bool>,
+    /// Lowest cost per inference, inclusive
+    pub min_cost: Option<U128>,
+    /// Highest cost per inference, inclusive
+    pub max_cost: Option<U128>,
+}
+
+impl ModelFilter {
+    fn matches(&self, model: &ModelMetadata) -> bool {
+        self.model_type
+            .as_ref()
+            .is_none_or(|model_type| &model.model_type == model_type)
+            && self.tee_required.is_none_or(|tee| model.tee_required == tee)
+            && self.is_active.is_none_or(|active| model.is_active == active)
+            && self.min_cost.is_none_or(|min| model.cost_per_inference.0 >= min.0)
+            && self.max_cost.is_none_or(|max| model.cost_per_inference.0 <= max.0)
+    }
+}
+
+/// One page of `get_models`
+#[near(serializers=[json])]
+pub struct ModelPage {
+    pub models: Vec<ModelMetadata>,
+    /// `from_index` of the next page; `None` once every model was scanned
+    pub next_index: Option<u64>,
+}
+
 /// Inference request stored on-chain
 #[near(serializers=[borsh, json])]
 pub struct InferenceRequest {
@@ -41,6 +78,54 @@ pub struct InferenceResult {
     pub verified: bool,
 }
 
+/// Prepaid inference credits of a user with one model owner
+#[near(serializers=[borsh, json])]
+#[derive(Default)]
+pub struct CreditAccount {
+    /// Yocto left to spend
+    pub balance: U128,
+    /// Yocto spent and not yet claimed by the model owner
+    pub earned: U128,
+}
+
+/// Fixed-price bundle of inferences per epoch, offered on a model
+#[near(serializers=[borsh, json])]
+pub struct SubscriptionPlan {
+    pub model_id: String,
+    pub plan_id: u8,
+    /// Inferences a subscriber may request per epoch
+    pub inferences_per_epoch: u32,
+    /// Yocto paid to the model owner per epoch
+    pub price: U128,
+    /// Inactive plans take no new subscriptions
+    pub is_active: bool,
+}
+
+/// A user's subscription to a model, valid for one epoch at a time
+#[near(serializers=[borsh, json])]
+pub struct Subscription {
+    /// Plan paid for in `epoch`
+    pub plan_id: u8,
+    /// Epoch height the subscription covers
+    pub epoch: u64,
+    /// Quota of the plan when it was paid for
+    pub inferences_per_epoch: u32,
+    /// Inferences used in `epoch`
+    pub used: u32,
+}
+
+/// How `create_inference_request` is paid for; requests without one stay free
+#[near(serializers=[json])]
+#[serde(rename_all = "snake_case")]
+pub enum Payment {
+    /// Attach exactly `cost_per_inference`, forwarded to the model owner
+    Deposit,
+    /// Debit the caller's credits with the model owner
+    Credits,
+    /// Use the caller's subscription to the model
+    Subscription,
+}
+
 /// TEE Attestation proof
 #[near(serializers=[borsh, json])]
 pub struct TEEAttestation {
@@ -51,6 +136,16 @@ pub struct TEEAttestation {
     pub quote: String,
 }
 
+/// Contract state as deployed before credits and subscriptions, read by `migrate`
+#[near(serializers=[borsh])]
+struct ModelRegistryV0 {
+    models: LookupMap<String, ModelMetadata>,
+    model_ids: UnorderedSet<String>,
+    requests: LookupMap<String, InferenceRequest>,
+    results: LookupMap<String, InferenceResult>,
+    owner: AccountId,
+}
+
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
 pub struct ModelRegistry {
@@ -64,6 +159,12 @@ pub struct ModelRegistry {
     results: LookupMap<String, InferenceResult>,
     /// Owner of the contract
     owner: AccountId,
+    /// Map of (user, model owner) -> CreditAccount
+    credits: LookupMap<(AccountId, AccountId), CreditAccount>,
+    /// Map of (model_id, plan_id) -> SubscriptionPlan
+    plans: LookupMap<(String, u8), SubscriptionPlan>,
+    /// Map of (user, model_id) -> Subscription
+    subscriptions: LookupMap<(AccountId, String), Subscription>,
 }
 
 #[near]
@@ -76,6 +177,26 @@ impl ModelRegistry {
             requests: LookupMap::new(b"r"),
             results: LookupMap::new(b"s"),
             owner: owner_id,
+            credits: LookupMap::new(b"c"),
+            plans: LookupMap::new(b"p"),
+            subscriptions: LookupMap::new(b"u"),
+        }
+    }
+
+    /// Upgrade state written before credits and subscriptions; call once right after deploying
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: ModelRegistryV0 = env::state_read().expect("No contract state to migrate");
+        Self {
+            models: old.models,
+            model_ids: old.model_ids,
+            requests: old.requests,
+            results: old.results,
+            owner: old.owner,
+            credits: LookupMap::new(b"c"),
+            plans: LookupMap::new(b"p"),
+            subscriptions: LookupMap::new(b"u"),
         }
     }
 
@@ -147,7 +268,8 @@ impl ModelRegistry {
         self.models.insert(&model_id, &model);
     }
 
-    /// Create an inference request
+    /// Create an inference request, paid per `payment`; without one it is free, as before payments
+    #[payable]
     pub fn create_inference_request(
         &mut self,
         request_id: String,
@@ -155,6 +277,7 @@ impl ModelRegistry {
         prompt: String,
         encrypted_data: Option<Base64VecU8>,
         require_attestation: bool,
+        payment: Option<Payment>,
     ) -> Promise {
         // Verify model exists and is active
         let model = self.models.get(&model_id).expect("Model not found");
@@ -165,10 +288,47 @@ impl ModelRegistry {
             assert!(require_attestation, "Attestation required for this model");
         }
         
+        let user = env::predecessor_account_id();
+        let deposit = env::attached_deposit().as_yoctonear();
+        let cost = model.cost_per_inference.0;
+        let payment_promise = match payment {
+            None => {
+                assert_eq!(deposit, 0, "Choose a payment to attach a deposit");
+                Promise::new(env::current_account_id())
+            }
+            Some(Payment::Deposit) => {
+                assert_eq!(deposit, cost, "Attach exactly the inference cost");
+                Promise::new(model.owner.clone()).transfer(NearToken::from_yoctonear(cost))
+            }
+            Some(Payment::Credits) => {
+                assert_eq!(deposit, 0, "Credit payments take no deposit");
+                let key = (user.clone(), model.owner.clone());
+                let mut credits = self.credits.get(&key).expect("No credits with this model owner");
+                assert!(credits.balance.0 >= cost, "Insufficient credits");
+                credits.balance = U128(credits.balance.0 - cost);
+                credits.earned = U128(credits.earned.0 + cost);
+                self.credits.insert(&key, &credits);
+                Promise::new(env::current_account_id())
+            }
+            Some(Payment::Subscription) => {
+                assert_eq!(deposit, 0, "Subscription payments take no deposit");
+                let key = (user.clone(), model_id.clone());
+                let mut subscription = self.subscriptions.get(&key).expect("Not subscribed to this model");
+                assert!(
+                    subscription.epoch == env::epoch_height()
+                        && subscription.used < subscription.inferences_per_epoch,
+                    "Subscription quota used up for this epoch"
+                );
+                subscription.used += 1;
+                self.subscriptions.insert(&key, &subscription);
+                Promise::new(env::current_account_id())
+            }
+        };
+        
         let request = InferenceRequest {
             request_id: request_id.clone(),
             model_id: model_id.clone(),
-            user_id: env::predecessor_account_id(),
+            user_id: user,
             prompt,
             encrypted_data,
             require_attestation,
@@ -179,7 +339,88 @@ impl ModelRegistry {
         self.requests.insert(&request_id, &request);
         
         // Return promise for async processing (would call TEE service)
-        Promise::new(env::current_account_id())
+        payment_promise
+    }
+
+    /// Prepay the attached deposit for inferences on models owned by `provider`
+    #[payable]
+    pub fn top_up_credits(&mut self, provider: AccountId) -> CreditAccount {
+        let amount = env::attached_deposit().as_yoctonear();
+        assert!(amount > 0, "Attach the amount to prepay");
+        let key = (env::predecessor_account_id(), provider);
+        let mut credits = self.credits.get(&key).unwrap_or_default();
+        credits.balance = U128(credits.balance.0.checked_add(amount).expect("Overflow"));
+        self.credits.insert(&key, &credits);
+        credits
+    }
+
+    /// Take back unspent credits with `provider`
+    pub fn withdraw_credits(&mut self, provider: AccountId, amount: U128) -> Promise {
+        let user = env::predecessor_account_id();
+        let key = (user.clone(), provider);
+        let mut credits = self.credits.get(&key).expect("No credits with this model owner");
+        assert!(credits.balance.0 >= amount.0, "Insufficient credits");
+        credits.balance = U128(credits.balance.0 - amount.0);
+        self.credits.insert(&key, &credits);
+        Promise::new(user).transfer(NearToken::from_yoctonear(amount.0))
+    }
+
+    /// Claim what `user` has spent from their credits with the caller
+    pub fn claim_credit_earnings(&mut self, user: AccountId) -> Promise {
+        let provider = env::predecessor_account_id();
+        let key = (user, provider.clone());
+        let mut credits = self.credits.get(&key).expect("No credits with this model owner");
+        let amount = credits.earned.0;
+        assert!(amount > 0, "Nothing to claim");
+        credits.earned = U128(0);
+        self.credits.insert(&key, &credits);
+        Promise::new(provider).transfer(NearToken::from_yoctonear(amount))
+    }
+
+    /// Create or change a subscription plan (model owner only); paid epochs keep their quota
+    pub fn set_subscription_plan(
+        &mut self,
+        model_id: String,
+        plan_id: u8,
+        inferences_per_epoch: u32,
+        price: U128,
+        is_active: bool,
+    ) {
+        let model = self.models.get(&model_id).expect("Model not found");
+        assert_eq!(env::predecessor_account_id(), model.owner, "Only the model owner can set plans");
+        assert!(inferences_per_epoch > 0, "Plan must include inferences");
+        let plan = SubscriptionPlan {
+            model_id: model_id.clone(),
+            plan_id,
+            inferences_per_epoch,
+            price,
+            is_active,
+        };
+        self.plans.insert(&(model_id, plan_id), &plan);
+    }
+
+    /// Pay for a plan's quota in the current epoch; attach exactly its price
+    #[payable]
+    pub fn subscribe(&mut self, model_id: String, plan_id: u8) -> Promise {
+        let model = self.models.get(&model_id).expect("Model not found");
+        assert!(model.is_active, "Model is not active");
+        let plan = self.plans.get(&(model_id.clone(), plan_id)).expect("Plan not found");
+        assert!(plan.is_active, "Plan is not active");
+        assert_eq!(env::attached_deposit().as_yoctonear(), plan.price.0, "Attach exactly the plan price");
+        
+        let epoch = env::epoch_height();
+        let key = (env::predecessor_account_id(), model_id);
+        if let Some(current) = self.subscriptions.get(&key) {
+            assert!(current.epoch != epoch, "Already subscribed for this epoch");
+        }
+        let subscription = Subscription {
+            plan_id,
+            epoch,
+            inferences_per_epoch: plan.inferences_per_epoch,
+            used: 0,
+        };
+        self.subscriptions.insert(&key, &subscription);
+        Promise::new(model.owner).transfer(NearToken::from_yoctonear(plan.price.0))
     }
 
     /// Store inference result (called by TEE service)
@@ -216,11 +457,55 @@ impl ModelRegistry {
         self.models.get(&model_id)
     }
 
-    /// Get all registered model IDs
+    /// Get all registered model IDs; prefer `get_models` once there are many
     pub fn get_all_models(&self) -> Vec<String> {
         self.model_ids.iter().collect()
     }
 
+    /// Number of registered models
+    pub fn get_model_count(&self) -> u64 {
+        self.model_ids.len()
+    }
+
+    /// Scan `limit` models (default 50, max 100) from `from_index` and return those
+    /// matching `filter`. A page may hold fewer matches than `limit`; keep calling
+    /// with `next_index` until it is `None`.
+    pub fn get_models(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        filter: Option<ModelFilter>,
+    ) -> ModelPage {
+        let ids = self.model_ids.as_vector();
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+        let start = from_index.unwrap_or(0).min(ids.len());
+        let end = start.saturating_add(limit).min(ids.len());
+        let models = (start..end)
+            .filter_map(|index| ids.get(index))
+            .filter_map(|model_id| self.models.get(&model_id))
+            .filter(|model| filter.as_ref().is_none_or(|filter| filter.matches(model)))
+            .collect();
+        ModelPage {
+            models,
+            next_index: (end < ids.len()).then_some(end),
+        }
+    }
+
+    /// Get prepaid credits of `user` with the model owner `provider`
+    pub fn get_credits(&self, user: AccountId, provider: AccountId) -> Option<CreditAccount> {
+        self.credits.get(&(user, provider))
+    }
+
+    /// Get a subscription plan
+    pub fn get_subscription_plan(&self, model_id: String, plan_id: u8) -> Option<SubscriptionPlan> {
+        self.plans.get(&(model_id, plan_id))
+    }
+
+    /// Get `user`'s subscription to a model
+    pub fn get_subscription(&self, user: AccountId, model_id: String) -> Option<Subscription> {
+        self.subscriptions.get(&(user, model_id))
+    }
+
     /// Get inference request
     pub fn get_request(&self, request_id: String) -> Option<InferenceRequest> {
         self.requests.get(&request_id)
@@ -0,0 +1,257 @@
+//! Paying for inference requests: deposits, prepaid credits, subscriptions,
+//! and the upgrade of state written before payments.
+
+use near_ai_model_registry::{InferenceRequest, InferenceResult, ModelMetadata, ModelRegistry, Payment};
+use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::{accounts, VMContextBuilder};
+use near_sdk::{env, near, testing_env, AccountId, NearToken};
+
+const MODEL_ID: &str = "model";
+const COST: u128 = 1_000;
+const PLAN_ID: u8 = 1;
+const PLAN_PRICE: u128 = 5_000;
+
+fn contract() -> AccountId {
+    "registry.near".parse().unwrap()
+}
+
+/// Contract owner, which also owns every model it registers
+fn owner() -> AccountId {
+    accounts(0)
+}
+
+fn user() -> AccountId {
+    accounts(1)
+}
+
+/// Call the contract as `caller` with `deposit` yocto attached, in `epoch`
+fn call(caller: AccountId, deposit: u128, epoch: u64) {
+    let context = VMContextBuilder::new()
+        .current_account_id(contract())
+        .signer_account_id(caller.clone())
+        .predecessor_account_id(caller)
+        .attached_deposit(NearToken::from_yoctonear(deposit))
+        .epoch_height(epoch)
+        .build();
+    testing_env!(context);
+}
+
+fn setup() -> ModelRegistry {
+    call(owner(), 0, 1);
+    let mut registry = ModelRegistry::new(owner());
+    registry.register_model(
+        MODEL_ID.to_string(),
+        "Model".to_string(),
+        "Test model".to_string(),
+        "llm".to_string(),
+        "1.0.0".to_string(),
+        false,
+        false,
+        U128(COST),
+    );
+    registry
+}
+
+fn request(registry: &mut ModelRegistry, request_id: &str, payment: Option<Payment>) {
+    registry.create_inference_request(
+        request_id.to_string(),
+        MODEL_ID.to_string(),
+        "prompt".to_string(),
+        None,
+        false,
+        payment,
+    );
+}
+
+fn credits(registry: &ModelRegistry) -> (u128, u128) {
+    let credits = registry.get_credits(user(), owner()).expect("credit account");
+    (credits.balance.0, credits.earned.0)
+}
+
+#[test]
+fn requests_without_payment_stay_free() {
+    let mut registry = setup();
+    call(user(), 0, 1);
+    request(&mut registry, "r1", None);
+    assert_eq!(registry.get_request("r1".to_string()).unwrap().user_id, user());
+}
+
+#[test]
+#[should_panic(expected = "Choose a payment to attach a deposit")]
+fn unpaid_requests_take_no_deposit() {
+    let mut registry = setup();
+    call(user(), COST, 1);
+    request(&mut registry, "r1", None);
+}
+
+#[test]
+fn deposits_pay_the_exact_cost() {
+    let mut registry = setup();
+    call(user(), COST, 1);
+    request(&mut registry, "r1", Some(Payment::Deposit));
+    assert!(registry.get_request("r1".to_string()).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Attach exactly the inference cost")]
+fn deposits_below_the_cost_fail() {
+    let mut registry = setup();
+    call(user(), COST - 1, 1);
+    request(&mut registry, "r1", Some(Payment::Deposit));
+}
+
+#[test]
+fn credits_pay_for_requests_until_claimed_or_withdrawn() {
+    let mut registry = setup();
+    call(user(), 3 * COST, 1);
+    registry.top_up_credits(owner());
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Credits));
+    request(&mut registry, "r2", Some(Payment::Credits));
+    assert_eq!(credits(&registry), (COST, 2 * COST));
+
+    call(owner(), 0, 1);
+    registry.claim_credit_earnings(user());
+    assert_eq!(credits(&registry), (COST, 0));
+
+    call(user(), 0, 1);
+    registry.withdraw_credits(owner(), U128(COST));
+    assert_eq!(credits(&registry), (0, 0));
+}
+
+#[test]
+#[should_panic(expected = "Insufficient credits")]
+fn credits_cover_no_more_than_their_balance() {
+    let mut registry = setup();
+    call(user(), COST - 1, 1);
+    registry.top_up_credits(owner());
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Credits));
+}
+
+#[test]
+#[should_panic(expected = "Insufficient credits")]
+fn spent_credits_cannot_be_withdrawn() {
+    let mut registry = setup();
+    call(user(), COST, 1);
+    registry.top_up_credits(owner());
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Credits));
+    registry.withdraw_credits(owner(), U128(COST));
+}
+
+#[test]
+#[should_panic(expected = "Nothing to claim")]
+fn earnings_are_claimed_once() {
+    let mut registry = setup();
+    call(user(), COST, 1);
+    registry.top_up_credits(owner());
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Credits));
+    call(owner(), 0, 1);
+    registry.claim_credit_earnings(user());
+    registry.claim_credit_earnings(user());
+}
+
+/// Offer a plan of `inferences_per_epoch` and subscribe the user in `epoch`
+fn subscribe(registry: &mut ModelRegistry, inferences_per_epoch: u32, epoch: u64) {
+    call(owner(), 0, epoch);
+    registry.set_subscription_plan(
+        MODEL_ID.to_string(),
+        PLAN_ID,
+        inferences_per_epoch,
+        U128(PLAN_PRICE),
+        true,
+    );
+    call(user(), PLAN_PRICE, epoch);
+    registry.subscribe(MODEL_ID.to_string(), PLAN_ID);
+}
+
+#[test]
+fn subscriptions_cover_their_quota() {
+    let mut registry = setup();
+    subscribe(&mut registry, 2, 1);
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Subscription));
+    request(&mut registry, "r2", Some(Payment::Subscription));
+    let subscription = registry.get_subscription(user(), MODEL_ID.to_string()).unwrap();
+    assert_eq!((subscription.epoch, subscription.used), (1, 2));
+}
+
+#[test]
+#[should_panic(expected = "Subscription quota used up for this epoch")]
+fn subscriptions_stop_at_their_quota() {
+    let mut registry = setup();
+    subscribe(&mut registry, 1, 1);
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Subscription));
+    request(&mut registry, "r2", Some(Payment::Subscription));
+}
+
+#[test]
+#[should_panic(expected = "Subscription quota used up for this epoch")]
+fn subscriptions_expire_with_their_epoch() {
+    let mut registry = setup();
+    subscribe(&mut registry, 5, 1);
+    call(user(), 0, 2);
+    request(&mut registry, "r1", Some(Payment::Subscription));
+}
+
+#[test]
+#[should_panic(expected = "Only the model owner can set plans")]
+fn only_the_model_owner_sets_plans() {
+    let mut registry = setup();
+    call(user(), 0, 1);
+    registry.set_subscription_plan(MODEL_ID.to_string(), PLAN_ID, 1, U128(PLAN_PRICE), true);
+}
+
+/// `ModelRegistry` as deployed before payments
+#[near(serializers=[borsh])]
+struct ModelRegistryV0 {
+    models: LookupMap<String, ModelMetadata>,
+    model_ids: UnorderedSet<String>,
+    requests: LookupMap<String, InferenceRequest>,
+    results: LookupMap<String, InferenceResult>,
+    owner: AccountId,
+}
+
+#[test]
+fn migrate_keeps_models_and_adds_payments() {
+    call(owner(), 0, 1);
+    let model = ModelMetadata {
+        model_id: MODEL_ID.to_string(),
+        name: "Model".to_string(),
+        description: "Test model".to_string(),
+        model_type: "llm".to_string(),
+        version: "1.0.0".to_string(),
+        owner: owner(),
+        tee_required: false,
+        attestation_required: false,
+        cost_per_inference: U128(COST),
+        created_at: 0,
+        updated_at: 0,
+        is_active: true,
+    };
+    let mut models = LookupMap::new(b"m");
+    models.insert(&MODEL_ID.to_string(), &model);
+    let mut model_ids = UnorderedSet::new(b"i");
+    model_ids.insert(&MODEL_ID.to_string());
+    env::state_write(&ModelRegistryV0 {
+        models,
+        model_ids,
+        requests: LookupMap::new(b"r"),
+        results: LookupMap::new(b"s"),
+        owner: owner(),
+    });
+
+    call(contract(), 0, 1);
+    let mut registry = ModelRegistry::migrate();
+    assert_eq!(registry.get_all_models(), vec![MODEL_ID.to_string()]);
+    assert!(registry.get_credits(user(), owner()).is_none());
+    call(user(), COST, 1);
+    registry.top_up_credits(owner());
+    call(user(), 0, 1);
+    request(&mut registry, "r1", Some(Payment::Credits));
+    assert_eq!(credits(&registry), (0, COST));
+}
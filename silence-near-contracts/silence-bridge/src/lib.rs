@@ -3,8 +3,9 @@ use near_sdk::collections::{UnorderedMap, LookupMap, Vector};
 use near_sdk::serde::{Deserialize, Serialize};
 #[cfg(not(target_arch = "wasm32"))]
 use near_sdk::schemars::JsonSchema;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::{env, near_bindgen, AccountId, Promise, PanicOnDefault, BorshStorageKey, NearToken};
+use silence_intent::{CrossChainIntent, DestinationAmount, FulfillmentReceipt, MESSAGE_VERSION};
 
 /// Storage keys for collections
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -16,18 +17,124 @@ enum StorageKey {
     ActiveSolvers,
     IntentsByCreator,
     IntentsBySolver,
+    Chains,
+    InboundIntents,
+    Receipts,
 }
 
-/// Supported blockchain networks
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq)]
+/// Well-known chain ids, shared with the Solana program
+pub const CHAIN_ID_SOLANA: u32 = 1;
+pub const CHAIN_ID_NEAR: u32 = 2;
+pub const CHAIN_ID_ZCASH: u32 = 3;
+
+/// Upper bound of `Solver.reputation_score`
+pub const MAX_REPUTATION: u32 = 1000;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Parameters of the time-weighted reputation model.
+///
+/// Mirrors `ReputationParams` in the Solana program; both sides use the same
+/// integer math so scores agree across chains.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), schemars(crate = "near_sdk::schemars"))]
 #[borsh(crate = "near_sdk::borsh")]
-pub enum Chain {
-    Near,
-    Solana,
-    Zcash,
+pub struct ReputationParams {
+    /// Time for an idle score to halve (0 = no decay)
+    pub half_life_secs: u64,
+    /// Points for every settled intent
+    pub success_points: u32,
+    /// Yocto of intent volume per bonus point
+    pub volume_unit: u128,
+    /// Cap on the volume bonus per intent
+    pub max_volume_bonus: u32,
+    /// Match-to-execution latency tolerated without penalty
+    pub target_latency_secs: u64,
+    /// Extra latency costing one point
+    pub latency_penalty_secs: u64,
+    /// Cap on the latency penalty per intent
+    pub max_latency_penalty: u32,
+    /// Points lost for a failed intent
+    pub failure_penalty: u32,
+}
+
+impl Default for ReputationParams {
+    fn default() -> Self {
+        Self {
+            half_life_secs: 30 * 86_400,
+            success_points: 1,
+            volume_unit: 10 * 10u128.pow(24), // 10 NEAR
+            max_volume_bonus: 10,
+            target_latency_secs: 600,
+            latency_penalty_secs: 600,
+            max_latency_penalty: 5,
+            failure_penalty: 5,
+        }
+    }
+}
+
+impl ReputationParams {
+    /// Decay `score` over `elapsed_secs`: halves every `half_life_secs`,
+    /// linearly interpolated between halvings
+    pub fn decay(&self, score: u32, elapsed_secs: u64) -> u32 {
+        self.decay_applied(score, elapsed_secs).0
+    }
+
+    /// `decay`, plus the seconds of `elapsed_secs` it accounts for: the whole
+    /// half-lives and the shortest remainder giving the same drop. The rest
+    /// has not lowered the score yet and carries over to the next update.
+    pub fn decay_applied(&self, score: u32, elapsed_secs: u64) -> (u32, u64) {
+        if self.half_life_secs == 0 || elapsed_secs == 0 {
+            return (score, elapsed_secs);
+        }
+        let halvings = elapsed_secs / self.half_life_secs;
+        if halvings >= 32 {
+            return (0, elapsed_secs);
+        }
+        let halved = score >> halvings;
+        let rem = elapsed_secs % self.half_life_secs;
+        let period = 2 * self.half_life_secs as u128;
+        let drop = (halved as u128 * rem as u128 / period) as u32;
+        let rem_applied = if halved == 0 {
+            rem
+        } else {
+            (drop as u128 * period).div_ceil(halved as u128) as u64
+        };
+        (halved - drop, halvings * self.half_life_secs + rem_applied)
+    }
+
+    /// Net points for an intent of `amount` executed `latency_secs` after matching
+    pub fn success_delta(&self, amount: u128, latency_secs: u64) -> i64 {
+        let volume_bonus = if self.volume_unit == 0 {
+            0
+        } else {
+            (amount / self.volume_unit).min(self.max_volume_bonus as u128) as i64
+        };
+        let late_secs = latency_secs.saturating_sub(self.target_latency_secs);
+        let latency_penalty = if self.latency_penalty_secs == 0 {
+            0
+        } else {
+            (late_secs / self.latency_penalty_secs).min(self.max_latency_penalty as u64) as i64
+        };
+        self.success_points as i64 + volume_bonus - latency_penalty
+    }
+}
+
+/// Chain registry entry
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), schemars(crate = "near_sdk::schemars"))]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ChainConfig {
+    pub chain_id: u32,
+    pub name: String,
+    pub enabled: bool,
+    pub min_amount: u128,
+    pub finality_seconds: u64,
+    pub fee_bps_override: Option<u32>,
 }
 
 /// Intent status lifecycle
@@ -45,6 +152,7 @@ pub enum IntentStatus {
     Settled,
     Failed,
     Disputed,
+    Cancelled,
 }
 
 /// Crosschain transfer intent
@@ -56,8 +164,8 @@ pub enum IntentStatus {
 pub struct Intent {
     pub intent_id: String,
     pub creator: AccountId,
-    pub source_chain: Chain,
-    pub destination_chain: Chain,
+    pub source_chain_id: u32,
+    pub destination_chain_id: u32,
     pub source_amount: u128,
     pub destination_amount: u128,
     pub source_token: String,
@@ -68,12 +176,33 @@ pub struct Intent {
     pub solver: Option<AccountId>,
     pub created_at: u64,
     pub expires_at: u64,
+    pub matched_at: Option<u64>,
+    pub execution_deadline: Option<u64>,
     pub executed_at: Option<u64>,
     pub source_tx_hash: Option<String>,
     pub destination_tx_hash: Option<String>,
     pub privacy_proof: Option<String>,
 }
 
+impl From<&Intent> for CrossChainIntent {
+    fn from(intent: &Intent) -> Self {
+        CrossChainIntent {
+            version: silence_intent::MESSAGE_VERSION,
+            source_chain_id: intent.source_chain_id,
+            destination_chain_id: intent.destination_chain_id,
+            intent_id: silence_intent::near_intent_id(&intent.intent_id),
+            creator: silence_intent::account_hash(intent.creator.as_str()),
+            source_token: silence_intent::token_hash(&intent.source_token),
+            source_amount: intent.source_amount,
+            destination_token_hash: silence_intent::token_hash(&intent.destination_token),
+            destination_amount: DestinationAmount::Plain(intent.destination_amount),
+            recipient_hash: silence_intent::recipient_hash(&intent.recipient),
+            is_shielded: intent.is_shielded,
+            expires_at: intent.expires_at / NANOS_PER_SEC,
+        }
+    }
+}
+
 /// Solver entity
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
@@ -82,7 +211,7 @@ pub struct Intent {
 #[borsh(crate = "near_sdk::borsh")]
 pub struct Solver {
     pub solver_id: AccountId,
-    pub supported_chains: Vec<Chain>,
+    pub supported_chains: Vec<u32>,
     pub stake: u128,
     pub reputation_score: u32,
     pub total_intents_executed: u64,
@@ -91,6 +220,87 @@ pub struct Solver {
     pub total_volume: u128,
     pub is_active: bool,
     pub registered_at: u64,
+    pub last_active_at: u64,
+    pub reputation_updated_at: u64,
+}
+
+impl Solver {
+    /// Reputation score with decay applied up to `now` (nanoseconds)
+    pub fn current_reputation(&self, params: &ReputationParams, now: u64) -> u32 {
+        let elapsed_secs = now.saturating_sub(self.reputation_updated_at) / NANOS_PER_SEC;
+        params.decay(self.reputation_score, elapsed_secs)
+    }
+
+    /// Decay the stored score up to `now`, keeping the time too short to
+    /// have cost a point for the next update
+    pub fn refresh_reputation(&mut self, params: &ReputationParams, now: u64) {
+        let elapsed_secs = now.saturating_sub(self.reputation_updated_at) / NANOS_PER_SEC;
+        let (score, applied) = params.decay_applied(self.reputation_score, elapsed_secs);
+        self.reputation_score = score;
+        self.reputation_updated_at += applied * NANOS_PER_SEC;
+    }
+
+    /// Decay, then credit a settled intent
+    pub fn record_success(&mut self, params: &ReputationParams, now: u64, amount: u128, latency_secs: u64) {
+        self.refresh_reputation(params, now);
+        let score = self.reputation_score as i64 + params.success_delta(amount, latency_secs);
+        self.reputation_score = score.clamp(0, MAX_REPUTATION as i64) as u32;
+        self.last_active_at = now;
+    }
+
+    /// Decay, then penalize a failed intent
+    pub fn record_failure(&mut self, params: &ReputationParams, now: u64) {
+        self.refresh_reputation(params, now);
+        self.reputation_score = self.reputation_score.saturating_sub(params.failure_penalty);
+        self.last_active_at = now;
+    }
+}
+
+/// Status of an intent relayed from another chain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), schemars(crate = "near_sdk::schemars"))]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum InboundStatus {
+    Pending,    // Waiting for relayer attestations
+    Open,       // Attested, awaiting a solver
+    Fulfilled,  // Delivered, receipt issued
+}
+
+/// Intent created on another chain and delivered on NEAR
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), schemars(crate = "near_sdk::schemars"))]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct InboundIntent {
+    /// Hex `CrossChainIntent::hash`
+    pub intent_hash: String,
+    /// Borsh-encoded `CrossChainIntent`
+    pub encoded: Vec<u8>,
+    pub source_chain_id: u32,
+    pub attestations: Vec<AccountId>,
+    pub status: InboundStatus,
+    pub receipt_hash: Option<String>,
+}
+
+/// Receipt for a fulfilled inbound intent, relayed back to the source chain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(JsonSchema))]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), schemars(crate = "near_sdk::schemars"))]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct InboundReceipt {
+    /// Hex `FulfillmentReceipt::hash`
+    pub receipt_hash: String,
+    pub intent_hash: String,
+    /// Borsh-encoded `FulfillmentReceipt`
+    pub encoded: Vec<u8>,
+    pub solver_id: AccountId,
+    pub recipient: AccountId,
+    pub amount: u128,
+    pub fulfilled_at: u64,
 }
 
 /// Intent match proposal
@@ -106,6 +316,129 @@ pub struct IntentMatch {
     pub created_at: u64,
 }
 
+/// Chain enum of the first deployment, replaced by chain ids
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+enum ChainV0 {
+    Near,
+    Solana,
+    Zcash,
+}
+
+impl ChainV0 {
+    fn chain_id(&self) -> u32 {
+        match self {
+            ChainV0::Near => CHAIN_ID_NEAR,
+            ChainV0::Solana => CHAIN_ID_SOLANA,
+            ChainV0::Zcash => CHAIN_ID_ZCASH,
+        }
+    }
+}
+
+/// `Intent` as stored by the first deployment
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct IntentV0 {
+    intent_id: String,
+    creator: AccountId,
+    source_chain: ChainV0,
+    destination_chain: ChainV0,
+    source_amount: u128,
+    destination_amount: u128,
+    source_token: String,
+    destination_token: String,
+    recipient: String,
+    is_shielded: bool,
+    status: IntentStatus,
+    solver: Option<AccountId>,
+    created_at: u64,
+    expires_at: u64,
+    executed_at: Option<u64>,
+    source_tx_hash: Option<String>,
+    destination_tx_hash: Option<String>,
+    privacy_proof: Option<String>,
+}
+
+impl From<IntentV0> for Intent {
+    /// Matched intents get no execution deadline, as before deadlines
+    fn from(intent: IntentV0) -> Self {
+        Intent {
+            intent_id: intent.intent_id,
+            creator: intent.creator,
+            source_chain_id: intent.source_chain.chain_id(),
+            destination_chain_id: intent.destination_chain.chain_id(),
+            source_amount: intent.source_amount,
+            destination_amount: intent.destination_amount,
+            source_token: intent.source_token,
+            destination_token: intent.destination_token,
+            recipient: intent.recipient,
+            is_shielded: intent.is_shielded,
+            status: intent.status,
+            solver: intent.solver,
+            created_at: intent.created_at,
+            expires_at: intent.expires_at,
+            matched_at: None,
+            execution_deadline: None,
+            executed_at: intent.executed_at,
+            source_tx_hash: intent.source_tx_hash,
+            destination_tx_hash: intent.destination_tx_hash,
+            privacy_proof: intent.privacy_proof,
+        }
+    }
+}
+
+/// `Solver` as stored by the first deployment
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct SolverV0 {
+    solver_id: AccountId,
+    supported_chains: Vec<ChainV0>,
+    stake: u128,
+    reputation_score: u32,
+    total_intents_executed: u64,
+    successful_intents: u64,
+    failed_intents: u64,
+    total_volume: u128,
+    is_active: bool,
+    registered_at: u64,
+}
+
+impl SolverV0 {
+    /// Current layout; reputation starts decaying at `now`
+    fn migrate(self, now: u64) -> Solver {
+        Solver {
+            solver_id: self.solver_id,
+            supported_chains: self.supported_chains.iter().map(ChainV0::chain_id).collect(),
+            stake: self.stake,
+            reputation_score: self.reputation_score.min(MAX_REPUTATION),
+            total_intents_executed: self.total_intents_executed,
+            successful_intents: self.successful_intents,
+            failed_intents: self.failed_intents,
+            total_volume: self.total_volume,
+            is_active: self.is_active,
+            registered_at: self.registered_at,
+            last_active_at: self.registered_at,
+            reputation_updated_at: now,
+        }
+    }
+}
+
+/// Contract state of the first deployment, read by `migrate`
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct SilenceBridgeRegistryV0 {
+    intents: UnorderedMap<String, IntentV0>,
+    solvers: UnorderedMap<AccountId, SolverV0>,
+    matches: UnorderedMap<String, IntentMatch>,
+    active_solvers: Vector<AccountId>,
+    intents_by_creator: LookupMap<AccountId, Vec<String>>,
+    intents_by_solver: LookupMap<AccountId, Vec<String>>,
+    owner: AccountId,
+    min_solver_stake: u128,
+    protocol_fee_bps: u32,
+    total_volume: u128,
+}
+
 /// Main contract
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -121,6 +454,16 @@ pub struct SilenceBridgeRegistry {
     pub min_solver_stake: u128,
     pub protocol_fee_bps: u32,
     pub total_volume: u128,
+    pub guardian: AccountId,
+    pub paused: bool,
+    pub chains: UnorderedMap<u32, ChainConfig>,
+    pub reputation: ReputationParams,
+    pub default_execution_secs: u64,
+    pub max_execution_secs: u64,
+    pub relayers: Vec<AccountId>,
+    pub relayer_threshold: u32,
+    pub inbound_intents: UnorderedMap<String, InboundIntent>,
+    pub receipts: UnorderedMap<String, InboundReceipt>,
 }
 
 #[near_bindgen]
@@ -135,18 +478,70 @@ impl SilenceBridgeRegistry {
             active_solvers: Vector::new(StorageKey::ActiveSolvers),
             intents_by_creator: LookupMap::new(StorageKey::IntentsByCreator),
             intents_by_solver: LookupMap::new(StorageKey::IntentsBySolver),
-            owner,
             min_solver_stake,
             protocol_fee_bps,
             total_volume: 0,
+            guardian: owner.clone(),
+            paused: false,
+            chains: UnorderedMap::new(StorageKey::Chains),
+            reputation: ReputationParams::default(),
+            default_execution_secs: 3_600,
+            max_execution_secs: 86_400,
+            relayers: Vec::new(),
+            relayer_threshold: 0,
+            inbound_intents: UnorderedMap::new(StorageKey::InboundIntents),
+            receipts: UnorderedMap::new(StorageKey::Receipts),
+            owner,
+        }
+    }
+
+    /// Upgrade state written by the first deployment; call once right after redeploying.
+    ///
+    /// Rewrites every stored intent and solver in the current layout and
+    /// registers the chains the first deployment supported, with no minimum.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let mut old: SilenceBridgeRegistryV0 = env::state_read().expect("No contract state to migrate");
+        let intents = old.intents.to_vec();
+        let solvers = old.solvers.to_vec();
+        old.intents.clear();
+        old.solvers.clear();
+
+        let mut state = Self {
+            matches: old.matches,
+            active_solvers: old.active_solvers,
+            intents_by_creator: old.intents_by_creator,
+            intents_by_solver: old.intents_by_solver,
+            total_volume: old.total_volume,
+            ..Self::new(old.owner, U128(old.min_solver_stake), old.protocol_fee_bps)
+        };
+        for (intent_id, intent) in intents {
+            state.intents.insert(&intent_id, &intent.into());
+        }
+        let now = env::block_timestamp();
+        for (solver_id, solver) in solvers {
+            state.solvers.insert(&solver_id, &solver.migrate(now));
+        }
+        for (chain_id, name) in [(CHAIN_ID_NEAR, "NEAR"), (CHAIN_ID_SOLANA, "Solana"), (CHAIN_ID_ZCASH, "Zcash")] {
+            let chain = ChainConfig {
+                chain_id,
+                name: name.to_string(),
+                enabled: true,
+                min_amount: 0,
+                finality_seconds: 0,
+                fee_bps_override: None,
+            };
+            state.chains.insert(&chain_id, &chain);
         }
+        state
     }
 
     #[payable]
     pub fn create_intent(
         &mut self,
         intent_id: String,
-        destination_chain: Chain,
+        destination_chain_id: u32,
         destination_amount: u128,
         destination_token: String,
         recipient: String,
@@ -158,12 +553,14 @@ impl SilenceBridgeRegistry {
         
         assert!(source_amount > 0, "Must attach deposit");
         assert!(self.intents.get(&intent_id).is_none(), "Intent already exists");
+        let chain = self.assert_operational(destination_chain_id);
+        assert!(source_amount >= chain.min_amount, "Amount below chain minimum");
         
         let intent = Intent {
             intent_id: intent_id.clone(),
             creator: creator.clone(),
-            source_chain: Chain::Near,
-            destination_chain,
+            source_chain_id: CHAIN_ID_NEAR,
+            destination_chain_id,
             source_amount,
             destination_amount,
             source_token: "NEAR".to_string(),
@@ -174,6 +571,8 @@ impl SilenceBridgeRegistry {
             solver: None,
             created_at: env::block_timestamp(),
             expires_at: env::block_timestamp() + (ttl_seconds * 1_000_000_000),
+            matched_at: None,
+            execution_deadline: None,
             executed_at: None,
             source_tx_hash: None,
             destination_tx_hash: None,
@@ -192,7 +591,7 @@ impl SilenceBridgeRegistry {
     }
 
     #[payable]
-    pub fn register_solver(&mut self, supported_chains: Vec<Chain>) {
+    pub fn register_solver(&mut self, supported_chains: Vec<u32>) {
         let solver_id = env::predecessor_account_id();
         let stake = env::attached_deposit().as_yoctonear();
         
@@ -211,6 +610,8 @@ impl SilenceBridgeRegistry {
             total_volume: 0,
             is_active: true,
             registered_at: env::block_timestamp(),
+            last_active_at: env::block_timestamp(),
+            reputation_updated_at: env::block_timestamp(),
         };
         
         self.solvers.insert(&solver_id, &solver);
@@ -223,7 +624,7 @@ impl SilenceBridgeRegistry {
         &mut self,
         intent_id: String,
         _proposed_rate: u128,
-        _estimated_time: u64,
+        estimated_time: u64,
     ) {
         let solver_id = env::predecessor_account_id();
         
@@ -232,16 +633,23 @@ impl SilenceBridgeRegistry {
         
         assert!(intent.status == IntentStatus::Created, "Intent already matched");
         assert!(solver.is_active, "Solver not active");
+        self.assert_operational(intent.destination_chain_id);
         assert!(env::block_timestamp() < intent.expires_at, "Intent expired");
         
         assert!(
-            solver.supported_chains.contains(&intent.source_chain)
-                && solver.supported_chains.contains(&intent.destination_chain),
+            solver.supported_chains.contains(&intent.source_chain_id)
+                && solver.supported_chains.contains(&intent.destination_chain_id),
             "Solver doesn't support required chains"
         );
         
         intent.status = IntentStatus::Matched;
         intent.solver = Some(solver_id.clone());
+        intent.matched_at = Some(env::block_timestamp());
+        
+        // Execution window in seconds; 0 falls back to the default
+        let execution_secs = if estimated_time == 0 { self.default_execution_secs } else { estimated_time };
+        assert!(execution_secs <= self.max_execution_secs, "Estimated time too long");
+        intent.execution_deadline = Some(env::block_timestamp() + execution_secs * NANOS_PER_SEC);
         self.intents.insert(&intent_id, &intent);
         
         let mut solver_intents = self.intents_by_solver.get(&solver_id).unwrap_or_default();
@@ -263,6 +671,11 @@ impl SilenceBridgeRegistry {
         
         assert_eq!(intent.solver, Some(solver_id.clone()), "Not the matched solver");
         assert!(intent.status == IntentStatus::Matched, "Invalid status");
+        self.assert_operational(intent.destination_chain_id);
+        assert!(
+            intent.execution_deadline.map_or(true, |deadline| env::block_timestamp() <= deadline),
+            "Execution deadline passed"
+        );
         
         intent.status = IntentStatus::Executed;
         intent.executed_at = Some(env::block_timestamp());
@@ -278,11 +691,18 @@ impl SilenceBridgeRegistry {
         let mut intent = self.intents.get(&intent_id).expect("Intent not found");
         
         assert!(intent.status == IntentStatus::Executed, "Not executed");
+        let chain = self.assert_operational(intent.destination_chain_id);
+        let executed_at = intent.executed_at.expect("Not executed");
+        assert!(
+            env::block_timestamp() >= executed_at + chain.finality_seconds * 1_000_000_000,
+            "Finality not reached"
+        );
         
         let solver_id = intent.solver.clone().expect("No solver");
         let mut solver = self.solvers.get(&solver_id).expect("Solver not found");
         
-        let protocol_fee = (intent.source_amount * self.protocol_fee_bps as u128) / 10000;
+        let fee_bps = chain.fee_bps_override.unwrap_or(self.protocol_fee_bps);
+        let protocol_fee = (intent.source_amount * fee_bps as u128) / 10000;
         let solver_reward = intent.source_amount - protocol_fee;
         
         Promise::new(solver_id.clone()).transfer(NearToken::from_yoctonear(solver_reward));
@@ -291,7 +711,8 @@ impl SilenceBridgeRegistry {
         solver.total_intents_executed += 1;
         solver.successful_intents += 1;
         solver.total_volume += intent.source_amount;
-        solver.reputation_score += 1;
+        let latency_secs = executed_at.saturating_sub(intent.matched_at.unwrap_or(executed_at)) / NANOS_PER_SEC;
+        solver.record_success(&self.reputation, env::block_timestamp(), intent.source_amount, latency_secs);
         self.solvers.insert(&solver_id, &solver);
         
         intent.status = IntentStatus::Settled;
@@ -313,7 +734,7 @@ impl SilenceBridgeRegistry {
         
         let mut solver = self.solvers.get(&solver_id).expect("Solver not found");
         solver.failed_intents += 1;
-        solver.reputation_score = solver.reputation_score.saturating_sub(5);
+        solver.record_failure(&self.reputation, env::block_timestamp());
         self.solvers.insert(&solver_id, &solver);
         
         intent.status = IntentStatus::Failed;
@@ -322,6 +743,155 @@ impl SilenceBridgeRegistry {
         env::log_str(&format!("Intent {} failed: {}", intent_id, reason));
     }
 
+    /// Time out a matched intent past its execution deadline (anyone may call).
+    /// Refunds the creator, or reopens the intent for rematching when the
+    /// creator asks to before expiry. The solver is penalized either way.
+    pub fn timeout_intent(&mut self, intent_id: String, reopen: bool) {
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+        
+        assert!(intent.status == IntentStatus::Matched, "Invalid status");
+        let deadline = intent.execution_deadline.expect("No execution deadline");
+        assert!(env::block_timestamp() > deadline, "Execution deadline not reached");
+        
+        let solver_id = intent.solver.clone().expect("No solver");
+        let mut solver = self.solvers.get(&solver_id).expect("Solver not found");
+        solver.failed_intents += 1;
+        solver.record_failure(&self.reputation, env::block_timestamp());
+        self.solvers.insert(&solver_id, &solver);
+        
+        if reopen {
+            assert_eq!(env::predecessor_account_id(), intent.creator, "Only creator can reopen");
+            assert!(env::block_timestamp() < intent.expires_at, "Intent expired");
+            intent.status = IntentStatus::Created;
+            intent.solver = None;
+            intent.matched_at = None;
+            intent.execution_deadline = None;
+        } else {
+            Promise::new(intent.creator.clone()).transfer(NearToken::from_yoctonear(intent.source_amount));
+            intent.status = IntentStatus::Failed;
+        }
+        self.intents.insert(&intent_id, &intent);
+        
+        env::log_str(&format!("Intent {} timed out (solver {}, reopened: {})", intent_id, solver_id, reopen));
+    }
+
+    /// Cancel an unmatched intent and refund creator (allowed while paused)
+    pub fn cancel_intent(&mut self, intent_id: String) {
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+        
+        assert_eq!(intent.creator, env::predecessor_account_id(), "Not the creator");
+        assert!(intent.status == IntentStatus::Created, "Invalid status");
+        
+        Promise::new(intent.creator.clone()).transfer(NearToken::from_yoctonear(intent.source_amount));
+        
+        intent.status = IntentStatus::Cancelled;
+        self.intents.insert(&intent_id, &intent);
+        
+        env::log_str(&format!("Intent {} cancelled", intent_id));
+    }
+
+    // Inbound intents
+
+    /// Attest a canonical intent from another chain (relayers only). The
+    /// intent opens for fulfillment once `relayer_threshold` relayers agree.
+    pub fn register_inbound_intent(&mut self, encoded: Base64VecU8) {
+        let relayer = env::predecessor_account_id();
+        assert!(self.relayers.contains(&relayer), "Not a relayer");
+        
+        let intent = CrossChainIntent::decode(&encoded.0).expect("Invalid intent encoding");
+        assert_eq!(intent.version, MESSAGE_VERSION, "Unsupported intent version");
+        assert_eq!(intent.destination_chain_id, CHAIN_ID_NEAR, "Intent not destined for NEAR");
+        self.assert_operational(intent.source_chain_id);
+        
+        let intent_hash = hex::encode(intent.hash());
+        let mut inbound = self.inbound_intents.get(&intent_hash).unwrap_or_else(|| InboundIntent {
+            intent_hash: intent_hash.clone(),
+            encoded: encoded.0.clone(),
+            source_chain_id: intent.source_chain_id,
+            attestations: Vec::new(),
+            status: InboundStatus::Pending,
+            receipt_hash: None,
+        });
+        
+        assert!(!inbound.attestations.contains(&relayer), "Already attested");
+        inbound.attestations.push(relayer);
+        if inbound.status == InboundStatus::Pending
+            && inbound.attestations.len() as u32 >= self.relayer_threshold
+        {
+            inbound.status = InboundStatus::Open;
+            env::log_str(&format!("Inbound intent {} registered", intent_hash));
+        }
+        self.inbound_intents.insert(&intent_hash, &inbound);
+    }
+
+    /// Deliver an inbound intent: the attached deposit is sent to `recipient`
+    /// and a receipt naming `source_solver` (hex source-chain key) is issued.
+    /// `blinding` (hex) opens committed destination amounts.
+    #[payable]
+    pub fn fulfill_inbound_intent(
+        &mut self,
+        intent_hash: String,
+        recipient: AccountId,
+        source_solver: String,
+        blinding: Option<String>,
+    ) -> String {
+        let solver_id = env::predecessor_account_id();
+        let solver = self.solvers.get(&solver_id).expect("Solver not found");
+        assert!(solver.is_active, "Solver not active");
+        
+        let mut inbound = self.inbound_intents.get(&intent_hash).expect("Inbound intent not found");
+        assert!(inbound.status == InboundStatus::Open, "Inbound intent not open");
+        self.assert_operational(inbound.source_chain_id);
+        
+        let intent = CrossChainIntent::decode(&inbound.encoded).expect("Invalid intent encoding");
+        let now_secs = env::block_timestamp() / NANOS_PER_SEC;
+        assert!(now_secs < intent.expires_at, "Intent expired");
+        assert_eq!(
+            silence_intent::recipient_hash(recipient.as_str()),
+            intent.recipient_hash,
+            "Recipient mismatch"
+        );
+        
+        let amount = env::attached_deposit().as_yoctonear();
+        let blinding = blinding.map(|b| decode_hash(&b));
+        assert!(
+            intent.destination_amount.is_met_by(amount, blinding.as_ref()),
+            "Amount does not match intent"
+        );
+        
+        let receipt = FulfillmentReceipt {
+            version: MESSAGE_VERSION,
+            intent_hash: intent.hash(),
+            destination_chain_id: CHAIN_ID_NEAR,
+            solver: decode_hash(&source_solver),
+            recipient_hash: intent.recipient_hash,
+            amount,
+            fulfilled_at: now_secs,
+        };
+        let receipt_hash = hex::encode(receipt.hash());
+        
+        Promise::new(recipient.clone()).transfer(NearToken::from_yoctonear(amount));
+        
+        self.receipts.insert(&receipt_hash, &InboundReceipt {
+            receipt_hash: receipt_hash.clone(),
+            intent_hash: intent_hash.clone(),
+            encoded: receipt.encode(),
+            solver_id: solver_id.clone(),
+            recipient,
+            amount,
+            fulfilled_at: now_secs,
+        });
+        inbound.status = InboundStatus::Fulfilled;
+        inbound.receipt_hash = Some(receipt_hash.clone());
+        self.inbound_intents.insert(&intent_hash, &inbound);
+        
+        env::log_str(&format!(
+            "Inbound intent {} fulfilled by {}, receipt {}",
+            intent_hash, solver_id, receipt_hash
+        ));
+        receipt_hash
+    }
+
     // View methods
 
     pub fn get_intent(&self, intent_id: String) -> Option<Intent> {
@@ -356,8 +926,8 @@ impl SilenceBridgeRegistry {
 
     pub fn find_solvers_for_chains(
         &self,
-        source_chain: Chain,
-        destination_chain: Chain,
+        source_chain_id: u32,
+        destination_chain_id: u32,
     ) -> Vec<Solver> {
         (0..self.active_solvers.len())
             .filter_map(|i| {
@@ -366,12 +936,43 @@ impl SilenceBridgeRegistry {
             })
             .filter(|s| {
                 s.is_active
-                    && s.supported_chains.contains(&source_chain)
-                    && s.supported_chains.contains(&destination_chain)
+                    && s.supported_chains.contains(&source_chain_id)
+                    && s.supported_chains.contains(&destination_chain_id)
             })
             .collect()
     }
 
+    /// Reputation score with decay applied up to the current block
+    pub fn get_solver_reputation(&self, solver_id: AccountId) -> Option<u32> {
+        self.solvers
+            .get(&solver_id)
+            .map(|solver| solver.current_reputation(&self.reputation, env::block_timestamp()))
+    }
+
+    pub fn get_inbound_intent(&self, intent_hash: String) -> Option<InboundIntent> {
+        self.inbound_intents.get(&intent_hash)
+    }
+
+    pub fn get_receipt(&self, receipt_hash: String) -> Option<InboundReceipt> {
+        self.receipts.get(&receipt_hash)
+    }
+
+    pub fn get_relayers(&self) -> (Vec<AccountId>, u32) {
+        (self.relayers.clone(), self.relayer_threshold)
+    }
+
+    pub fn get_reputation_params(&self) -> ReputationParams {
+        self.reputation.clone()
+    }
+
+    pub fn get_chain(&self, chain_id: u32) -> Option<ChainConfig> {
+        self.chains.get(&chain_id)
+    }
+
+    pub fn get_chains(&self) -> Vec<ChainConfig> {
+        self.chains.values().collect()
+    }
+
     pub fn get_stats(&self) -> serde_json::Value {
         serde_json::json!({
             "total_intents": self.intents.len(),
@@ -379,6 +980,7 @@ impl SilenceBridgeRegistry {
             "active_solvers": self.active_solvers.len(),
             "total_volume": self.total_volume.to_string(),
             "protocol_fee_bps": self.protocol_fee_bps,
+            "paused": self.paused,
         })
     }
 
@@ -390,6 +992,21 @@ impl SilenceBridgeRegistry {
         self.protocol_fee_bps = fee_bps;
     }
 
+    pub fn set_execution_window(&mut self, default_execution_secs: u64, max_execution_secs: u64) {
+        assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        assert!(
+            default_execution_secs > 0 && default_execution_secs <= max_execution_secs,
+            "Invalid execution window"
+        );
+        self.default_execution_secs = default_execution_secs;
+        self.max_execution_secs = max_execution_secs;
+    }
+
+    pub fn set_reputation_params(&mut self, params: ReputationParams) {
+        assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        self.reputation = params;
+    }
+
     pub fn deactivate_solver(&mut self, solver_id: AccountId) {
         assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
         
@@ -397,4 +1014,128 @@ impl SilenceBridgeRegistry {
         solver.is_active = false;
         self.solvers.insert(&solver_id, &solver);
     }
+
+    /// Set the relayer accounts and how many must attest an inbound intent
+    pub fn set_relayers(&mut self, relayers: Vec<AccountId>, threshold: u32) {
+        assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        assert!(
+            threshold > 0 && threshold as usize <= relayers.len(),
+            "Invalid relayer threshold"
+        );
+        self.relayers = relayers;
+        self.relayer_threshold = threshold;
+    }
+
+    // Emergency controls
+
+    pub fn set_guardian(&mut self, guardian: AccountId) {
+        assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        self.guardian = guardian;
+    }
+
+    /// Pause or unpause the bridge (guardian may only pause)
+    pub fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.assert_owner_or_guardian();
+        } else {
+            assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        }
+        self.paused = paused;
+        
+        env::log_str(&format!("Bridge paused: {}", paused));
+    }
+
+    /// Trip or reset the circuit breaker for a chain (guardian may only disable)
+    pub fn set_chain_enabled(&mut self, chain_id: u32, enabled: bool) {
+        if enabled {
+            assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        } else {
+            self.assert_owner_or_guardian();
+        }
+        
+        let mut chain = self.chains.get(&chain_id).expect("Chain not found");
+        chain.enabled = enabled;
+        self.chains.insert(&chain_id, &chain);
+    }
+
+    // Chain registry
+
+    pub fn register_chain(
+        &mut self,
+        chain_id: u32,
+        name: String,
+        min_amount: U128,
+        finality_seconds: u64,
+        fee_bps_override: Option<u32>,
+    ) {
+        assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        assert!(self.chains.get(&chain_id).is_none(), "Chain already registered");
+        if let Some(fee) = fee_bps_override {
+            assert!(fee <= 1000, "Fee too high");
+        }
+        
+        let chain = ChainConfig {
+            chain_id,
+            name,
+            enabled: true,
+            min_amount: min_amount.into(),
+            finality_seconds,
+            fee_bps_override,
+        };
+        self.chains.insert(&chain_id, &chain);
+        
+        env::log_str(&format!("Chain registered: {}", chain_id));
+    }
+
+    pub fn update_chain(
+        &mut self,
+        chain_id: u32,
+        name: Option<String>,
+        min_amount: Option<U128>,
+        finality_seconds: Option<u64>,
+        fee_bps_override: Option<Option<u32>>,
+    ) {
+        assert_eq!(env::predecessor_account_id(), self.owner, "Only owner");
+        
+        let mut chain = self.chains.get(&chain_id).expect("Chain not found");
+        if let Some(name) = name {
+            chain.name = name;
+        }
+        if let Some(min_amount) = min_amount {
+            chain.min_amount = min_amount.into();
+        }
+        if let Some(finality_seconds) = finality_seconds {
+            chain.finality_seconds = finality_seconds;
+        }
+        if let Some(fee_bps_override) = fee_bps_override {
+            if let Some(fee) = fee_bps_override {
+                assert!(fee <= 1000, "Fee too high");
+            }
+            chain.fee_bps_override = fee_bps_override;
+        }
+        self.chains.insert(&chain_id, &chain);
+    }
+
+    fn assert_owner_or_guardian(&self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == self.owner || caller == self.guardian,
+            "Only owner or guardian"
+        );
+    }
+
+    fn assert_operational(&self, chain_id: u32) -> ChainConfig {
+        assert!(!self.paused, "Bridge is paused");
+        let chain = self.chains.get(&chain_id).expect("Chain not registered");
+        assert!(chain.enabled, "Chain is disabled");
+        chain
+    }
+}
+
+/// Decode a hex-encoded 32-byte value
+fn decode_hash(value: &str) -> [u8; 32] {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .expect("Expected 32 hex-encoded bytes")
 }
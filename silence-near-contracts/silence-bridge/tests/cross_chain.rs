@@ -0,0 +1,74 @@
+//! Canonical cross-chain form of the bridge's intents; mirrors the Solana
+//! program's tests.
+
+use silence_bridge::{Intent, IntentStatus, CHAIN_ID_NEAR, CHAIN_ID_SOLANA};
+use silence_intent::{CrossChainIntent, DestinationAmount, MESSAGE_VERSION};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// The NEAR vector of silence-intent/tests/golden.rs as the bridge stores it
+fn intent() -> Intent {
+    Intent {
+        intent_id: "intent_42".to_string(),
+        creator: "bob.near".parse().unwrap(),
+        source_chain_id: CHAIN_ID_NEAR,
+        destination_chain_id: CHAIN_ID_SOLANA,
+        source_amount: 5 * 10u128.pow(24),
+        destination_amount: 2_500_000_000,
+        source_token: "near".to_string(),
+        destination_token: "So11111111111111111111111111111111111111112".to_string(),
+        recipient: "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin".to_string(),
+        is_shielded: false,
+        status: IntentStatus::Created,
+        solver: None,
+        created_at: 1_699_996_400 * NANOS_PER_SEC,
+        expires_at: 1_700_000_000 * NANOS_PER_SEC,
+        matched_at: None,
+        execution_deadline: None,
+        executed_at: None,
+        source_tx_hash: None,
+        destination_tx_hash: None,
+        privacy_proof: None,
+    }
+}
+
+#[test]
+fn intents_convert_field_by_field() {
+    let intent = intent();
+    let canonical = CrossChainIntent::from(&intent);
+    assert_eq!(
+        canonical,
+        CrossChainIntent {
+            version: MESSAGE_VERSION,
+            source_chain_id: CHAIN_ID_NEAR,
+            destination_chain_id: CHAIN_ID_SOLANA,
+            intent_id: silence_intent::near_intent_id(&intent.intent_id),
+            creator: silence_intent::account_hash("bob.near"),
+            source_token: silence_intent::token_hash(&intent.source_token),
+            source_amount: intent.source_amount,
+            destination_token_hash: silence_intent::token_hash(&intent.destination_token),
+            destination_amount: DestinationAmount::Plain(intent.destination_amount),
+            recipient_hash: silence_intent::recipient_hash(&intent.recipient),
+            is_shielded: false,
+            expires_at: 1_700_000_000,
+        }
+    );
+    assert_eq!(CrossChainIntent::decode(&canonical.encode()).unwrap(), canonical);
+}
+
+#[test]
+fn converted_intents_hash_like_the_golden_vector() {
+    let hash = CrossChainIntent::from(&intent()).hash();
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    assert_eq!(hex, "b97e237f9c6ee8173ec7656dcff10289ad20c169581c7d7c720e4a56e1860bac");
+}
+
+#[test]
+fn lifecycle_fields_do_not_change_the_canonical_form() {
+    let mut matched = intent();
+    matched.status = IntentStatus::Matched;
+    matched.solver = Some("solver.near".parse().unwrap());
+    matched.matched_at = Some(1_699_999_000 * NANOS_PER_SEC);
+    matched.execution_deadline = Some(1_700_002_600 * NANOS_PER_SEC);
+    assert_eq!(CrossChainIntent::from(&matched), CrossChainIntent::from(&intent()));
+}
@@ -0,0 +1,155 @@
+//! Execution deadlines of matched intents and timing out the ones that miss
+//! them; mirrors the Solana program's tests.
+
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::{accounts, VMContextBuilder};
+use near_sdk::{testing_env, AccountId, NearToken};
+use silence_bridge::{IntentStatus, SilenceBridgeRegistry, CHAIN_ID_NEAR, CHAIN_ID_SOLANA};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const INTENT_ID: &str = "intent";
+const AMOUNT: u128 = 1_000;
+const TTL_SECS: u64 = 3_600;
+
+fn owner() -> AccountId {
+    accounts(0)
+}
+
+fn creator() -> AccountId {
+    accounts(1)
+}
+
+fn solver() -> AccountId {
+    accounts(2)
+}
+
+/// Call the contract as `caller` with `deposit` yocto attached, at `secs`
+fn call(caller: AccountId, deposit: u128, secs: u64) {
+    let context = VMContextBuilder::new()
+        .current_account_id("bridge.near".parse().unwrap())
+        .signer_account_id(caller.clone())
+        .predecessor_account_id(caller)
+        .attached_deposit(NearToken::from_yoctonear(deposit))
+        .block_timestamp(secs * NANOS_PER_SEC)
+        .build();
+    testing_env!(context);
+}
+
+/// Bridge with a registered solver and an intent to Solana, created at 0s
+fn setup() -> SilenceBridgeRegistry {
+    call(owner(), 0, 0);
+    let mut bridge = SilenceBridgeRegistry::new(owner(), U128(0), 50);
+    bridge.register_chain(CHAIN_ID_SOLANA, "Solana".to_string(), U128(0), 0, None);
+    call(solver(), 0, 0);
+    bridge.register_solver(vec![CHAIN_ID_NEAR, CHAIN_ID_SOLANA]);
+    call(creator(), AMOUNT, 0);
+    bridge.create_intent(
+        INTENT_ID.to_string(),
+        CHAIN_ID_SOLANA,
+        AMOUNT,
+        "SOL".to_string(),
+        "recipient".to_string(),
+        false,
+        TTL_SECS,
+    );
+    bridge
+}
+
+/// `setup` with the intent matched at 0s, to execute within `estimated_time`
+fn matched(estimated_time: u64) -> SilenceBridgeRegistry {
+    let mut bridge = setup();
+    call(solver(), 0, 0);
+    bridge.match_intent(INTENT_ID.to_string(), 0, estimated_time);
+    bridge
+}
+
+fn deadline(bridge: &SilenceBridgeRegistry) -> Option<u64> {
+    bridge.get_intent(INTENT_ID.to_string()).unwrap().execution_deadline
+}
+
+#[test]
+fn matches_default_to_the_execution_window() {
+    assert_eq!(deadline(&matched(0)), Some(3_600 * NANOS_PER_SEC));
+}
+
+#[test]
+fn estimated_times_set_the_deadline() {
+    assert_eq!(deadline(&matched(10)), Some(10 * NANOS_PER_SEC));
+}
+
+#[test]
+#[should_panic(expected = "Estimated time too long")]
+fn matches_stay_within_the_max_execution_window() {
+    let mut bridge = setup();
+    call(owner(), 0, 0);
+    bridge.set_execution_window(60, 120);
+    call(solver(), 0, 0);
+    bridge.match_intent(INTENT_ID.to_string(), 0, 121);
+}
+
+#[test]
+fn execution_is_allowed_up_to_the_deadline() {
+    let mut bridge = matched(10);
+    call(solver(), 0, 10);
+    bridge.execute_intent(INTENT_ID.to_string(), "tx".to_string(), None);
+    assert!(bridge.get_intent(INTENT_ID.to_string()).unwrap().status == IntentStatus::Executed);
+}
+
+#[test]
+#[should_panic(expected = "Execution deadline passed")]
+fn execution_after_the_deadline_fails() {
+    let mut bridge = matched(10);
+    call(solver(), 0, 11);
+    bridge.execute_intent(INTENT_ID.to_string(), "tx".to_string(), None);
+}
+
+#[test]
+#[should_panic(expected = "Execution deadline not reached")]
+fn timeouts_wait_for_the_deadline() {
+    let mut bridge = matched(10);
+    call(owner(), 0, 10);
+    bridge.timeout_intent(INTENT_ID.to_string(), false);
+}
+
+#[test]
+fn timeouts_refund_and_penalize_the_solver() {
+    let mut bridge = matched(10);
+    call(owner(), 0, 11);
+    bridge.timeout_intent(INTENT_ID.to_string(), false);
+
+    assert!(bridge.get_intent(INTENT_ID.to_string()).unwrap().status == IntentStatus::Failed);
+    let record = bridge.get_solver(solver()).unwrap();
+    assert_eq!(record.failed_intents, 1);
+    assert!(record.reputation_score < 100);
+}
+
+#[test]
+fn creators_reopen_timed_out_intents_for_another_match() {
+    let mut bridge = matched(10);
+    call(creator(), 0, 11);
+    bridge.timeout_intent(INTENT_ID.to_string(), true);
+
+    let intent = bridge.get_intent(INTENT_ID.to_string()).unwrap();
+    assert!(intent.status == IntentStatus::Created);
+    assert_eq!((intent.solver, intent.execution_deadline), (None, None));
+
+    call(solver(), 0, 12);
+    bridge.match_intent(INTENT_ID.to_string(), 0, 0);
+    assert_eq!(deadline(&bridge), Some((12 + 3_600) * NANOS_PER_SEC));
+}
+
+#[test]
+#[should_panic(expected = "Only creator can reopen")]
+fn only_the_creator_reopens() {
+    let mut bridge = matched(10);
+    call(owner(), 0, 11);
+    bridge.timeout_intent(INTENT_ID.to_string(), true);
+}
+
+#[test]
+#[should_panic(expected = "Intent expired")]
+fn expired_intents_are_not_reopened() {
+    let mut bridge = matched(10);
+    call(creator(), 0, TTL_SECS);
+    bridge.timeout_intent(INTENT_ID.to_string(), true);
+}
@@ -0,0 +1,80 @@
+//! Time-weighted solver reputation and its decay; mirrors the Solana program's tests.
+
+use silence_bridge::{ReputationParams, Solver, CHAIN_ID_SOLANA};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const HOUR: u64 = 3_600;
+
+fn solver(reputation_score: u32) -> Solver {
+    Solver {
+        solver_id: "solver.near".parse().unwrap(),
+        supported_chains: vec![CHAIN_ID_SOLANA],
+        stake: 0,
+        reputation_score,
+        total_intents_executed: 0,
+        successful_intents: 0,
+        failed_intents: 0,
+        total_volume: 0,
+        is_active: true,
+        registered_at: 0,
+        last_active_at: 0,
+        reputation_updated_at: 0,
+    }
+}
+
+#[test]
+fn decay_halves_every_half_life() {
+    let params = ReputationParams::default();
+    let half_life = params.half_life_secs;
+    assert_eq!(params.decay(1000, 0), 1000);
+    assert_eq!(params.decay(1000, half_life / 2), 750);
+    assert_eq!(params.decay(1000, half_life), 500);
+    assert_eq!(params.decay(1000, 3 * half_life), 125);
+    assert_eq!(params.decay(1000, 40 * half_life), 0);
+
+    let no_decay = ReputationParams {
+        half_life_secs: 0,
+        ..ReputationParams::default()
+    };
+    assert_eq!(no_decay.decay(1000, 40 * half_life), 1000);
+}
+
+#[test]
+fn decay_applies_only_the_time_that_cost_points() {
+    let params = ReputationParams::default();
+    let half_life = params.half_life_secs;
+    // At 100 points a point drops every 2 * half_life / 100 seconds
+    let per_point = 2 * half_life / 100;
+    assert_eq!(params.decay_applied(100, HOUR), (100, 0));
+    assert_eq!(params.decay_applied(100, per_point), (99, per_point));
+    assert_eq!(params.decay_applied(100, per_point + HOUR), (99, per_point));
+    assert_eq!(params.decay_applied(100, half_life + HOUR), (50, half_life));
+    assert_eq!(params.decay_applied(1, 40 * half_life), (0, 40 * half_life));
+}
+
+#[test]
+fn short_gaps_carry_over_to_the_next_refresh() {
+    let params = ReputationParams::default();
+    let mut solver = solver(100);
+    solver.refresh_reputation(&params, HOUR * NANOS_PER_SEC);
+    assert_eq!((solver.reputation_score, solver.reputation_updated_at), (100, 0));
+
+    let per_point = 2 * params.half_life_secs / 100;
+    solver.refresh_reputation(&params, (per_point + HOUR) * NANOS_PER_SEC);
+    assert_eq!(solver.reputation_score, 99);
+    assert_eq!(solver.reputation_updated_at, per_point * NANOS_PER_SEC);
+}
+
+#[test]
+fn hourly_refreshes_keep_decaying() {
+    let params = ReputationParams::default();
+    let half_life = params.half_life_secs;
+    let mut solver = solver(100);
+    for hour in 1..=half_life / HOUR {
+        solver.refresh_reputation(&params, hour * HOUR * NANOS_PER_SEC);
+    }
+    // Each refresh restarts the interpolation from the lower score, so the
+    // result lies between one refresh (50) and continuous decay (100 / e^0.5)
+    let score = solver.current_reputation(&params, half_life * NANOS_PER_SEC);
+    assert!((50..=61).contains(&score), "score {score}");
+}
@@ -0,0 +1,93 @@
+use super::{ChainAdapter, TransferReceipt, TransferRequest};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+
+/// In-memory adapter for tests: keeps a balance and a transfer log
+pub struct MockAdapter {
+    chain_id: u32,
+    fee: u128,
+    state: Mutex<MockState>,
+}
+
+struct MockState {
+    balance: u128,
+    transfers: Vec<TransferRequest>,
+    fail_next: bool,
+}
+
+impl MockAdapter {
+    pub fn new(chain_id: u32, balance: u128) -> Self {
+        Self {
+            chain_id,
+            fee: 0,
+            state: Mutex::new(MockState {
+                balance,
+                transfers: Vec::new(),
+                fail_next: false,
+            }),
+        }
+    }
+
+    pub fn with_fee(mut self, fee: u128) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Make the next `transfer` call fail
+    pub fn fail_next_transfer(&self) {
+        self.state.lock().unwrap().fail_next = true;
+    }
+
+    /// Transfers delivered so far
+    pub fn transfers(&self) -> Vec<TransferRequest> {
+        self.state.lock().unwrap().transfers.clone()
+    }
+
+    fn receipt(request: &TransferRequest) -> TransferReceipt {
+        let tx_hash: [u8; 32] = Sha256::digest(request.intent_hash).into();
+        TransferReceipt {
+            tx_hash,
+            reference: hex::encode(tx_hash),
+        }
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for MockAdapter {
+    fn chain_id(&self) -> u32 {
+        self.chain_id
+    }
+
+    fn transfer_fee(&self) -> u128 {
+        self.fee
+    }
+
+    async fn balance(&self) -> Result<u128> {
+        Ok(self.state.lock().unwrap().balance)
+    }
+
+    async fn transfer(&self, request: &TransferRequest) -> Result<TransferReceipt> {
+        let mut state = self.state.lock().unwrap();
+        if std::mem::take(&mut state.fail_next) {
+            bail!("mock transfer failure");
+        }
+        let cost = request.amount + self.fee;
+        if state.balance < cost {
+            bail!("insufficient balance: have {}, need {}", state.balance, cost);
+        }
+        state.balance -= cost;
+        state.transfers.push(request.clone());
+        Ok(Self::receipt(request))
+    }
+
+    async fn find_transfer(&self, request: &TransferRequest) -> Result<Option<TransferReceipt>> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .transfers
+            .iter()
+            .any(|t| t.intent_hash == request.intent_hash)
+            .then(|| Self::receipt(request)))
+    }
+}
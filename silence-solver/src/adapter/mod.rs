@@ -0,0 +1,63 @@
+//! Destination-chain adapters.
+//!
+//! An adapter delivers funds to an intent's recipient on one destination
+//! chain. Transfers are tagged with the intent hash so that, after a crash
+//! between sending a transfer and recording it, the solver can find it again
+//! instead of paying twice.
+
+mod mock;
+mod near;
+mod solana;
+mod zcash;
+
+pub use mock::MockAdapter;
+pub use near::NearAdapter;
+pub use solana::SolanaAdapter;
+pub use zcash::ZcashAdapter;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Funds to deliver on the destination chain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferRequest {
+    /// Source-chain intent id
+    pub intent_id: u64,
+    /// `CrossChainIntent::hash` of the source intent
+    pub intent_hash: [u8; 32],
+    /// Plaintext recipient; its `recipient_hash` matches the intent
+    pub recipient: String,
+    /// Amount in the destination chain's base unit
+    pub amount: u128,
+    /// Blinding factor opening the intent's amount commitment
+    pub blinding: Option<[u8; 32]>,
+    /// Solver authority on the source chain, named in fulfillment receipts
+    pub source_solver: [u8; 32],
+}
+
+/// Completed destination transfer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferReceipt {
+    /// Value passed to `execute_intent` as `destination_tx_hash`
+    pub tx_hash: [u8; 32],
+    /// Chain-native transaction reference, for logs
+    pub reference: String,
+}
+
+#[async_trait]
+pub trait ChainAdapter: Send + Sync {
+    /// Chain id as registered in the program's `ChainConfig`
+    fn chain_id(&self) -> u32;
+
+    /// Estimated network fee of one transfer, in the chain's base unit
+    fn transfer_fee(&self) -> u128;
+
+    /// Spendable balance in the chain's base unit
+    async fn balance(&self) -> Result<u128>;
+
+    /// Deliver `request.amount` to `request.recipient`
+    async fn transfer(&self, request: &TransferRequest) -> Result<TransferReceipt>;
+
+    /// Look up a transfer previously sent for `request`
+    async fn find_transfer(&self, request: &TransferRequest) -> Result<Option<TransferReceipt>>;
+}
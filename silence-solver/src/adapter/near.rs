@@ -0,0 +1,213 @@
+use super::{ChainAdapter, TransferReceipt, TransferRequest};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use borsh::BorshSerialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Gas attached to `fulfill_inbound_intent` (30 TGas)
+const FULFILL_GAS: u64 = 30_000_000_000_000;
+
+/// Upper bound of the gas price in yoctoNEAR per gas unit
+const GAS_PRICE: u128 = 100_000_000;
+
+/// `Action::FunctionCall` variant index in the NEAR transaction schema
+const FUNCTION_CALL_ACTION: u8 = 2;
+
+/// Delivers inbound intents through the silence-bridge contract's
+/// `fulfill_inbound_intent`, which forwards the attached deposit to the
+/// recipient and issues a fulfillment receipt for the relayers.
+pub struct NearAdapter {
+    http: reqwest::Client,
+    rpc_url: String,
+    account_id: String,
+    bridge_contract: String,
+    key: Keypair,
+}
+
+#[derive(BorshSerialize)]
+struct FunctionCall {
+    method_name: String,
+    args: Vec<u8>,
+    gas: u64,
+    deposit: u128,
+}
+
+impl NearAdapter {
+    /// `secret_key` uses the NEAR CLI format `ed25519:<base58>`
+    pub fn new(rpc_url: String, account_id: String, bridge_contract: String, secret_key: &str) -> Result<Self> {
+        let encoded = secret_key
+            .strip_prefix("ed25519:")
+            .ok_or_else(|| anyhow!("only ed25519 NEAR keys are supported"))?;
+        let bytes = bs58::decode(encoded).into_vec().context("secret key is not base58")?;
+        let key = Keypair::try_from(bytes.as_slice()).map_err(|e| anyhow!("invalid NEAR secret key: {e}"))?;
+        Ok(Self {
+            http: reqwest::Client::new(),
+            rpc_url,
+            account_id,
+            bridge_contract,
+            key,
+        })
+    }
+
+    fn public_key(&self) -> String {
+        format!("ed25519:{}", bs58::encode(self.key.pubkey().to_bytes()).into_string())
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": "silence-solver", "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("NEAR {method} request failed"))?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            bail!("NEAR {method} failed: {error}");
+        }
+        Ok(response["result"].clone())
+    }
+
+    async fn view(&self, method: &str, args: Value) -> Result<Value> {
+        let args = base64::engine::general_purpose::STANDARD.encode(args.to_string());
+        let result = self
+            .call(
+                "query",
+                json!({
+                    "request_type": "call_function",
+                    "finality": "final",
+                    "account_id": self.bridge_contract,
+                    "method_name": method,
+                    "args_base64": args,
+                }),
+            )
+            .await?;
+        let bytes: Vec<u8> = serde_json::from_value(result["result"].clone()).context("view returned no bytes")?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Sign and submit a single function call, returning its decoded return value
+    async fn function_call(&self, call: FunctionCall) -> Result<Value> {
+        let access_key = self
+            .call(
+                "query",
+                json!({
+                    "request_type": "view_access_key",
+                    "finality": "final",
+                    "account_id": self.account_id,
+                    "public_key": self.public_key(),
+                }),
+            )
+            .await?;
+        let nonce = access_key["nonce"].as_u64().ok_or_else(|| anyhow!("access key has no nonce"))? + 1;
+        let block_hash: [u8; 32] = bs58::decode(access_key["block_hash"].as_str().unwrap_or_default())
+            .into_vec()?
+            .try_into()
+            .map_err(|_| anyhow!("invalid block hash"))?;
+
+        // Transaction { signer_id, public_key, nonce, receiver_id, block_hash, actions }
+        let mut tx = Vec::new();
+        self.account_id.serialize(&mut tx)?;
+        0u8.serialize(&mut tx)?;
+        self.key.pubkey().to_bytes().serialize(&mut tx)?;
+        nonce.serialize(&mut tx)?;
+        self.bridge_contract.serialize(&mut tx)?;
+        block_hash.serialize(&mut tx)?;
+        1u32.serialize(&mut tx)?;
+        FUNCTION_CALL_ACTION.serialize(&mut tx)?;
+        call.serialize(&mut tx)?;
+
+        let signature = self.key.sign_message(&Sha256::digest(&tx));
+        let mut signed = tx;
+        0u8.serialize(&mut signed)?;
+        signed.extend_from_slice(signature.as_ref());
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(signed);
+        let outcome = self.call("broadcast_tx_commit", json!([encoded])).await?;
+        let status = &outcome["status"];
+        if let Some(failure) = status.get("Failure") {
+            bail!("NEAR transaction failed: {failure}");
+        }
+        let value = status["SuccessValue"]
+            .as_str()
+            .ok_or_else(|| anyhow!("NEAR transaction has no return value"))?;
+        let bytes = base64::engine::general_purpose::STANDARD.decode(value)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+fn receipt_from_hex(receipt_hash: &str) -> Result<TransferReceipt> {
+    let tx_hash: [u8; 32] = hex::decode(receipt_hash)?
+        .try_into()
+        .map_err(|_| anyhow!("receipt hash must be 32 bytes"))?;
+    Ok(TransferReceipt {
+        tx_hash,
+        reference: receipt_hash.to_string(),
+    })
+}
+
+#[async_trait]
+impl ChainAdapter for NearAdapter {
+    fn chain_id(&self) -> u32 {
+        silence_intent::CHAIN_ID_NEAR
+    }
+
+    fn transfer_fee(&self) -> u128 {
+        FULFILL_GAS as u128 * GAS_PRICE
+    }
+
+    async fn balance(&self) -> Result<u128> {
+        let account = self
+            .call(
+                "query",
+                json!({ "request_type": "view_account", "finality": "final", "account_id": self.account_id }),
+            )
+            .await?;
+        account["amount"]
+            .as_str()
+            .and_then(|amount| amount.parse().ok())
+            .ok_or_else(|| anyhow!("view_account returned no balance"))
+    }
+
+    async fn transfer(&self, request: &TransferRequest) -> Result<TransferReceipt> {
+        let args = json!({
+            "intent_hash": hex::encode(request.intent_hash),
+            "recipient": request.recipient,
+            "source_solver": hex::encode(request.source_solver),
+            "blinding": request.blinding.map(hex::encode),
+        });
+        let result = self
+            .function_call(FunctionCall {
+                method_name: "fulfill_inbound_intent".to_string(),
+                args: args.to_string().into_bytes(),
+                gas: FULFILL_GAS,
+                deposit: request.amount,
+            })
+            .await?;
+        let receipt_hash = result.as_str().ok_or_else(|| anyhow!("fulfill returned no receipt hash"))?;
+        receipt_from_hex(receipt_hash)
+    }
+
+    async fn find_transfer(&self, request: &TransferRequest) -> Result<Option<TransferReceipt>> {
+        let inbound = self
+            .view("get_inbound_intent", json!({ "intent_hash": hex::encode(request.intent_hash) }))
+            .await?;
+        if inbound["status"] != "Fulfilled" {
+            return Ok(None);
+        }
+        let Some(receipt_hash) = inbound["receipt_hash"].as_str() else {
+            return Ok(None);
+        };
+        // Another solver may have delivered it
+        let receipt = self.view("get_receipt", json!({ "receipt_hash": receipt_hash })).await?;
+        if receipt["solver_id"] != self.account_id.as_str() {
+            return Ok(None);
+        }
+        receipt_from_hex(receipt_hash).map(Some)
+    }
+}
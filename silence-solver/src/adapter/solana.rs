@@ -0,0 +1,95 @@
+use super::{ChainAdapter, TransferReceipt, TransferRequest};
+use crate::rpc::RpcClient;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_program;
+use std::str::FromStr;
+
+/// SPL memo program v2
+const MEMO_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Recent recipient signatures scanned by `find_transfer`
+const LOOKUP_LIMIT: usize = 100;
+
+/// `SystemInstruction::Transfer` variant index
+const SYSTEM_TRANSFER: u32 = 2;
+
+/// Base fee of a single-signature transaction in lamports
+const SIGNATURE_FEE: u128 = 5_000;
+
+/// Native SOL transfers, tagged with a memo carrying the hex intent hash
+pub struct SolanaAdapter {
+    rpc: RpcClient,
+    payer: Keypair,
+}
+
+impl SolanaAdapter {
+    pub fn new(rpc: RpcClient, payer: Keypair) -> Self {
+        Self { rpc, payer }
+    }
+
+    fn receipt(signature: &Signature) -> TransferReceipt {
+        TransferReceipt {
+            tx_hash: Sha256::digest(signature.as_ref()).into(),
+            reference: signature.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for SolanaAdapter {
+    fn chain_id(&self) -> u32 {
+        silence_intent::CHAIN_ID_SOLANA
+    }
+
+    fn transfer_fee(&self) -> u128 {
+        SIGNATURE_FEE
+    }
+
+    async fn balance(&self) -> Result<u128> {
+        Ok(self.rpc.balance(&self.payer.pubkey()).await? as u128)
+    }
+
+    async fn transfer(&self, request: &TransferRequest) -> Result<TransferReceipt> {
+        let recipient = Pubkey::from_str(&request.recipient).context("recipient is not a Solana address")?;
+        let lamports = u64::try_from(request.amount).context("amount exceeds u64")?;
+        let memo = Instruction {
+            program_id: MEMO_PROGRAM_ID,
+            accounts: vec![],
+            data: hex::encode(request.intent_hash).into_bytes(),
+        };
+        let mut data = SYSTEM_TRANSFER.to_le_bytes().to_vec();
+        data.extend_from_slice(&lamports.to_le_bytes());
+        let transfer = Instruction {
+            program_id: system_program::ID,
+            accounts: vec![
+                AccountMeta::new(self.payer.pubkey(), true),
+                AccountMeta::new(recipient, false),
+            ],
+            data,
+        };
+        let signature = self.rpc.send_and_confirm(&[memo, transfer], &self.payer).await?;
+        Ok(Self::receipt(&signature))
+    }
+
+    async fn find_transfer(&self, request: &TransferRequest) -> Result<Option<TransferReceipt>> {
+        let recipient = Pubkey::from_str(&request.recipient).context("recipient is not a Solana address")?;
+        let tag = hex::encode(request.intent_hash);
+        let signatures = self.rpc.signatures_for_address(&recipient, None, None, LOOKUP_LIMIT).await?;
+        let found = signatures
+            .into_iter()
+            .filter(|info| info.err.is_none())
+            .find(|info| info.memo.as_deref().is_some_and(|memo| memo.contains(&tag)));
+        match found {
+            Some(info) => {
+                let signature = Signature::from_str(&info.signature).context("invalid signature")?;
+                Ok(Some(Self::receipt(&signature)))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use super::{ChainAdapter, TransferReceipt, TransferRequest};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// ZIP-317 conventional fee for a small shielded transaction in zatoshis
+const CONVENTIONAL_FEE: u128 = 10_000;
+const ZATOSHIS_PER_ZEC: u128 = 100_000_000;
+
+const OPERATION_POLL_ATTEMPTS: u32 = 120;
+const OPERATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Shielded transfers through a zcashd wallet (`z_sendmany`). The memo
+/// field carries the hex intent hash.
+pub struct ZcashAdapter {
+    http: reqwest::Client,
+    rpc_url: String,
+    rpc_user: String,
+    rpc_password: String,
+    from_address: String,
+}
+
+impl ZcashAdapter {
+    pub fn new(rpc_url: String, rpc_user: String, rpc_password: String, from_address: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            rpc_url,
+            rpc_user,
+            rpc_password,
+            from_address,
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "1.0", "id": "silence-solver", "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(&self.rpc_url)
+            .basic_auth(&self.rpc_user, Some(&self.rpc_password))
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("zcashd {method} request failed"))?
+            .json()
+            .await?;
+        if !response["error"].is_null() {
+            bail!("zcashd {method} failed: {}", response["error"]);
+        }
+        Ok(response["result"].clone())
+    }
+
+    /// Successful operations whose memo tags `intent_hash`
+    async fn operations_for(&self, intent_hash: &[u8; 32]) -> Result<Vec<Value>> {
+        let memo = hex::encode(hex::encode(intent_hash));
+        let operations = self.call("z_getoperationstatus", json!([])).await?;
+        Ok(operations
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|op| {
+                op["params"]["amounts"]
+                    .as_array()
+                    .is_some_and(|amounts| amounts.iter().any(|a| a["memo"] == memo.as_str()))
+            })
+            .cloned()
+            .collect())
+    }
+}
+
+fn receipt_from_txid(txid: &str) -> Result<TransferReceipt> {
+    let mut tx_hash: [u8; 32] = hex::decode(txid)?
+        .try_into()
+        .map_err(|_| anyhow!("txid must be 32 bytes"))?;
+    // zcashd displays txids byte-reversed
+    tx_hash.reverse();
+    Ok(TransferReceipt {
+        tx_hash,
+        reference: txid.to_string(),
+    })
+}
+
+/// Format zatoshis as a decimal ZEC amount
+fn format_zec(zatoshis: u128) -> String {
+    format!("{}.{:08}", zatoshis / ZATOSHIS_PER_ZEC, zatoshis % ZATOSHIS_PER_ZEC)
+}
+
+#[async_trait]
+impl ChainAdapter for ZcashAdapter {
+    fn chain_id(&self) -> u32 {
+        silence_intent::CHAIN_ID_ZCASH
+    }
+
+    fn transfer_fee(&self) -> u128 {
+        CONVENTIONAL_FEE
+    }
+
+    async fn balance(&self) -> Result<u128> {
+        let balance = self.call("z_getbalance", json!([self.from_address])).await?;
+        let zec = balance.as_f64().ok_or_else(|| anyhow!("z_getbalance returned no balance"))?;
+        Ok((zec * ZATOSHIS_PER_ZEC as f64).round() as u128)
+    }
+
+    async fn transfer(&self, request: &TransferRequest) -> Result<TransferReceipt> {
+        let amounts = json!([{
+            "address": request.recipient,
+            "amount": format_zec(request.amount),
+            "memo": hex::encode(hex::encode(request.intent_hash)),
+        }]);
+        let opid = self
+            .call("z_sendmany", json!([self.from_address, amounts, 1, null, "AllowRevealedAmounts"]))
+            .await?;
+        let opid = opid.as_str().ok_or_else(|| anyhow!("z_sendmany returned no operation id"))?;
+
+        for _ in 0..OPERATION_POLL_ATTEMPTS {
+            let status = self.call("z_getoperationstatus", json!([[opid]])).await?;
+            let operation = &status[0];
+            match operation["status"].as_str() {
+                Some("success") => {
+                    let txid = operation["result"]["txid"]
+                        .as_str()
+                        .ok_or_else(|| anyhow!("operation {opid} has no txid"))?;
+                    return receipt_from_txid(txid);
+                }
+                Some("failed") | Some("cancelled") => {
+                    bail!("zcash operation {opid} failed: {}", operation["error"]);
+                }
+                _ => tokio::time::sleep(OPERATION_POLL_INTERVAL).await,
+            }
+        }
+        bail!("zcash operation {opid} did not complete in time")
+    }
+
+    async fn find_transfer(&self, request: &TransferRequest) -> Result<Option<TransferReceipt>> {
+        let operations = self.operations_for(&request.intent_hash).await?;
+        let txid = operations
+            .iter()
+            .find(|op| op["status"] == "success")
+            .and_then(|op| op["result"]["txid"].as_str());
+        if let Some(txid) = txid {
+            return receipt_from_txid(txid).map(Some);
+        }
+        if operations.iter().any(|op| op["status"] == "queued" || op["status"] == "executing") {
+            bail!("zcash transfer for intent {} is still in flight", request.intent_id);
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,202 @@
+//! Source-chain side of the solver: the `project_silence` program.
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use project_silence_client::accounts::{self, BridgeConfig, ChainConfig, Intent, IntentFill, ReceiptAttestation};
+use project_silence_client::events::{parse_logs, IntentCreated, ProgramEvent};
+use project_silence_client::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::rpc::{RpcClient, SignatureInfo};
+
+/// Signatures fetched per `getSignaturesForAddress` page
+const SIGNATURE_PAGE: usize = 1000;
+
+/// `ReceiptAttestation` field offsets: discriminator, then `receipt_hash`,
+/// `intent_hash` and `destination_chain_id`
+const RECEIPT_INTENT_HASH_OFFSET: usize = 8 + 32;
+const RECEIPT_SOLVER_OFFSET: usize = RECEIPT_INTENT_HASH_OFFSET + 32 + 4;
+
+/// New `IntentCreated` events and the cursor to resume from
+pub struct EventBatch {
+    pub events: Vec<IntentCreated>,
+    pub cursor: Option<String>,
+}
+
+#[async_trait]
+pub trait Bridge: Send + Sync {
+    /// Solver authority signing every transaction
+    fn solver_authority(&self) -> Pubkey;
+
+    /// `IntentCreated` events emitted after `cursor`, oldest first
+    async fn intent_events(&self, cursor: Option<&str>) -> Result<EventBatch>;
+
+    async fn bridge_config(&self) -> Result<BridgeConfig>;
+    async fn chain_config(&self, chain_id: u32) -> Result<Option<ChainConfig>>;
+    async fn intent(&self, intent_id: u64) -> Result<Option<Intent>>;
+    /// This solver's fill on `intent_id`
+    async fn fill(&self, intent_id: u64) -> Result<Option<IntentFill>>;
+    /// Attested, unconsumed receipt for `intent_hash` naming this solver
+    async fn receipt(&self, intent_hash: &[u8; 32]) -> Result<Option<ReceiptAttestation>>;
+
+    async fn heartbeat(&self) -> Result<()>;
+    async fn match_intent(
+        &self,
+        intent_id: u64,
+        destination_chain_id: u32,
+        fill_amount: u64,
+        estimated_time_secs: Option<i64>,
+    ) -> Result<()>;
+    /// `receipt_hash` names the attested receipt to consume
+    async fn execute_intent(
+        &self,
+        intent_id: u64,
+        destination_chain_id: u32,
+        destination_tx_hash: [u8; 32],
+        receipt_hash: Option<&[u8; 32]>,
+    ) -> Result<()>;
+    async fn settle_intent(&self, intent_id: u64, destination_chain_id: u32) -> Result<()>;
+}
+
+pub struct SolanaBridge {
+    rpc: RpcClient,
+    authority: Keypair,
+}
+
+impl SolanaBridge {
+    pub fn new(rpc: RpcClient, authority: Keypair) -> Self {
+        Self { rpc, authority }
+    }
+
+    async fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        let Some(data) = self.rpc.account_data(address).await? else {
+            return Ok(None);
+        };
+        accounts::decode(&data)
+            .map(Some)
+            .map_err(|e| anyhow!("{address} is not a valid {}: {e}", std::any::type_name::<T>()))
+    }
+}
+
+#[async_trait]
+impl Bridge for SolanaBridge {
+    fn solver_authority(&self) -> Pubkey {
+        self.authority.pubkey()
+    }
+
+    async fn intent_events(&self, cursor: Option<&str>) -> Result<EventBatch> {
+        let mut signatures = Vec::new();
+        loop {
+            let before = signatures.last().map(|s: &SignatureInfo| s.signature.clone());
+            let page = self
+                .rpc
+                .signatures_for_address(&project_silence_client::ID, before.as_deref(), cursor, SIGNATURE_PAGE)
+                .await?;
+            let done = page.len() < SIGNATURE_PAGE;
+            signatures.extend(page);
+            if done {
+                break;
+            }
+        }
+        let next_cursor = signatures.first().map(|s| s.signature.clone());
+
+        let mut events = Vec::new();
+        for info in signatures.iter().rev().filter(|s| s.err.is_none()) {
+            let logs = self.rpc.transaction_logs(&info.signature).await?;
+            events.extend(parse_logs(&logs).into_iter().filter_map(|event| match event {
+                ProgramEvent::IntentCreated(created) => Some(created),
+                _ => None,
+            }));
+        }
+        Ok(EventBatch {
+            events,
+            cursor: next_cursor.or_else(|| cursor.map(String::from)),
+        })
+    }
+
+    async fn bridge_config(&self) -> Result<BridgeConfig> {
+        self.account(&pda::bridge_config())
+            .await?
+            .ok_or_else(|| anyhow!("bridge is not initialized"))
+    }
+
+    async fn chain_config(&self, chain_id: u32) -> Result<Option<ChainConfig>> {
+        self.account(&pda::chain_config(chain_id)).await
+    }
+
+    async fn intent(&self, intent_id: u64) -> Result<Option<Intent>> {
+        self.account(&pda::intent(intent_id)).await
+    }
+
+    async fn fill(&self, intent_id: u64) -> Result<Option<IntentFill>> {
+        let fill = pda::fill(&pda::intent(intent_id), &self.authority.pubkey());
+        self.account(&fill).await
+    }
+
+    async fn receipt(&self, intent_hash: &[u8; 32]) -> Result<Option<ReceiptAttestation>> {
+        // Receipts are keyed by their own hash, so look them up by content
+        let solver = self.authority.pubkey();
+        let filters: [(usize, &[u8]); 3] = [
+            (0, ReceiptAttestation::DISCRIMINATOR),
+            (RECEIPT_INTENT_HASH_OFFSET, intent_hash),
+            (RECEIPT_SOLVER_OFFSET, solver.as_ref()),
+        ];
+        let receipts = self.rpc.program_accounts(&project_silence_client::ID, &filters).await?;
+        Ok(receipts
+            .iter()
+            .filter_map(|(_, data)| accounts::decode::<ReceiptAttestation>(data).ok())
+            .find(|receipt| receipt.attested && !receipt.consumed))
+    }
+
+    async fn heartbeat(&self) -> Result<()> {
+        let ix = instructions::solver_heartbeat(&self.authority.pubkey());
+        self.rpc.send_and_confirm(&[ix], &self.authority).await?;
+        Ok(())
+    }
+
+    async fn match_intent(
+        &self,
+        intent_id: u64,
+        destination_chain_id: u32,
+        fill_amount: u64,
+        estimated_time_secs: Option<i64>,
+    ) -> Result<()> {
+        let ix = instructions::match_intent(
+            &self.authority.pubkey(),
+            intent_id,
+            destination_chain_id,
+            fill_amount,
+            estimated_time_secs,
+        );
+        self.rpc.send_and_confirm(&[ix], &self.authority).await?;
+        Ok(())
+    }
+
+    async fn execute_intent(
+        &self,
+        intent_id: u64,
+        destination_chain_id: u32,
+        destination_tx_hash: [u8; 32],
+        receipt_hash: Option<&[u8; 32]>,
+    ) -> Result<()> {
+        let ix = instructions::execute_intent(
+            &self.authority.pubkey(),
+            intent_id,
+            destination_chain_id,
+            receipt_hash,
+            destination_tx_hash,
+            None,
+        );
+        self.rpc.send_and_confirm(&[ix], &self.authority).await?;
+        Ok(())
+    }
+
+    async fn settle_intent(&self, intent_id: u64, destination_chain_id: u32) -> Result<()> {
+        let authority = self.authority.pubkey();
+        let ix = instructions::settle_intent(&authority, intent_id, destination_chain_id, &authority);
+        self.rpc.send_and_confirm(&[ix], &self.authority).await?;
+        Ok(())
+    }
+}
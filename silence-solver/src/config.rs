@@ -0,0 +1,158 @@
+//! TOML configuration.
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::adapter::{ChainAdapter, NearAdapter, SolanaAdapter, ZcashAdapter};
+use crate::engine::DEFAULT_HEARTBEAT_INTERVAL_SECS;
+use crate::rpc::RpcClient;
+use crate::strategy::{Price, Strategy};
+
+#[derive(Deserialize, Debug)]
+pub struct Config {
+    /// SQLite database holding jobs and the event cursor
+    #[serde(default = "default_db_path")]
+    pub db_path: PathBuf,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_heartbeat_interval")]
+    pub heartbeat_interval_secs: i64,
+    pub solana: SolanaConfig,
+    pub orders: OrdersConfig,
+    pub strategy: Strategy,
+    #[serde(default)]
+    pub chains: Vec<ChainConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SolanaConfig {
+    pub rpc_url: String,
+    #[serde(default = "default_commitment")]
+    pub commitment: String,
+    /// Solver authority keypair (Solana CLI JSON format)
+    pub keypair_path: PathBuf,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OrdersConfig {
+    /// Order relay serving `GET /intents/{id}`
+    pub url: String,
+}
+
+/// Destination chain adapter; secrets are read from environment variables
+#[derive(Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ChainConfig {
+    Solana {
+        rpc_url: String,
+        keypair_path: PathBuf,
+        price: Price,
+    },
+    Near {
+        rpc_url: String,
+        account_id: String,
+        bridge_contract: String,
+        secret_key_env: String,
+        price: Price,
+    },
+    Zcash {
+        rpc_url: String,
+        rpc_user: String,
+        rpc_password_env: String,
+        from_address: String,
+        price: Price,
+    },
+}
+
+fn default_db_path() -> PathBuf {
+    PathBuf::from("silence-solver.db")
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+fn default_heartbeat_interval() -> i64 {
+    DEFAULT_HEARTBEAT_INTERVAL_SECS
+}
+
+fn default_commitment() -> String {
+    "confirmed".to_string()
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+        let mut config: Config = toml::from_str(&text).with_context(|| format!("parse {}", path.display()))?;
+        for chain in &config.chains {
+            config.strategy.prices.insert(chain.chain_id(), chain.price());
+        }
+        Ok(config)
+    }
+}
+
+impl ChainConfig {
+    pub fn chain_id(&self) -> u32 {
+        match self {
+            ChainConfig::Solana { .. } => silence_intent::CHAIN_ID_SOLANA,
+            ChainConfig::Near { .. } => silence_intent::CHAIN_ID_NEAR,
+            ChainConfig::Zcash { .. } => silence_intent::CHAIN_ID_ZCASH,
+        }
+    }
+
+    pub fn price(&self) -> Price {
+        match self {
+            ChainConfig::Solana { price, .. } | ChainConfig::Near { price, .. } | ChainConfig::Zcash { price, .. } => {
+                *price
+            }
+        }
+    }
+
+    pub fn build(&self, commitment: &str) -> Result<Arc<dyn ChainAdapter>> {
+        Ok(match self {
+            ChainConfig::Solana { rpc_url, keypair_path, .. } => Arc::new(SolanaAdapter::new(
+                RpcClient::new(rpc_url, commitment),
+                read_keypair(keypair_path)?,
+            )),
+            ChainConfig::Near {
+                rpc_url,
+                account_id,
+                bridge_contract,
+                secret_key_env,
+                ..
+            } => Arc::new(NearAdapter::new(
+                rpc_url.clone(),
+                account_id.clone(),
+                bridge_contract.clone(),
+                &env_secret(secret_key_env)?,
+            )?),
+            ChainConfig::Zcash {
+                rpc_url,
+                rpc_user,
+                rpc_password_env,
+                from_address,
+                ..
+            } => Arc::new(ZcashAdapter::new(
+                rpc_url.clone(),
+                rpc_user.clone(),
+                env_secret(rpc_password_env)?,
+                from_address.clone(),
+            )),
+        })
+    }
+}
+
+/// Read a Solana CLI keypair file, expanding a leading `~/`
+pub fn read_keypair(path: &Path) -> Result<solana_sdk::signature::Keypair> {
+    let path = match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    };
+    solana_sdk::signature::read_keypair_file(&path).map_err(|e| anyhow!("read keypair {}: {e}", path.display()))
+}
+
+fn env_secret(name: &str) -> Result<String> {
+    std::env::var(name).with_context(|| format!("environment variable {name} is not set"))
+}
@@ -0,0 +1,354 @@
+//! Solver state machine.
+//!
+//! Each tick sends a heartbeat when due, turns new `IntentCreated` events
+//! into jobs, then drives every pending job as far as it can go:
+//!
+//! `Matching -> Matched -> Transferring -> Transferred -> Executed -> Settled`
+//!
+//! A state is persisted before the step it guards runs, and every step first
+//! checks whether its effect already happened, so a crash at any point is
+//! recovered by re-running the step.
+
+use anyhow::{anyhow, Result};
+use project_silence_client::accounts::{FillStatus, IntentStatus};
+use project_silence_client::events::IntentCreated;
+use silence_intent::CrossChainIntent;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::adapter::{ChainAdapter, TransferRequest};
+use crate::bridge::Bridge;
+use crate::orders::OrderSource;
+use crate::store::{Job, JobState, Store};
+use crate::strategy::{Decision, Strategy};
+
+/// Delay before retrying a settlement deferred by a volume cap
+const SETTLE_RETRY_SECS: i64 = 60;
+
+/// Default interval between solver heartbeats
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECS: i64 = 600;
+
+pub struct Solver {
+    bridge: Arc<dyn Bridge>,
+    orders: Arc<dyn OrderSource>,
+    adapters: HashMap<u32, Arc<dyn ChainAdapter>>,
+    strategy: Strategy,
+    store: Store,
+    heartbeat_interval_secs: i64,
+}
+
+impl Solver {
+    pub fn new(bridge: Arc<dyn Bridge>, orders: Arc<dyn OrderSource>, strategy: Strategy, store: Store) -> Self {
+        Self {
+            bridge,
+            orders,
+            adapters: HashMap::new(),
+            strategy,
+            store,
+            heartbeat_interval_secs: DEFAULT_HEARTBEAT_INTERVAL_SECS,
+        }
+    }
+
+    pub fn with_adapter(mut self, adapter: Arc<dyn ChainAdapter>) -> Self {
+        self.adapters.insert(adapter.chain_id(), adapter);
+        self
+    }
+
+    /// Seconds between heartbeats (0 disables them)
+    pub fn with_heartbeat_interval(mut self, secs: i64) -> Self {
+        self.heartbeat_interval_secs = secs;
+        self
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Tick every `poll_interval` until Ctrl-C
+    pub async fn run(&self, poll_interval: Duration) -> Result<()> {
+        let mut ticker = tokio::time::interval(poll_interval);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if let Err(err) = self.tick(unix_now()).await {
+                        warn!("tick failed: {err:#}");
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    info!("shutting down");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// One pass over heartbeat, discovery and pending jobs
+    pub async fn tick(&self, now: i64) -> Result<()> {
+        if let Err(err) = self.heartbeat(now).await {
+            warn!("heartbeat failed: {err:#}");
+        }
+        self.discover(now).await?;
+        for job in self.store.pending_jobs()? {
+            let intent_id = job.intent_id;
+            if let Err(err) = self.advance(job, now).await {
+                warn!(intent_id, "job stalled: {err:#}");
+            }
+        }
+        Ok(())
+    }
+
+    async fn heartbeat(&self, now: i64) -> Result<()> {
+        if self.heartbeat_interval_secs <= 0 || now < self.store.last_heartbeat()? + self.heartbeat_interval_secs {
+            return Ok(());
+        }
+        self.bridge.heartbeat().await?;
+        self.store.set_last_heartbeat(now)?;
+        debug!("heartbeat sent");
+        Ok(())
+    }
+
+    async fn discover(&self, now: i64) -> Result<()> {
+        let cursor = self.store.cursor()?;
+        let batch = self.bridge.intent_events(cursor.as_deref()).await?;
+        for event in &batch.events {
+            if !self.adapters.contains_key(&event.destination_chain_id) || self.store.job(event.intent_id)?.is_some() {
+                continue;
+            }
+            let job = self.evaluate(event).await?;
+            match &job.state {
+                JobState::Skipped => info!(
+                    intent_id = job.intent_id,
+                    "skipped: {}",
+                    job.reason.as_deref().unwrap_or_default()
+                ),
+                _ => info!(intent_id = job.intent_id, fill_amount = job.fill_amount, "taking intent"),
+            }
+            self.store.save(&job, now)?;
+        }
+        if let Some(cursor) = batch.cursor {
+            self.store.set_cursor(&cursor)?;
+        }
+        Ok(())
+    }
+
+    /// Decide whether to take a new intent
+    async fn evaluate(&self, event: &IntentCreated) -> Result<Job> {
+        let skip = |reason: &str| Job::skipped(event.intent_id, event.destination_chain_id, reason.to_string());
+
+        let Some(intent) = self.bridge.intent(event.intent_id).await? else {
+            return Ok(skip("intent not found"));
+        };
+        if intent.status != IntentStatus::Created {
+            return Ok(skip("intent no longer open"));
+        }
+        let Some(chain) = self.bridge.chain_config(intent.destination_chain_id).await? else {
+            return Ok(skip("destination chain not configured"));
+        };
+        if !chain.enabled {
+            return Ok(skip("destination chain disabled"));
+        }
+        let Some(details) = self.orders.details(intent.intent_id).await? else {
+            return Ok(skip("no order details published"));
+        };
+        if !details.matches(&intent) {
+            return Ok(skip("order details do not open the intent"));
+        }
+
+        let adapter = self.adapter(intent.destination_chain_id)?;
+        let protocol_fee_bps = self.bridge.bridge_config().await?.protocol_fee_bps;
+        let quote = match self
+            .strategy
+            .evaluate(&intent, &chain, protocol_fee_bps, &details, adapter.transfer_fee())
+        {
+            Decision::Fill(quote) => quote,
+            Decision::Skip(reason) => return Ok(skip(&reason)),
+        };
+        if adapter.balance().await? < quote.destination_amount + adapter.transfer_fee() {
+            return Ok(skip("insufficient destination balance"));
+        }
+
+        Ok(Job {
+            intent_id: intent.intent_id,
+            destination_chain_id: intent.destination_chain_id,
+            intent_hash: CrossChainIntent::from(&intent).hash(),
+            state: JobState::Matching,
+            fill_amount: quote.fill_amount,
+            destination_amount: quote.destination_amount,
+            recipient: details.recipient,
+            blinding: details.blinding,
+            destination_tx_hash: None,
+            settle_after: 0,
+            reason: None,
+            updated_at: 0,
+        })
+    }
+
+    /// Run steps until the job waits or finishes
+    async fn advance(&self, mut job: Job, now: i64) -> Result<()> {
+        while let Some(next) = self.step(&job, now).await? {
+            let progressed = next.state != job.state;
+            self.store.save(&next, now)?;
+            if progressed {
+                debug!(intent_id = job.intent_id, "{} -> {}", job.state.as_str(), next.state.as_str());
+            }
+            job = next;
+            if !progressed || job.state.is_terminal() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Perform the step guarded by `job.state`; `None` means nothing to do yet
+    async fn step(&self, job: &Job, now: i64) -> Result<Option<Job>> {
+        let id = job.intent_id;
+        let chain_id = job.destination_chain_id;
+        match job.state {
+            JobState::Matching => {
+                if self.bridge.fill(id).await?.is_none() {
+                    let open = self
+                        .bridge
+                        .intent(id)
+                        .await?
+                        .is_some_and(|intent| intent.status == IntentStatus::Created && now < intent.expires_at);
+                    if !open {
+                        return Ok(Some(job.fail("intent closed before match")));
+                    }
+                    self.bridge
+                        .match_intent(id, chain_id, job.fill_amount, self.strategy.estimated_time_secs)
+                        .await?;
+                    info!(intent_id = id, "matched");
+                }
+                Ok(Some(job.with_state(JobState::Matched)))
+            }
+            JobState::Matched => {
+                if let Some(reason) = self.check_fill_open(id, now).await? {
+                    return Ok(Some(job.fail(reason)));
+                }
+                Ok(Some(job.with_state(JobState::Transferring)))
+            }
+            JobState::Transferring => {
+                let adapter = self.adapter(chain_id)?;
+                let request = self.transfer_request(job);
+                let receipt = match adapter.find_transfer(&request).await? {
+                    Some(receipt) => receipt,
+                    None => {
+                        if let Some(reason) = self.check_fill_open(id, now).await? {
+                            return Ok(Some(job.fail(reason)));
+                        }
+                        adapter.transfer(&request).await?
+                    }
+                };
+                info!(intent_id = id, reference = %receipt.reference, "destination transfer sent");
+                let mut next = job.with_state(JobState::Transferred);
+                next.destination_tx_hash = Some(receipt.tx_hash);
+                Ok(Some(next))
+            }
+            JobState::Transferred => {
+                let chain = self
+                    .bridge
+                    .chain_config(chain_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("chain {chain_id} not configured"))?;
+                let Some(fill) = self.bridge.fill(id).await? else {
+                    return Ok(Some(job.fail("fill closed before execution")));
+                };
+                if fill.status == FillStatus::Matched {
+                    let receipt_hash = if chain.requires_receipt {
+                        match self.bridge.receipt(&job.intent_hash).await? {
+                            Some(receipt) => Some(receipt.receipt_hash),
+                            None => {
+                                debug!(intent_id = id, "waiting for receipt attestation");
+                                return Ok(None);
+                            }
+                        }
+                    } else {
+                        None
+                    };
+                    let tx_hash = job.destination_tx_hash.ok_or_else(|| anyhow!("missing destination tx hash"))?;
+                    self.bridge
+                        .execute_intent(id, chain_id, tx_hash, receipt_hash.as_ref())
+                        .await?;
+                    info!(intent_id = id, "executed");
+                }
+                let mut next = job.with_state(JobState::Executed);
+                next.settle_after = fill.executed_at.unwrap_or(now) + chain.finality_seconds as i64;
+                Ok(Some(next))
+            }
+            JobState::Executed => {
+                if now < job.settle_after {
+                    return Ok(None);
+                }
+                if self.bridge.fill(id).await?.is_none() {
+                    let mut next = job.with_state(JobState::Settled);
+                    next.reason = Some("fill closed externally".to_string());
+                    return Ok(Some(next));
+                }
+                self.bridge.settle_intent(id, chain_id).await?;
+                if self.bridge.fill(id).await?.is_some() {
+                    // Deferred by a volume cap; the fill stays open
+                    let mut next = job.clone();
+                    next.settle_after = now + SETTLE_RETRY_SECS;
+                    info!(intent_id = id, "settlement deferred by volume cap");
+                    return Ok(Some(next));
+                }
+                info!(intent_id = id, "settled");
+                Ok(Some(job.with_state(JobState::Settled)))
+            }
+            JobState::Settled | JobState::Skipped | JobState::Failed => Ok(None),
+        }
+    }
+
+    /// Reason the fill can no longer be executed, if any
+    async fn check_fill_open(&self, intent_id: u64, now: i64) -> Result<Option<&'static str>> {
+        Ok(match self.bridge.fill(intent_id).await? {
+            None => Some("fill closed before transfer"),
+            Some(fill) if now > fill.execution_deadline => Some("execution deadline passed"),
+            Some(_) => None,
+        })
+    }
+
+    fn adapter(&self, chain_id: u32) -> Result<&Arc<dyn ChainAdapter>> {
+        self.adapters
+            .get(&chain_id)
+            .ok_or_else(|| anyhow!("no adapter for chain {chain_id}"))
+    }
+
+    fn transfer_request(&self, job: &Job) -> TransferRequest {
+        TransferRequest {
+            intent_id: job.intent_id,
+            intent_hash: job.intent_hash,
+            recipient: job.recipient.clone(),
+            amount: job.destination_amount,
+            blinding: job.blinding,
+            source_solver: self.bridge.solver_authority().to_bytes(),
+        }
+    }
+}
+
+impl Job {
+    fn with_state(&self, state: JobState) -> Job {
+        Job {
+            state,
+            ..self.clone()
+        }
+    }
+
+    fn fail(&self, reason: &str) -> Job {
+        warn!(intent_id = self.intent_id, "failed: {reason}");
+        Job {
+            state: JobState::Failed,
+            reason: Some(reason.to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
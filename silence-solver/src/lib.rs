@@ -0,0 +1,18 @@
+//! Off-chain solver for Project Silence intents.
+//!
+//! The solver watches the `project_silence` program for `IntentCreated`
+//! events, quotes each intent against its destination-chain costs, and for
+//! profitable ones runs `match_intent`, the destination transfer (through a
+//! [`adapter::ChainAdapter`]), `execute_intent` and `settle_intent`. Progress
+//! is kept in SQLite so a restarted solver resumes where it stopped.
+
+pub mod adapter;
+pub mod bridge;
+pub mod config;
+pub mod engine;
+pub mod orders;
+pub mod rpc;
+pub mod store;
+pub mod strategy;
+
+pub use engine::Solver;
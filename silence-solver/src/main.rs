@@ -0,0 +1,76 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
+
+use silence_solver::bridge::SolanaBridge;
+use silence_solver::config::{read_keypair, Config};
+use silence_solver::orders::HttpOrderSource;
+use silence_solver::rpc::RpcClient;
+use silence_solver::store::Store;
+use silence_solver::Solver;
+
+#[derive(Parser)]
+#[command(name = "silence-solver", about = "Project Silence cross-chain intent solver")]
+struct Cli {
+    /// Configuration file
+    #[arg(short, long, default_value = "solver.toml")]
+    config: PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solver loop (default)
+    Run,
+    /// Run a single tick and exit
+    Once,
+    /// Print the persisted jobs
+    Jobs,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let cli = Cli::parse();
+    let config = Config::load(&cli.config)?;
+    let store = Store::open(&config.db_path)?;
+
+    if let Some(Command::Jobs) = cli.command {
+        for job in store.jobs()? {
+            println!(
+                "{:>8}  chain {}  {:<12}  fill {:>14}  {}",
+                job.intent_id,
+                job.destination_chain_id,
+                job.state.as_str(),
+                job.fill_amount,
+                job.reason.unwrap_or_default()
+            );
+        }
+        return Ok(());
+    }
+
+    let bridge = SolanaBridge::new(
+        RpcClient::new(&config.solana.rpc_url, &config.solana.commitment),
+        read_keypair(&config.solana.keypair_path)?,
+    );
+    let orders = HttpOrderSource::new(&config.orders.url);
+
+    let mut solver = Solver::new(Arc::new(bridge), Arc::new(orders), config.strategy.clone(), store)
+        .with_heartbeat_interval(config.heartbeat_interval_secs);
+    for chain in &config.chains {
+        solver = solver.with_adapter(chain.build(&config.solana.commitment)?);
+    }
+
+    match cli.command {
+        Some(Command::Once) => solver.tick(silence_solver::engine::unix_now()).await,
+        _ => solver.run(Duration::from_secs(config.poll_interval_secs)).await,
+    }
+}
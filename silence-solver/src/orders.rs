@@ -0,0 +1,109 @@
+//! Plaintext order details.
+//!
+//! Intents only carry a recipient hash and an amount commitment, so creators
+//! share the opening with solvers off-chain. Details are always checked
+//! against the on-chain hashes before the solver commits to an intent.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use project_silence_client::accounts::Intent;
+use serde::Deserialize;
+use silence_intent::DestinationAmount;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Opening of an intent's recipient hash and amount commitment
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderDetails {
+    pub recipient: String,
+    /// Full destination amount in the destination chain's base unit
+    pub destination_amount: u128,
+    pub blinding: Option<[u8; 32]>,
+}
+
+impl OrderDetails {
+    /// Whether the details open the intent's on-chain hashes
+    pub fn matches(&self, intent: &Intent) -> bool {
+        silence_intent::recipient_hash(&self.recipient) == intent.recipient_hash
+            && DestinationAmount::Commitment(intent.destination_amount_commitment)
+                .is_met_by(self.destination_amount, self.blinding.as_ref())
+    }
+}
+
+#[async_trait]
+pub trait OrderSource: Send + Sync {
+    /// Details for a Solana intent, if the creator published them
+    async fn details(&self, intent_id: u64) -> Result<Option<OrderDetails>>;
+}
+
+/// Wire format served by an order relay at `GET {base_url}/intents/{id}`
+#[derive(Deserialize)]
+struct OrderResponse {
+    recipient: String,
+    /// Decimal string, since amounts may exceed JSON's safe integer range
+    destination_amount: String,
+    /// Hex blinding factor
+    blinding: Option<String>,
+}
+
+pub struct HttpOrderSource {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpOrderSource {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl OrderSource for HttpOrderSource {
+    async fn details(&self, intent_id: u64) -> Result<Option<OrderDetails>> {
+        let response = self
+            .http
+            .get(format!("{}/intents/{intent_id}", self.base_url))
+            .send()
+            .await
+            .context("order relay request failed")?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let order: OrderResponse = response.error_for_status()?.json().await?;
+        let blinding = order
+            .blinding
+            .map(|b| -> Result<[u8; 32]> {
+                hex::decode(b)?
+                    .try_into()
+                    .map_err(|_| anyhow!("blinding must be 32 bytes"))
+            })
+            .transpose()?;
+        Ok(Some(OrderDetails {
+            recipient: order.recipient,
+            destination_amount: order.destination_amount.parse().context("invalid destination amount")?,
+            blinding,
+        }))
+    }
+}
+
+/// In-memory order source for tests and local runs
+#[derive(Default)]
+pub struct MemoryOrderSource {
+    orders: Mutex<HashMap<u64, OrderDetails>>,
+}
+
+impl MemoryOrderSource {
+    pub fn insert(&self, intent_id: u64, details: OrderDetails) {
+        self.orders.lock().unwrap().insert(intent_id, details);
+    }
+}
+
+#[async_trait]
+impl OrderSource for MemoryOrderSource {
+    async fn details(&self, intent_id: u64) -> Result<Option<OrderDetails>> {
+        Ok(self.orders.lock().unwrap().get(&intent_id).cloned())
+    }
+}
@@ -0,0 +1,232 @@
+//! Thin Solana JSON-RPC client covering the calls the solver needs.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How many times a sent transaction's status is polled before giving up
+const CONFIRM_ATTEMPTS: u32 = 60;
+const CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<Value>,
+    pub memo: Option<String>,
+}
+
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+    commitment: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>, commitment: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            commitment: commitment.into(),
+        }
+    }
+
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("{method} request failed"))?
+            .json()
+            .await
+            .with_context(|| format!("{method} returned invalid JSON"))?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+
+    /// Raw account data, or `None` if the account does not exist
+    pub async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self
+            .call(
+                "getAccountInfo",
+                json!([address.to_string(), { "encoding": "base64", "commitment": self.commitment }]),
+            )
+            .await?;
+        let Some(data) = result["value"]["data"][0].as_str() else {
+            return Ok(None);
+        };
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .context("account data is not base64")?;
+        Ok(Some(bytes))
+    }
+
+    /// Addresses and data of `program_id`'s accounts holding each
+    /// `(offset, bytes)` of `filters`
+    pub async fn program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[(usize, &[u8])],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let filters: Vec<Value> = filters
+            .iter()
+            .map(|(offset, bytes)| {
+                let bytes = bs58::encode(bytes).into_string();
+                json!({ "memcmp": { "offset": offset, "bytes": bytes } })
+            })
+            .collect();
+        let result = self
+            .call(
+                "getProgramAccounts",
+                json!([program_id.to_string(), {
+                    "encoding": "base64",
+                    "commitment": self.commitment,
+                    "filters": filters
+                }]),
+            )
+            .await?;
+        let entries = result
+            .as_array()
+            .ok_or_else(|| anyhow!("getProgramAccounts returned no accounts"))?;
+        entries
+            .iter()
+            .map(|entry| {
+                let address = entry["pubkey"]
+                    .as_str()
+                    .and_then(|s| Pubkey::from_str(s).ok())
+                    .ok_or_else(|| anyhow!("getProgramAccounts returned an invalid address"))?;
+                let data = entry["account"]["data"][0]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("{address} has no data"))?;
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .context("account data is not base64")?;
+                Ok((address, bytes))
+            })
+            .collect()
+    }
+
+    pub async fn balance(&self, address: &Pubkey) -> Result<u64> {
+        let result = self
+            .call("getBalance", json!([address.to_string(), { "commitment": self.commitment }]))
+            .await?;
+        result["value"].as_u64().ok_or_else(|| anyhow!("getBalance returned no value"))
+    }
+
+    /// Signatures touching `address`, newest first, older than `before`
+    /// and stopping at `until`
+    pub async fn signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>> {
+        let mut options = json!({ "limit": limit, "commitment": self.commitment });
+        if let Some(before) = before {
+            options["before"] = json!(before);
+        }
+        if let Some(until) = until {
+            options["until"] = json!(until);
+        }
+        let result = self
+            .call("getSignaturesForAddress", json!([address.to_string(), options]))
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Log messages of a confirmed transaction
+    pub async fn transaction_logs(&self, signature: &str) -> Result<Vec<String>> {
+        let result = self
+            .call(
+                "getTransaction",
+                json!([signature, {
+                    "encoding": "json",
+                    "commitment": self.commitment,
+                    "maxSupportedTransactionVersion": 0
+                }]),
+            )
+            .await?;
+        let logs = result["meta"]["logMessages"]
+            .as_array()
+            .map(|lines| lines.iter().filter_map(|l| l.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        Ok(logs)
+    }
+
+    pub async fn latest_blockhash(&self) -> Result<Hash> {
+        let result = self
+            .call("getLatestBlockhash", json!([{ "commitment": self.commitment }]))
+            .await?;
+        let hash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("getLatestBlockhash returned no blockhash"))?;
+        Hash::from_str(hash).map_err(|e| anyhow!("invalid blockhash: {e}"))
+    }
+
+    /// Sign, send and wait for confirmation of a transaction
+    pub async fn send_and_confirm(&self, instructions: &[Instruction], payer: &Keypair) -> Result<Signature> {
+        let blockhash = self.latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[payer], blockhash);
+        let wire = bincode::serialize(&tx).context("serialize transaction")?;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(wire);
+        let result = self
+            .call(
+                "sendTransaction",
+                json!([encoded, { "encoding": "base64", "preflightCommitment": self.commitment }]),
+            )
+            .await?;
+        let signature = result
+            .as_str()
+            .and_then(|s| Signature::from_str(s).ok())
+            .ok_or_else(|| anyhow!("sendTransaction returned no signature"))?;
+        self.confirm(&signature).await?;
+        Ok(signature)
+    }
+
+    async fn confirm(&self, signature: &Signature) -> Result<()> {
+        for _ in 0..CONFIRM_ATTEMPTS {
+            let result = self
+                .call("getSignatureStatuses", json!([[signature.to_string()]]))
+                .await?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {signature} failed: {}", status["err"]);
+                }
+                let level = status["confirmationStatus"].as_str().unwrap_or_default();
+                if commitment_rank(level) >= commitment_rank(&self.commitment) {
+                    return Ok(());
+                }
+            }
+            tokio::time::sleep(CONFIRM_INTERVAL).await;
+        }
+        bail!("transaction {signature} was not confirmed in time")
+    }
+}
+
+fn commitment_rank(level: &str) -> u8 {
+    match level {
+        "processed" => 1,
+        "confirmed" => 2,
+        "finalized" => 3,
+        _ => 0,
+    }
+}
@@ -0,0 +1,261 @@
+//! SQLite persistence for crash recovery.
+//!
+//! Every intent the solver looks at becomes a job. A job's state is written
+//! before the side effect it guards (e.g. `Transferring` before paying on the
+//! destination chain), so a restarted solver knows which steps may already
+//! have happened and must be reconciled rather than repeated.
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobState {
+    /// `match_intent` may have been sent
+    Matching,
+    /// Fill account exists on chain
+    Matched,
+    /// Destination transfer may have been sent
+    Transferring,
+    /// Destination transfer confirmed
+    Transferred,
+    /// `execute_intent` confirmed, waiting for finality
+    Executed,
+    /// Fill settled and closed
+    Settled,
+    /// Intent not taken
+    Skipped,
+    /// Job abandoned
+    Failed,
+}
+
+impl JobState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobState::Matching => "matching",
+            JobState::Matched => "matched",
+            JobState::Transferring => "transferring",
+            JobState::Transferred => "transferred",
+            JobState::Executed => "executed",
+            JobState::Settled => "settled",
+            JobState::Skipped => "skipped",
+            JobState::Failed => "failed",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobState::Settled | JobState::Skipped | JobState::Failed)
+    }
+}
+
+impl std::str::FromStr for JobState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "matching" => JobState::Matching,
+            "matched" => JobState::Matched,
+            "transferring" => JobState::Transferring,
+            "transferred" => JobState::Transferred,
+            "executed" => JobState::Executed,
+            "settled" => JobState::Settled,
+            "skipped" => JobState::Skipped,
+            "failed" => JobState::Failed,
+            other => return Err(anyhow!("unknown job state {other}")),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Job {
+    pub intent_id: u64,
+    pub destination_chain_id: u32,
+    pub intent_hash: [u8; 32],
+    pub state: JobState,
+    pub fill_amount: u64,
+    pub destination_amount: u128,
+    pub recipient: String,
+    pub blinding: Option<[u8; 32]>,
+    pub destination_tx_hash: Option<[u8; 32]>,
+    /// Unix time after which `settle_intent` may succeed
+    pub settle_after: i64,
+    pub reason: Option<String>,
+    pub updated_at: i64,
+}
+
+impl Job {
+    /// Job for an intent the solver declined
+    pub fn skipped(intent_id: u64, destination_chain_id: u32, reason: String) -> Self {
+        Self {
+            intent_id,
+            destination_chain_id,
+            intent_hash: [0; 32],
+            state: JobState::Skipped,
+            fill_amount: 0,
+            destination_amount: 0,
+            recipient: String::new(),
+            blinding: None,
+            destination_tx_hash: None,
+            settle_after: 0,
+            reason: Some(reason),
+            updated_at: 0,
+        }
+    }
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS jobs (
+    intent_id INTEGER PRIMARY KEY,
+    destination_chain_id INTEGER NOT NULL,
+    intent_hash BLOB NOT NULL,
+    state TEXT NOT NULL,
+    fill_amount INTEGER NOT NULL,
+    destination_amount TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    blinding BLOB,
+    destination_tx_hash BLOB,
+    settle_after INTEGER NOT NULL,
+    reason TEXT,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS jobs_state ON jobs(state);
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+const CURSOR_KEY: &str = "event_cursor";
+const HEARTBEAT_KEY: &str = "last_heartbeat";
+
+pub struct Store {
+    conn: Mutex<Connection>,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path.as_ref())
+            .with_context(|| format!("open {}", path.as_ref().display()))?;
+        Self::init(conn)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Insert or replace a job, stamping `updated_at`
+    pub fn save(&self, job: &Job, now: i64) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO jobs (intent_id, destination_chain_id, intent_hash, state, fill_amount,
+                destination_amount, recipient, blinding, destination_tx_hash, settle_after, reason, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                job.intent_id as i64,
+                job.destination_chain_id,
+                job.intent_hash.as_slice(),
+                job.state.as_str(),
+                job.fill_amount as i64,
+                job.destination_amount.to_string(),
+                job.recipient,
+                job.blinding.as_ref().map(|b| b.as_slice()),
+                job.destination_tx_hash.as_ref().map(|h| h.as_slice()),
+                job.settle_after,
+                job.reason,
+                now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn job(&self, intent_id: u64) -> Result<Option<Job>> {
+        let conn = self.conn.lock().unwrap();
+        let job = conn
+            .query_row("SELECT * FROM jobs WHERE intent_id = ?1", [intent_id as i64], |row| {
+                Ok(row_to_job(row))
+            })
+            .optional()?;
+        job.transpose()
+    }
+
+    /// Jobs that still have work to do, oldest first
+    pub fn pending_jobs(&self) -> Result<Vec<Job>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM jobs WHERE state NOT IN ('settled', 'skipped', 'failed') ORDER BY intent_id",
+        )?;
+        let jobs = stmt.query_map([], |row| Ok(row_to_job(row)))?;
+        jobs.map(|job| job?).collect()
+    }
+
+    pub fn jobs(&self) -> Result<Vec<Job>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT * FROM jobs ORDER BY intent_id")?;
+        let jobs = stmt.query_map([], |row| Ok(row_to_job(row)))?;
+        jobs.map(|job| job?).collect()
+    }
+
+    /// Last processed program transaction signature
+    pub fn cursor(&self) -> Result<Option<String>> {
+        self.meta(CURSOR_KEY)
+    }
+
+    pub fn set_cursor(&self, signature: &str) -> Result<()> {
+        self.set_meta(CURSOR_KEY, signature)
+    }
+
+    pub fn last_heartbeat(&self) -> Result<i64> {
+        Ok(self.meta(HEARTBEAT_KEY)?.and_then(|v| v.parse().ok()).unwrap_or(0))
+    }
+
+    pub fn set_last_heartbeat(&self, at: i64) -> Result<()> {
+        self.set_meta(HEARTBEAT_KEY, &at.to_string())
+    }
+
+    fn meta(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| row.get(0))
+            .optional()?)
+    }
+
+    fn set_meta(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+}
+
+fn hash_column(row: &Row, column: &str) -> Result<Option<[u8; 32]>> {
+    let bytes: Option<Vec<u8>> = row.get(column)?;
+    bytes
+        .map(|b| b.try_into().map_err(|_| anyhow!("{column} must be 32 bytes")))
+        .transpose()
+}
+
+fn row_to_job(row: &Row) -> Result<Job> {
+    let state: String = row.get("state")?;
+    let destination_amount: String = row.get("destination_amount")?;
+    Ok(Job {
+        intent_id: row.get::<_, i64>("intent_id")? as u64,
+        destination_chain_id: row.get("destination_chain_id")?,
+        intent_hash: hash_column(row, "intent_hash")?.unwrap_or_default(),
+        state: state.parse()?,
+        fill_amount: row.get::<_, i64>("fill_amount")? as u64,
+        destination_amount: destination_amount.parse()?,
+        recipient: row.get("recipient")?,
+        blinding: hash_column(row, "blinding")?,
+        destination_tx_hash: hash_column(row, "destination_tx_hash")?,
+        settle_after: row.get("settle_after")?,
+        reason: row.get("reason")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
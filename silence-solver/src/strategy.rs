@@ -0,0 +1,108 @@
+//! Profitability evaluation.
+
+use project_silence_client::accounts::{ChainConfig, Intent};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::orders::OrderDetails;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Exchange rate: `lamports` are worth `units` of the destination base unit
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Price {
+    pub lamports: u64,
+    pub units: u64,
+}
+
+impl Price {
+    /// Lamport value of `amount` destination units, rounded up
+    pub fn to_lamports(&self, amount: u128) -> u128 {
+        (amount * self.lamports as u128).div_ceil(self.units.max(1) as u128)
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Strategy {
+    /// Minimum profit over cost, in basis points of the fill amount
+    #[serde(default)]
+    pub min_profit_bps: u16,
+    /// Largest fill taken on one intent in lamports (0 = unlimited)
+    #[serde(default)]
+    pub max_fill_amount: u64,
+    /// Execution estimate passed to `match_intent` (program default if unset)
+    #[serde(default)]
+    pub estimated_time_secs: Option<i64>,
+    /// Price per destination chain id, taken from the configured chains
+    #[serde(skip)]
+    pub prices: HashMap<u32, Price>,
+}
+
+/// Terms the solver commits to when matching
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Quote {
+    /// Lamports claimed with `match_intent`
+    pub fill_amount: u64,
+    /// Destination units delivered to the recipient
+    pub destination_amount: u128,
+    /// Lamports paid to the solver on settlement
+    pub reward: u64,
+    /// Lamport value of the destination transfer including network fees
+    pub cost: u128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Fill(Quote),
+    Skip(String),
+}
+
+impl Strategy {
+    pub fn evaluate(
+        &self,
+        intent: &Intent,
+        chain: &ChainConfig,
+        protocol_fee_bps: u16,
+        details: &OrderDetails,
+        transfer_fee: u128,
+    ) -> Decision {
+        let Some(price) = self.prices.get(&intent.destination_chain_id) else {
+            return Decision::Skip(format!("no price for chain {}", intent.destination_chain_id));
+        };
+        let remaining = intent.remaining_amount();
+        if remaining == 0 {
+            return Decision::Skip("nothing left to fill".to_string());
+        }
+
+        // Receipts open the full amount commitment, so those chains take whole intents
+        let fill_amount = if intent.allow_partial_fill && !chain.requires_receipt && self.max_fill_amount > 0 {
+            remaining.min(self.max_fill_amount)
+        } else {
+            remaining
+        };
+        if chain.requires_receipt && fill_amount != intent.source_amount {
+            return Decision::Skip("receipt chains only accept whole intents".to_string());
+        }
+        if self.max_fill_amount > 0 && fill_amount > self.max_fill_amount {
+            return Decision::Skip(format!("fill {fill_amount} exceeds max {}", self.max_fill_amount));
+        }
+
+        let destination_amount =
+            details.destination_amount * fill_amount as u128 / intent.source_amount.max(1) as u128;
+        let fee_bps = chain.fee_bps_override.unwrap_or(protocol_fee_bps) as u128;
+        let protocol_fee = fill_amount as u128 * fee_bps / BPS_DENOMINATOR;
+        let reward = fill_amount - protocol_fee as u64;
+        let cost = price.to_lamports(destination_amount + transfer_fee);
+        let min_profit = fill_amount as u128 * self.min_profit_bps as u128 / BPS_DENOMINATOR;
+
+        if (reward as u128) < cost + min_profit {
+            return Decision::Skip(format!("unprofitable: reward {reward}, cost {cost}, min profit {min_profit}"));
+        }
+        Decision::Fill(Quote {
+            fill_amount,
+            destination_amount,
+            reward,
+            cost,
+        })
+    }
+}
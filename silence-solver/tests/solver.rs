@@ -0,0 +1,376 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use project_silence_client::accounts::{
+    BridgeConfig, ChainConfig, FillStatus, Intent, IntentFill, IntentStatus, PreviousVolume, ReceiptAttestation,
+    ReceiptRecipient, ReputationParams, Versioned, VolumeWindow,
+};
+use project_silence_client::events::IntentCreated;
+use silence_intent::{amount_commitment, recipient_hash, CHAIN_ID_NEAR, CHAIN_ID_ZCASH};
+use silence_solver::adapter::{ChainAdapter, MockAdapter, TransferRequest};
+use silence_solver::bridge::{Bridge, EventBatch};
+use silence_solver::orders::{MemoryOrderSource, OrderDetails};
+use silence_solver::store::{Job, JobState, Store};
+use silence_solver::strategy::{Price, Strategy};
+use silence_solver::Solver;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const NOW: i64 = 1_700_000_000;
+const BLINDING: [u8; 32] = [7; 32];
+const RECIPIENT: &str = "alice.testnet";
+
+/// In-memory model of the program, enough to drive the solver
+#[derive(Default)]
+struct MockBridge {
+    state: Mutex<BridgeState>,
+}
+
+#[derive(Default)]
+struct BridgeState {
+    /// Intent ids in `IntentCreated` order
+    events: Vec<u64>,
+    intents: HashMap<u64, Intent>,
+    fills: HashMap<u64, IntentFill>,
+    chains: HashMap<u32, ChainConfig>,
+    receipts: HashMap<[u8; 32], ReceiptAttestation>,
+    settled: Vec<u64>,
+}
+
+impl MockBridge {
+    fn add_chain(&self, chain_id: u32, requires_receipt: bool) {
+        self.state.lock().unwrap().chains.insert(
+            chain_id,
+            ChainConfig {
+                chain_id,
+                name: format!("chain-{chain_id}"),
+                enabled: true,
+                min_amount: 0,
+                finality_seconds: 0,
+                fee_bps_override: None,
+                volume_cap: 0,
+                volume_window: VolumeWindow::default(),
+                requires_receipt,
+                bump: 0,
+                layout_version: ChainConfig::LAYOUT_VERSION,
+                previous_window_volume: PreviousVolume(Some(0)),
+            },
+        );
+    }
+
+    /// Create an intent and its event; returns the matching order details
+    fn create_intent(&self, intent_id: u64, chain_id: u32, source_amount: u64, destination_amount: u128) -> OrderDetails {
+        let intent = Intent {
+            intent_id,
+            creator: Pubkey::new_from_array([1; 32]),
+            source_chain_id: 1,
+            destination_chain_id: chain_id,
+            source_amount,
+            destination_amount_commitment: amount_commitment(destination_amount, &BLINDING),
+            source_token: Pubkey::default(),
+            destination_token_hash: [2; 32],
+            recipient_hash: recipient_hash(RECIPIENT),
+            is_shielded: false,
+            status: IntentStatus::Created,
+            allow_partial_fill: false,
+            filled_amount: 0,
+            settled_amount: 0,
+            refunded_amount: 0,
+            matched_fills: 0,
+            executed_fills: 0,
+            created_at: NOW,
+            expires_at: NOW + 3600,
+            executed_at: None,
+            destination_tx_hash: [0; 32],
+            privacy_proof: [0; 32],
+            bump: 0,
+            layout_version: Intent::LAYOUT_VERSION,
+        };
+        let mut state = self.state.lock().unwrap();
+        state.events.push(intent_id);
+        state.intents.insert(intent_id, intent);
+        OrderDetails {
+            recipient: RECIPIENT.to_string(),
+            destination_amount,
+            blinding: Some(BLINDING),
+        }
+    }
+
+    fn attest_receipt(&self, intent_hash: [u8; 32]) {
+        self.state.lock().unwrap().receipts.insert(
+            intent_hash,
+            ReceiptAttestation {
+                receipt_hash: [9; 32],
+                intent_hash,
+                destination_chain_id: CHAIN_ID_NEAR,
+                solver: self.solver_authority(),
+                amount: 0,
+                fulfilled_at: 0,
+                attesters: vec![],
+                attested: true,
+                consumed: false,
+                bump: 0,
+                layout_version: ReceiptAttestation::LAYOUT_VERSION,
+                recipient_hash: ReceiptRecipient(Some(recipient_hash(RECIPIENT))),
+            },
+        );
+    }
+
+    fn settled(&self) -> Vec<u64> {
+        self.state.lock().unwrap().settled.clone()
+    }
+}
+
+#[async_trait]
+impl Bridge for MockBridge {
+    fn solver_authority(&self) -> Pubkey {
+        Pubkey::new_from_array([5; 32])
+    }
+
+    async fn intent_events(&self, cursor: Option<&str>) -> Result<EventBatch> {
+        let state = self.state.lock().unwrap();
+        let start = cursor.map(|c| c.parse().unwrap()).unwrap_or(0);
+        let events = state.events[start..]
+            .iter()
+            .map(|intent_id| {
+                let intent = &state.intents[intent_id];
+                IntentCreated {
+                    intent_id: *intent_id,
+                    creator: intent.creator,
+                    destination_chain_id: intent.destination_chain_id,
+                    source_amount: intent.source_amount,
+                    is_shielded: intent.is_shielded,
+                }
+            })
+            .collect();
+        Ok(EventBatch {
+            events,
+            cursor: Some(state.events.len().to_string()),
+        })
+    }
+
+    async fn bridge_config(&self) -> Result<BridgeConfig> {
+        Ok(BridgeConfig {
+            owner: Pubkey::default(),
+            min_solver_stake: 0,
+            protocol_fee_bps: 30,
+            total_volume: 0,
+            total_intents: 0,
+            active_solvers: 1,
+            fee_vault: Pubkey::default(),
+            guardian: Pubkey::default(),
+            paused: false,
+            rate_limit_window_secs: 0,
+            solver_volume_cap: 0,
+            staker_fee_share_bps: 0,
+            total_stake: 0,
+            heartbeat_timeout_secs: 0,
+            reputation: ReputationParams::default(),
+            default_execution_secs: 3600,
+            max_execution_secs: 86_400,
+            bump: 0,
+            layout_version: BridgeConfig::LAYOUT_VERSION,
+        })
+    }
+
+    async fn chain_config(&self, chain_id: u32) -> Result<Option<ChainConfig>> {
+        Ok(self.state.lock().unwrap().chains.get(&chain_id).cloned())
+    }
+
+    async fn intent(&self, intent_id: u64) -> Result<Option<Intent>> {
+        Ok(self.state.lock().unwrap().intents.get(&intent_id).cloned())
+    }
+
+    async fn fill(&self, intent_id: u64) -> Result<Option<IntentFill>> {
+        Ok(self.state.lock().unwrap().fills.get(&intent_id).cloned())
+    }
+
+    async fn receipt(&self, intent_hash: &[u8; 32]) -> Result<Option<ReceiptAttestation>> {
+        Ok(self.state.lock().unwrap().receipts.get(intent_hash).cloned())
+    }
+
+    async fn heartbeat(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn match_intent(&self, intent_id: u64, _: u32, fill_amount: u64, _: Option<i64>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let intent = state.intents.get_mut(&intent_id).unwrap();
+        if intent.status != IntentStatus::Created {
+            bail!("IntentAlreadyMatched");
+        }
+        intent.status = IntentStatus::Matched;
+        intent.filled_amount += fill_amount;
+        state.fills.insert(
+            intent_id,
+            IntentFill {
+                intent: Pubkey::default(),
+                solver: Pubkey::new_from_array([5; 32]),
+                amount: fill_amount,
+                status: FillStatus::Matched,
+                matched_at: NOW,
+                execution_deadline: NOW + 3600,
+                executed_at: None,
+                destination_tx_hash: [0; 32],
+                bump: 0,
+                layout_version: IntentFill::LAYOUT_VERSION,
+            },
+        );
+        Ok(())
+    }
+
+    async fn execute_intent(
+        &self,
+        intent_id: u64,
+        destination_chain_id: u32,
+        destination_tx_hash: [u8; 32],
+        receipt_hash: Option<&[u8; 32]>,
+    ) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let attested = receipt_hash.is_some_and(|hash| state.receipts.values().any(|r| r.receipt_hash == *hash));
+        if state.chains[&destination_chain_id].requires_receipt && !attested {
+            bail!("ReceiptRequired");
+        }
+        let fill = state.fills.get_mut(&intent_id).unwrap();
+        fill.status = FillStatus::Executed;
+        fill.executed_at = Some(NOW);
+        fill.destination_tx_hash = destination_tx_hash;
+        Ok(())
+    }
+
+    async fn settle_intent(&self, intent_id: u64, _: u32) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let fill = state.fills.remove(&intent_id).unwrap();
+        assert!(fill.status == FillStatus::Executed);
+        state.intents.get_mut(&intent_id).unwrap().status = IntentStatus::Settled;
+        state.settled.push(intent_id);
+        Ok(())
+    }
+}
+
+/// 1 lamport per destination unit, 30 bps protocol fee, 10 bps margin
+fn strategy() -> Strategy {
+    Strategy {
+        min_profit_bps: 10,
+        max_fill_amount: 0,
+        estimated_time_secs: None,
+        prices: HashMap::from([
+            (CHAIN_ID_ZCASH, Price { lamports: 1, units: 1 }),
+            (CHAIN_ID_NEAR, Price { lamports: 1, units: 1 }),
+        ]),
+    }
+}
+
+fn solver(bridge: &Arc<MockBridge>, orders: &Arc<MemoryOrderSource>, adapter: &Arc<MockAdapter>, store: Store) -> Solver {
+    Solver::new(bridge.clone(), orders.clone(), strategy(), store).with_adapter(adapter.clone())
+}
+
+fn job(store: &Store, intent_id: u64) -> Job {
+    store.job(intent_id).unwrap().expect("job recorded")
+}
+
+#[tokio::test]
+async fn settles_a_profitable_intent() {
+    let bridge = Arc::new(MockBridge::default());
+    let orders = Arc::new(MemoryOrderSource::default());
+    let adapter = Arc::new(MockAdapter::new(CHAIN_ID_ZCASH, 10_000_000));
+    bridge.add_chain(CHAIN_ID_ZCASH, false);
+    orders.insert(0, bridge.create_intent(0, CHAIN_ID_ZCASH, 1_000_000, 990_000));
+
+    let solver = solver(&bridge, &orders, &adapter, Store::in_memory().unwrap());
+    solver.tick(NOW).await.unwrap();
+
+    let job = job(solver.store(), 0);
+    assert_eq!(job.state, JobState::Settled);
+    assert_eq!(job.fill_amount, 1_000_000);
+    assert_eq!(bridge.settled(), vec![0]);
+    let transfers = adapter.transfers();
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].recipient, RECIPIENT);
+    assert_eq!(transfers[0].amount, 990_000);
+    assert_eq!(transfers[0].blinding, Some(BLINDING));
+}
+
+#[tokio::test]
+async fn skips_unprofitable_and_unverifiable_intents() {
+    let bridge = Arc::new(MockBridge::default());
+    let orders = Arc::new(MemoryOrderSource::default());
+    let adapter = Arc::new(MockAdapter::new(CHAIN_ID_ZCASH, 10_000_000));
+    bridge.add_chain(CHAIN_ID_ZCASH, false);
+
+    // Reward after the 30 bps fee is below the destination amount
+    orders.insert(0, bridge.create_intent(0, CHAIN_ID_ZCASH, 1_000_000, 999_000));
+    // Published amount does not open the commitment
+    let mut details = bridge.create_intent(1, CHAIN_ID_ZCASH, 1_000_000, 900_000);
+    details.destination_amount = 800_000;
+    orders.insert(1, details);
+    // Nothing published
+    bridge.create_intent(2, CHAIN_ID_ZCASH, 1_000_000, 900_000);
+
+    let solver = solver(&bridge, &orders, &adapter, Store::in_memory().unwrap());
+    solver.tick(NOW).await.unwrap();
+
+    for intent_id in 0..3 {
+        assert_eq!(job(solver.store(), intent_id).state, JobState::Skipped);
+    }
+    assert!(adapter.transfers().is_empty());
+    assert!(bridge.settled().is_empty());
+}
+
+#[tokio::test]
+async fn resumes_after_restart_without_paying_twice() {
+    let db = std::env::temp_dir().join(format!("silence-solver-test-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&db);
+
+    let bridge = Arc::new(MockBridge::default());
+    let orders = Arc::new(MemoryOrderSource::default());
+    let adapter = Arc::new(MockAdapter::new(CHAIN_ID_ZCASH, 10_000_000));
+    bridge.add_chain(CHAIN_ID_ZCASH, false);
+    orders.insert(0, bridge.create_intent(0, CHAIN_ID_ZCASH, 1_000_000, 990_000));
+
+    // The first run matches, then the transfer fails
+    adapter.fail_next_transfer();
+    solver(&bridge, &orders, &adapter, Store::open(&db).unwrap()).tick(NOW).await.unwrap();
+    let store = Store::open(&db).unwrap();
+    assert_eq!(job(&store, 0).state, JobState::Transferring);
+    drop(store);
+
+    // A transfer that went out before a crash is found, not repeated
+    let request = TransferRequest {
+        intent_id: 0,
+        intent_hash: job(&Store::open(&db).unwrap(), 0).intent_hash,
+        recipient: RECIPIENT.to_string(),
+        amount: 990_000,
+        blinding: Some(BLINDING),
+        source_solver: [5; 32],
+    };
+    adapter.transfer(&request).await.unwrap();
+
+    let restarted = solver(&bridge, &orders, &adapter, Store::open(&db).unwrap());
+    restarted.tick(NOW).await.unwrap();
+    assert_eq!(job(restarted.store(), 0).state, JobState::Settled);
+    assert_eq!(adapter.transfers().len(), 1);
+    assert_eq!(bridge.settled(), vec![0]);
+
+    let _ = std::fs::remove_file(&db);
+}
+
+#[tokio::test]
+async fn waits_for_receipt_attestation() {
+    let bridge = Arc::new(MockBridge::default());
+    let orders = Arc::new(MemoryOrderSource::default());
+    let adapter = Arc::new(MockAdapter::new(CHAIN_ID_NEAR, 10_000_000));
+    bridge.add_chain(CHAIN_ID_NEAR, true);
+    orders.insert(0, bridge.create_intent(0, CHAIN_ID_NEAR, 1_000_000, 990_000));
+
+    let solver = solver(&bridge, &orders, &adapter, Store::in_memory().unwrap());
+    solver.tick(NOW).await.unwrap();
+    let pending = job(solver.store(), 0);
+    assert_eq!(pending.state, JobState::Transferred);
+    assert_eq!(adapter.transfers().len(), 1);
+
+    bridge.attest_receipt(pending.intent_hash);
+    solver.tick(NOW + 5).await.unwrap();
+    assert_eq!(job(solver.store(), 0).state, JobState::Settled);
+    assert_eq!(adapter.transfers().len(), 1);
+}
@@ -48,17 +48,19 @@ describe("ProjectSilence", () => {
   const arciumEnv = getArciumEnv();
   let owner: Keypair;
   let configPda: PublicKey;
-  let feeVault: Keypair;
+  let feeVaultPda: PublicKey;
 
   before(async () => {
     owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
-    feeVault = Keypair.generate();
-    
-    // Find config PDA
+    // Find config and fee vault PDAs
     [configPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("bridge_config")],
       program.programId
     );
+    [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault")],
+      program.programId
+    );
   });
 
   // =========================================================================
@@ -75,7 +77,7 @@ describe("ProjectSilence", () => {
         .accounts({
           owner: owner.publicKey,
           config: configPda,
-          feeVault: feeVault.publicKey,
+          feeVault: feeVaultPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([owner])
@@ -85,6 +87,7 @@ describe("ProjectSilence", () => {
       expect(config.owner.toString()).to.equal(owner.publicKey.toString());
       expect(config.minSolverStake.toNumber()).to.equal(minSolverStake.toNumber());
       expect(config.protocolFeeBps).to.equal(protocolFeeBps);
+      expect(config.feeVault.toString()).to.equal(feeVaultPda.toString());
     });
   });
 
@@ -95,12 +98,39 @@ describe("ProjectSilence", () => {
   describe("Model Registry", () => {
     const modelId = new anchor.BN(1);
     let modelPda: PublicKey;
+    let modelAccessPda: PublicKey;
+    let modelVersionPda: PublicKey;
+    let directoryPda: PublicKey;
+    let modelIndexPda: PublicKey;
+    let modelListingPda: PublicKey;
 
     before(() => {
       [modelPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("model"), modelId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
+      [modelAccessPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_access"), modelId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      // Registered version, never published, so requests stay unpinned
+      [modelVersionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_version"), modelId.toArrayLike(Buffer, "le", 8), Buffer.from("1.0.0")],
+        program.programId
+      );
+      [directoryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_directory")],
+        program.programId
+      );
+      // First model on a fresh validator, so the first index page
+      [modelIndexPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_index"), new anchor.BN(0).toArrayLike(Buffer, "le", 4)],
+        program.programId
+      );
+      [modelListingPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_listing"), modelId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
     });
 
     it("registers a new AI model", async () => {
@@ -126,6 +156,9 @@ describe("ProjectSilence", () => {
         .accounts({
           owner: owner.publicKey,
           model: modelPda,
+          directory: directoryPda,
+          modelIndex: modelIndexPda,
+          modelListing: modelListingPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([owner])
@@ -139,36 +172,42 @@ describe("ProjectSilence", () => {
     });
 
     it("updates model metadata", async () => {
-      const newVersion = "1.1.0";
       const newCost = new anchor.BN(150000);
 
       await program.methods
         .updateModel(
           null, // name unchanged
           null, // description unchanged
-          newVersion,
           newCost,
           null // is_active unchanged
         )
         .accounts({
-          owner: owner.publicKey,
+          authority: owner.publicKey,
           model: modelPda,
+          modelAccess: modelAccessPda,
+          modelListing: modelListingPda,
+          modelIndex: modelIndexPda,
         })
         .signers([owner])
         .rpc();
 
       const model = await program.account.modelMetadata.fetch(modelPda);
-      expect(model.version).to.equal(newVersion);
       expect(model.costPerInference.toNumber()).to.equal(newCost.toNumber());
+      const index = await program.account.modelIndex.fetch(modelIndexPda);
+      expect(index.entries[0].costPerInference.toNumber()).to.equal(newCost.toNumber());
     });
 
     it("creates an inference request", async () => {
-      const requestId = new anchor.BN(1);
+      const requestId = new anchor.BN(0); // First request for this user
       const promptHash = Array.from(randomBytes(32));
       const requireAttestation = true;
 
       const [requestPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("request"), requestId.toArrayLike(Buffer, "le", 8)],
+        [
+          Buffer.from("request"),
+          owner.publicKey.toBuffer(),
+          requestId.toArrayLike(Buffer, "le", 8),
+        ],
         program.programId
       );
 
@@ -178,13 +217,17 @@ describe("ProjectSilence", () => {
       );
 
       await program.methods
-        .createInferenceRequest(requestId, promptHash, requireAttestation)
+        .createInferenceRequest(promptHash, requireAttestation)
         .accounts({
           user: owner.publicKey,
           model: modelPda,
+          modelAccess: modelAccessPda,
           modelOwner: owner.publicKey,
-          request: requestPda,
+          modelVersion: modelVersionPda,
+          creditAccount: null, // paid in lamports
+          subscription: null,
           userMetrics: userMetricsPda,
+          request: requestPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([owner])
@@ -197,6 +240,7 @@ describe("ProjectSilence", () => {
 
       const metrics = await program.account.userMetrics.fetch(userMetricsPda);
       expect(metrics.totalInferences.toNumber()).to.equal(1);
+      expect(metrics.nextRequestId.toNumber()).to.equal(1);
     });
   });
 
@@ -206,8 +250,10 @@ describe("ProjectSilence", () => {
 
   describe("Batch Inference", () => {
     const modelId = new anchor.BN(1);
-    const batchId = new anchor.BN(1);
+    const batchId = new anchor.BN(0); // First batch for this user
     let modelPda: PublicKey;
+    let modelAccessPda: PublicKey;
+    let modelVersionPda: PublicKey;
     let batchPda: PublicKey;
 
     before(() => {
@@ -215,8 +261,20 @@ describe("ProjectSilence", () => {
         [Buffer.from("model"), modelId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
+      [modelAccessPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_access"), modelId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [modelVersionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("model_version"), modelId.toArrayLike(Buffer, "le", 8), Buffer.from("1.0.0")],
+        program.programId
+      );
       [batchPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("batch"), batchId.toArrayLike(Buffer, "le", 8)],
+        [
+          Buffer.from("batch"),
+          owner.publicKey.toBuffer(),
+          batchId.toArrayLike(Buffer, "le", 8),
+        ],
         program.programId
       );
     });
@@ -231,13 +289,17 @@ describe("ProjectSilence", () => {
       );
 
       await program.methods
-        .createBatchInference(batchId, promptCount, requireAttestation)
+        .createBatchInference(promptCount, requireAttestation)
         .accounts({
           user: owner.publicKey,
           model: modelPda,
+          modelAccess: modelAccessPda,
           modelOwner: owner.publicKey,
-          batch: batchPda,
+          modelVersion: modelVersionPda,
+          creditAccount: null, // paid in lamports
+          subscription: null,
           userMetrics: userMetricsPda,
+          batch: batchPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([owner])
@@ -275,7 +337,7 @@ describe("ProjectSilence", () => {
     });
 
     it("registers a new solver", async () => {
-      const supportedChains = 0b011; // Solana + NEAR
+      const supportedChains = [1, 2]; // Solana + NEAR chain ids
 
       await program.methods
         .registerSolver(supportedChains)
@@ -283,6 +345,7 @@ describe("ProjectSilence", () => {
           user: solver.publicKey,
           config: configPda,
           solver: solverPda,
+          feeVault: feeVaultPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([solver])
@@ -290,10 +353,64 @@ describe("ProjectSilence", () => {
 
       const solverAccount = await program.account.solver.fetch(solverPda);
       expect(solverAccount.solverId.toString()).to.equal(solver.publicKey.toString());
-      expect(solverAccount.supportedChains).to.equal(supportedChains);
+      expect(solverAccount.supportedChains).to.deep.equal(supportedChains);
       expect(solverAccount.reputationScore).to.equal(100);
       expect(solverAccount.isActive).to.equal(true);
     });
+
+    it("updates the solver profile and records a heartbeat", async () => {
+      await program.methods
+        .updateSolver([1, 2, 3], "https://solver.example/profile.json", 1000, null)
+        .accounts({
+          solverAuthority: solver.publicKey,
+          solver: solverPda,
+        })
+        .signers([solver])
+        .rpc();
+
+      await program.methods
+        .solverHeartbeat()
+        .accounts({
+          solverAuthority: solver.publicKey,
+          solver: solverPda,
+        })
+        .signers([solver])
+        .rpc();
+
+      const solverAccount = await program.account.solver.fetch(solverPda);
+      expect(solverAccount.supportedChains).to.deep.equal([1, 2, 3]);
+      expect(solverAccount.metadataUri).to.equal("https://solver.example/profile.json");
+      expect(solverAccount.feeBps).to.equal(1000);
+      expect(solverAccount.lastHeartbeatAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("deactivates and reactivates a solver with owner approval", async () => {
+      await program.methods
+        .deactivateSolver()
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+          solver: solverPda,
+          feeVault: feeVaultPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .reactivateSolver()
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+          solver: solverPda,
+          solverAuthority: solver.publicKey,
+          feeVault: feeVaultPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      const solverAccount = await program.account.solver.fetch(solverPda);
+      expect(solverAccount.isActive).to.equal(true);
+    });
   });
 
   // =========================================================================
@@ -304,8 +421,10 @@ describe("ProjectSilence", () => {
     let creator: Keypair;
     let solver: Keypair;
     let intentPda: PublicKey;
+    let fillPda: PublicKey;
     let solverPda: PublicKey;
-    const intentId = new anchor.BN(1);
+    let nearChainConfigPda: PublicKey;
+    let intentId: anchor.BN;
 
     before(async () => {
       creator = Keypair.generate();
@@ -324,6 +443,10 @@ describe("ProjectSilence", () => {
       );
       await provider.connection.confirmTransaction(sig2);
 
+      // Intent ids are assigned from the config counter
+      const config = await program.account.bridgeConfig.fetch(configPda);
+      intentId = config.totalIntents;
+
       [intentPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("intent"), intentId.toArrayLike(Buffer, "le", 8)],
         program.programId
@@ -334,13 +457,37 @@ describe("ProjectSilence", () => {
         program.programId
       );
 
+      [fillPda] = getFillPda(program.programId, intentPda, solver.publicKey);
+
+      [nearChainConfigPda] = getChainConfigPda(program.programId, NEAR_CHAIN_ID);
+
+      // Register destination chain (no minimum, no finality delay, no cap)
+      await program.methods
+        .registerChain(
+          NEAR_CHAIN_ID,
+          "near",
+          new anchor.BN(0),
+          0,
+          null,
+          new anchor.BN(0)
+        )
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
       // Register solver first
       await program.methods
-        .registerSolver(0b111) // All chains
+        .registerSolver([1, 2, 3]) // All chains
         .accounts({
           user: solver.publicKey,
           config: configPda,
           solver: solverPda,
+          feeVault: feeVaultPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([solver])
@@ -348,7 +495,7 @@ describe("ProjectSilence", () => {
     });
 
     it("creates a cross-chain intent", async () => {
-      const destinationChain = { near: {} };
+      const destinationChainId = NEAR_CHAIN_ID;
       const destinationAmountCommitment = Array.from(randomBytes(32));
       const destinationTokenHash = Array.from(randomBytes(32));
       const recipientHash = Array.from(randomBytes(32));
@@ -364,18 +511,19 @@ describe("ProjectSilence", () => {
 
       await program.methods
         .createIntent(
-          intentId,
-          destinationChain,
+          destinationChainId,
           destinationAmountCommitment,
           destinationTokenHash,
           recipientHash,
           isShielded,
           ttlSeconds,
-          sourceAmount
+          sourceAmount,
+          false
         )
         .accounts({
           creator: creator.publicKey,
           config: configPda,
+          chainConfig: nearChainConfigPda,
           intent: intentPda,
           intentVault: intentVaultPda,
           systemProgram: SystemProgram.programId,
@@ -397,18 +545,25 @@ describe("ProjectSilence", () => {
 
     it("matches an intent with a solver", async () => {
       await program.methods
-        .matchIntent()
+        .matchIntent(new anchor.BN(LAMPORTS_PER_SOL), null)
         .accounts({
           solverAuthority: solver.publicKey,
           intent: intentPda,
+          fill: fillPda,
           solver: solverPda,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          systemProgram: SystemProgram.programId,
         })
         .signers([solver])
         .rpc();
 
       const intent = await program.account.intent.fetch(intentPda);
       expect(intent.status).to.deep.equal({ matched: {} });
-      expect(intent.solver.toString()).to.equal(solver.publicKey.toString());
+      expect(intent.filledAmount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+
+      const fill = await program.account.intentFill.fetch(fillPda);
+      expect(fill.solver.toString()).to.equal(solver.publicKey.toString());
     });
 
     it("executes an intent", async () => {
@@ -419,6 +574,10 @@ describe("ProjectSilence", () => {
         .accounts({
           solverAuthority: solver.publicKey,
           intent: intentPda,
+          fill: fillPda,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          receipt: null,
         })
         .signers([solver])
         .rpc();
@@ -427,6 +586,170 @@ describe("ProjectSilence", () => {
       expect(intent.status).to.deep.equal({ executed: {} });
       expect(intent.executedAt).to.not.be.null;
     });
+
+    it("settles executed fills in a batch", async () => {
+      const [intentVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("intent_vault"), intentId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .settleIntentsBatch()
+        .accounts({
+          authority: solver.publicKey,
+          solver: solverPda,
+          solverAuthority: solver.publicKey,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          feeVault: feeVaultPda,
+        })
+        .remainingAccounts([
+          { pubkey: intentPda, isSigner: false, isWritable: true },
+          { pubkey: fillPda, isSigner: false, isWritable: true },
+          { pubkey: intentVaultPda, isSigner: false, isWritable: true },
+        ])
+        .signers([solver])
+        .rpc();
+
+      const intent = await program.account.intent.fetch(intentPda);
+      expect(intent.status).to.deep.equal({ settled: {} });
+      expect(intent.settledAmount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+
+      const solverAccount = await program.account.solver.fetch(solverPda);
+      expect(solverAccount.successfulIntents.toNumber()).to.equal(1);
+
+      const fill = await provider.connection.getAccountInfo(fillPda);
+      expect(fill).to.be.null;
+    });
+
+    it("cancels and closes an unmatched intent", async () => {
+      const config = await program.account.bridgeConfig.fetch(configPda);
+      const [cancelIntentPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("intent"), config.totalIntents.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [cancelVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("intent_vault"), config.totalIntents.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .createIntent(
+          NEAR_CHAIN_ID,
+          Array.from(randomBytes(32)),
+          Array.from(randomBytes(32)),
+          Array.from(randomBytes(32)),
+          false,
+          new anchor.BN(3600),
+          new anchor.BN(LAMPORTS_PER_SOL),
+          false
+        )
+        .accounts({
+          creator: creator.publicKey,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          intent: cancelIntentPda,
+          intentVault: cancelVaultPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .cancelIntent()
+        .accounts({
+          creator: creator.publicKey,
+          intent: cancelIntentPda,
+          intentVault: cancelVaultPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const intent = await program.account.intent.fetch(cancelIntentPda);
+      expect(intent.status).to.deep.equal({ cancelled: {} });
+
+      await program.methods
+        .closeIntent()
+        .accounts({
+          creator: creator.publicKey,
+          intent: cancelIntentPda,
+          intentVault: cancelVaultPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(cancelIntentPda);
+      expect(closed).to.be.null;
+    });
+
+    it("partially fills an intent and keeps the remainder open", async () => {
+      const config = await program.account.bridgeConfig.fetch(configPda);
+      const [partialIntentPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("intent"), config.totalIntents.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [partialVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("intent_vault"), config.totalIntents.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [partialFillPda] = getFillPda(program.programId, partialIntentPda, solver.publicKey);
+
+      await program.methods
+        .createIntent(
+          NEAR_CHAIN_ID,
+          Array.from(randomBytes(32)),
+          Array.from(randomBytes(32)),
+          Array.from(randomBytes(32)),
+          false,
+          new anchor.BN(3600),
+          new anchor.BN(LAMPORTS_PER_SOL),
+          true
+        )
+        .accounts({
+          creator: creator.publicKey,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          intent: partialIntentPda,
+          intentVault: partialVaultPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .matchIntent(new anchor.BN(LAMPORTS_PER_SOL / 4), new anchor.BN(600))
+        .accounts({
+          solverAuthority: solver.publicKey,
+          intent: partialIntentPda,
+          fill: partialFillPda,
+          solver: solverPda,
+          config: configPda,
+          chainConfig: nearChainConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([solver])
+        .rpc();
+
+      const intent = await program.account.intent.fetch(partialIntentPda);
+      expect(intent.status).to.deep.equal({ created: {} });
+      expect(intent.filledAmount.toNumber()).to.equal(LAMPORTS_PER_SOL / 4);
+
+      // Remainder cannot be cancelled before expiry once a fill exists
+      try {
+        await program.methods
+          .cancelIntent()
+          .accounts({
+            creator: creator.publicKey,
+            intent: partialIntentPda,
+            intentVault: partialVaultPda,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err) {
+        expect(err.toString()).to.include("InvalidIntentStatus");
+      }
+    });
   });
 
   // =========================================================================
@@ -653,6 +976,177 @@ describe("ProjectSilence", () => {
         expect(err.message).to.include("FeeTooHigh");
       }
     });
+
+    it("sets the staker fee split", async () => {
+      await program.methods
+        .setFeeSplit(2000) // 20% of fees to stakers
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      const config = await program.account.bridgeConfig.fetch(configPda);
+      expect(config.stakerFeeShareBps).to.equal(2000);
+    });
+
+    it("rejects fee withdrawal above available balance", async () => {
+      try {
+        await program.methods
+          .withdrawFees(new anchor.BN(1000 * LAMPORTS_PER_SOL))
+          .accounts({
+            owner: owner.publicKey,
+            config: configPda,
+            feeVault: feeVaultPda,
+            recipient: owner.publicKey,
+          })
+          .signers([owner])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err) {
+        expect(err.message).to.include("InsufficientVaultBalance");
+      }
+    });
+
+    it("updates rate limits", async () => {
+      const windowSecs = new anchor.BN(3600);
+      const solverVolumeCap = new anchor.BN(100 * LAMPORTS_PER_SOL);
+
+      await program.methods
+        .setRateLimits(windowSecs, solverVolumeCap)
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      const config = await program.account.bridgeConfig.fetch(configPda);
+      expect(config.rateLimitWindowSecs.toNumber()).to.equal(3600);
+      expect(config.solverVolumeCap.toString()).to.equal(solverVolumeCap.toString());
+    });
+
+    it("lets the guardian pause and only the owner unpause", async () => {
+      const guardian = Keypair.generate();
+
+      await program.methods
+        .setGuardian(guardian.publicKey)
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .setPaused(true)
+        .accounts({
+          authority: guardian.publicKey,
+          config: configPda,
+        })
+        .signers([guardian])
+        .rpc();
+
+      let config = await program.account.bridgeConfig.fetch(configPda);
+      expect(config.paused).to.equal(true);
+
+      try {
+        await program.methods
+          .setPaused(false)
+          .accounts({
+            authority: guardian.publicKey,
+            config: configPda,
+          })
+          .signers([guardian])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (err) {
+        expect(err.message).to.include("Unauthorized");
+      }
+
+      await program.methods
+        .setPaused(false)
+        .accounts({
+          authority: owner.publicKey,
+          config: configPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      config = await program.account.bridgeConfig.fetch(configPda);
+      expect(config.paused).to.equal(false);
+    });
+
+    it("disables and re-enables a chain", async () => {
+      const [chainConfigPda] = getChainConfigPda(program.programId, NEAR_CHAIN_ID);
+
+      await program.methods
+        .setChainEnabled(false)
+        .accounts({
+          authority: owner.publicKey,
+          config: configPda,
+          chainConfig: chainConfigPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      let chainConfig = await program.account.chainConfig.fetch(chainConfigPda);
+      expect(chainConfig.enabled).to.equal(false);
+
+      await program.methods
+        .setChainEnabled(true)
+        .accounts({
+          authority: owner.publicKey,
+          config: configPda,
+          chainConfig: chainConfigPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      chainConfig = await program.account.chainConfig.fetch(chainConfigPda);
+      expect(chainConfig.enabled).to.equal(true);
+    });
+
+    it("overrides the protocol fee for a chain", async () => {
+      const [chainConfigPda] = getChainConfigPda(program.programId, NEAR_CHAIN_ID);
+
+      await program.methods
+        .updateChain(null, null, null, 25, null, null)
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+          chainConfig: chainConfigPda,
+        })
+        .signers([owner])
+        .rpc();
+
+      const chainConfig = await program.account.chainConfig.fetch(chainConfigPda);
+      expect(chainConfig.feeBpsOverride).to.equal(25);
+    });
+
+    it("sets the relayer set", async () => {
+      const [relayerSetPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("relayer_set")],
+        program.programId
+      );
+      const relayer = Keypair.generate();
+
+      await program.methods
+        .setRelayers([owner.publicKey, relayer.publicKey], 2)
+        .accounts({
+          owner: owner.publicKey,
+          config: configPda,
+          relayerSet: relayerSetPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      const relayerSet = await program.account.relayerSet.fetch(relayerSetPda);
+      expect(relayerSet.relayers.length).to.equal(2);
+      expect(relayerSet.threshold).to.equal(2);
+    });
   });
 });
 
@@ -660,6 +1154,20 @@ describe("ProjectSilence", () => {
 // HELPER FUNCTIONS
 // =========================================================================
 
+const NEAR_CHAIN_ID = 2;
+
+function getChainConfigPda(
+  programId: PublicKey,
+  chainId: number
+): [PublicKey, number] {
+  const chainIdBytes = Buffer.alloc(4);
+  chainIdBytes.writeUInt32LE(chainId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("chain_config"), chainIdBytes],
+    programId
+  );
+}
+
 async function getMXEPublicKeyWithRetry(
   provider: anchor.AnchorProvider,
   programId: PublicKey,
@@ -695,3 +1203,14 @@ function readKpJson(path: string): anchor.web3.Keypair {
     new Uint8Array(JSON.parse(file.toString()))
   );
 }
+
+function getFillPda(
+  programId: PublicKey,
+  intent: PublicKey,
+  solverAuthority: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fill"), intent.toBuffer(), solverAuthority.toBuffer()],
+    programId
+  );
+}